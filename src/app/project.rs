@@ -0,0 +1,245 @@
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::elements::images::image::IMAGE_EXTENSIONS;
+use crate::models::site::Site;
+use crate::utils::file_io;
+
+// Текущая версия формата файла проекта.
+// Увеличивается при любом несовместимом изменении структуры сохраняемых данных.
+pub const PROJECT_FORMAT_VERSION: u32 = 1;
+
+// Расширение файла проекта по умолчанию
+pub const PROJECT_EXTENSION: &str = "sgproj";
+
+// Папка ресурсов проекта
+pub const ASSETS_DIR: &str = "assets";
+
+#[derive(Serialize)]
+struct ProjectFileRef<'a> {
+    format_version: u32,
+    site: &'a Site,
+}
+
+#[derive(Deserialize)]
+struct ProjectFile {
+    site: Site,
+}
+
+// Сохраняет сайт в файл проекта. Файлы ресурсов, которые лежат вне папки assets
+// (выбраны до первого сохранения или остались в папке прежнего файла проекта), копируются
+// в нее, и после успешной записи ссылки на них в site заменяются путями от папки проекта
+pub fn save_project(site: &mut Site, old_path: Option<&Path>, path: &Path) -> io::Result<()> {
+    let mut saved = site.clone();
+    localize_assets(&mut saved, old_path.map(project_dir), project_dir(path))?;
+
+    // Сохраняются основные значения элементов, отличия ширин - в их overrides
+    let file = ProjectFileRef {
+        format_version: PROJECT_FORMAT_VERSION,
+        site: &saved.with_base_breakpoint(),
+    };
+    file_io::write_json(path, &file)?;
+
+    *site = saved;
+    Ok(())
+}
+
+// Загружает сайт из файла проекта, проверяя версию формата
+pub fn load_project(path: &Path) -> io::Result<Site> {
+    let value: serde_json::Value = file_io::read_json(path)?;
+
+    let version = value.get("format_version")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| invalid_data("Файл не является проектом: нет версии формата".to_string()))?;
+
+    if version > PROJECT_FORMAT_VERSION as u64 {
+        return Err(invalid_data(format!(
+            "Проект сохранен более новой версией программы (формат {}, поддерживается до {})",
+            version, PROJECT_FORMAT_VERSION
+        )));
+    }
+
+    let file: ProjectFile = serde_json::from_value(value)
+        .map_err(|e| invalid_data(format!("Не удалось прочитать проект: {}", e)))?;
+
+    let mut site = file.site;
+    if site.pages.is_empty() {
+        site = Site { settings: site.settings, ..Site::new() };
+    }
+    Ok(site)
+}
+
+// Импортирует файл изображения в проект и возвращает путь, который хранится в элементе.
+// У сохраненного проекта файл копируется в папку assets рядом с файлом проекта и
+// хранится путь от папки проекта; у еще не сохраненного - абсолютный исходный путь,
+// файл будет скопирован при первом сохранении
+pub fn import_asset(project_path: Option<&Path>, source: &Path) -> io::Result<String> {
+    let extension = source.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if !IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Неподдерживаемый формат изображения (ожидается {})", IMAGE_EXTENSIONS.join(", "))
+        ));
+    }
+
+    let source = source.canonicalize()?;
+    match project_path {
+        Some(project_path) => localize_asset(&source, project_dir(project_path)),
+        None => Ok(source.display().to_string()),
+    }
+}
+
+// Путь к файлу ресурса на диске. Пути в проекте отсчитываются от папки проекта
+// (project_dir), абсолютные пути файлов несохраненного проекта не меняются
+pub fn resolve_asset(project_dir: Option<&Path>, path: &str) -> PathBuf {
+    let path = Path::new(path);
+    match project_dir {
+        Some(dir) if path.is_relative() => dir.join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+// Папка проекта - папка, в которой лежит файл проекта
+fn project_dir(project_path: &Path) -> &Path {
+    project_path.parent().unwrap_or(Path::new(""))
+}
+
+// Переносит ресурсы всех элементов сайта и иконку сайта в папку assets проекта project_dir.
+// Относительные пути отсчитываются от прежней папки проекта old_dir.
+// Ссылки на отсутствующие файлы остаются как есть
+fn localize_assets(site: &mut Site, old_dir: Option<&Path>, project_dir: &Path) -> io::Result<()> {
+    // Каждый файл копируется один раз, даже если на него ссылаются несколько элементов
+    let mut localized: HashMap<String, String> = HashMap::new();
+    let mut error = None;
+    let mut localize = |path: &str| -> String {
+        if let Some(local) = localized.get(path) {
+            return local.clone();
+        }
+        let source = resolve_asset(old_dir, path);
+        if error.is_some() || !source.exists() {
+            return path.to_string();
+        }
+        match localize_asset(&source, project_dir) {
+            Ok(local) => {
+                localized.insert(path.to_string(), local.clone());
+                local
+            },
+            Err(e) => {
+                error = Some(e);
+                path.to_string()
+            },
+        }
+    };
+
+    for page in &mut site.pages {
+        for element in &mut page.elements {
+            element.map_assets(&mut localize);
+        }
+    }
+    if let Some(favicon) = site.settings.favicon.as_mut() {
+        *favicon = localize(favicon);
+    }
+    error.map_or(Ok(()), Err)
+}
+
+// Копирует файл в папку assets проекта, если его там еще нет, и возвращает путь к нему
+// от папки проекта (с разделителем /, чтобы проект открывался на любой системе)
+fn localize_asset(source: &Path, project_dir: &Path) -> io::Result<String> {
+    let assets_dir = project_dir.join(ASSETS_DIR);
+    fs::create_dir_all(&assets_dir)?;
+    let assets_dir = assets_dir.canonicalize()?;
+
+    let source = source.canonicalize()?;
+    let target = if source.starts_with(&assets_dir) {
+        source
+    } else {
+        file_io::copy_to_dir_unique(&source, &assets_dir)?
+    };
+
+    let relative = target.strip_prefix(&assets_dir)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let mut parts = vec![ASSETS_DIR.to_string()];
+    parts.extend(relative.components().map(|part| part.as_os_str().to_string_lossy().to_string()));
+    Ok(parts.join("/"))
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::UIElement;
+    use crate::elements::images::image::Image;
+
+    #[test]
+    fn first_save_copies_external_assets_into_project() {
+        let dir = std::env::temp_dir().join(format!("project-assets-{}", std::process::id()));
+        fs::create_dir_all(dir.join("pictures")).unwrap();
+        fs::write(dir.join("pictures/logo.png"), "png").unwrap();
+        fs::write(dir.join("pictures/favicon.ico"), "ico").unwrap();
+
+        let mut site = Site::new();
+        site.settings.favicon = Some(dir.join("pictures/favicon.ico").display().to_string());
+        let mut image = Image::new();
+        image.source = import_asset(None, &dir.join("pictures/logo.png")).unwrap();
+        assert!(Path::new(&image.source).is_absolute());
+        site.current_page_mut().add_element(Box::new(image));
+
+        let project_path = dir.join("site").join("site.sgproj");
+        save_project(&mut site, None, &project_path).unwrap();
+
+        let image = site.current_page().elements[0].as_any().downcast_ref::<Image>().unwrap();
+        assert_eq!(image.source, "assets/logo.png");
+        assert!(resolve_asset(project_path.parent(), &image.source).exists());
+        assert_eq!(site.settings.favicon.as_deref(), Some("assets/favicon.ico"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn saved_project_loads_back_unchanged() {
+        crate::elements::registry::register_builtin_elements();
+        let dir = std::env::temp_dir().join(format!("project-round-trip-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("photo.png"), "png").unwrap();
+
+        let mut site = Site::new();
+        site.settings.name = "Портфолио".to_string();
+        site.settings.palette.push("#123456".to_string());
+        site.add_page();
+        site.current_page_mut().title = "Контакты".to_string();
+
+        let mut image = Image::new();
+        image.source = import_asset(None, &dir.join("photo.png")).unwrap();
+        image.set_position((10.0, 20.0));
+        site.current_page_mut().add_element(Box::new(image));
+
+        // Сохранение на другой ширине: основные значения и отличия должны сохраниться порознь
+        site.set_breakpoint(Some("mobile".to_string()));
+        site.current_page_mut().elements[0].set_position((5.0, 40.0));
+
+        let project_path = dir.join("site.sgproj");
+        save_project(&mut site, None, &project_path).unwrap();
+        let loaded = load_project(&project_path).unwrap();
+
+        assert_eq!(loaded.settings, site.settings);
+        assert_eq!(loaded.pages.len(), 2);
+        assert_eq!(loaded.pages[1].title, "Контакты");
+        let element = &loaded.pages[1].elements[0];
+        let image = element.as_any().downcast_ref::<Image>().unwrap();
+        assert_eq!(image.source, "assets/photo.png");
+        assert_eq!(element.get_position(), (10.0, 20.0));
+        assert_eq!(element.base().overrides["mobile"].position, Some((5.0, 40.0)));
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(site.with_base_breakpoint().as_ref()).unwrap(),
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,284 +1,929 @@
+mod alignment;
+mod canvas;
+mod clipboard;
+mod file_dialog;
+mod history;
+mod layers;
+mod palette;
+mod resize;
+mod rulers;
+mod snapping;
+mod viewport;
+
+use crate::app::project;
 use crate::elements::ElementType;
 use crate::elements::UIElement;
-use crate::elements::buttons::button::Button;
-use crate::models::page::Page;
-use egui::{Ui, Context, Color32};
+use crate::models::page::{self, Page};
+use crate::models::site::{Breakpoint, Site, SiteSettings};
+use crate::utils::html_generator::HtmlGenerator;
+use egui::{Ui, Context, Color32, Key, KeyboardShortcut, Modifiers};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use file_dialog::{FileAction, FileDialog, FileDialogResult};
+use history::{EditCommand, History, PageNames};
+use canvas::{CanvasDrag, ElementSnapshot};
+use palette::PaletteDrag;
+use snapping::GridSettings;
+use viewport::Viewport;
 
-// Импортируем ButtonProperty
+// Импортируем редакторы свойств
 use crate::elements::buttons::buttonProperty::ButtonProperty;
+use crate::elements::color_picker::{self, ColorPicker};
+use crate::elements::containers::container_property::ContainerProperty;
+use crate::elements::forms::form_property::{FormFieldProperty, FormProperty};
+use crate::elements::layout::FlowLayout;
+use crate::elements::images::image_property::ImageProperty;
+use crate::elements::links::link_property::LinkProperty;
+use crate::elements::links::link_target::PageInfo;
+use crate::elements::property_inspector::PropertyInspector;
+use crate::elements::texts::text_property::TextProperty;
+
+// Действие со списком страниц
+#[derive(Clone, Copy)]
+enum PageAction {
+    Add,
+    Duplicate,
+    MoveUp,
+    MoveDown,
+    Remove,
+}
+
+// Ширина страницы на холсте для выбранной ширины экрана (без контрольных точек - по ширине холста)
+fn canvas_page_width(site: &Site) -> f32 {
+    site.settings.breakpoint(site.active_breakpoint.as_deref())
+        .map(|b| b.width)
+        .unwrap_or(f32::INFINITY)
+}
 
 // Структура редактора
+#[derive(Default)]
 pub struct Editor {
-    // Выбранный элемент для редактирования
-    selected_element_id: Option<String>,
-    // Выбранный тип элемента для добавления
-    selected_element_type: Option<ElementType>,
-    // Перетаскивание элемента
-    dragging_new_element: bool,
-    // Позиция мыши
-    mouse_pos: Option<(f32, f32)>,
+    // Выделенные элементы; последний - основной
+    selected_ids: Vec<String>,
+    // Строка поиска в палитре элементов
+    palette_search: String,
+    // Элемент, перетаскиваемый из палитры
+    palette_drag: Option<PaletteDrag>,
+    // Новый стиль для всех выделенных элементов: свойство и значение
+    new_style_key: String,
+    new_style_value: String,
+    // Скопированные элементы (позиции в координатах страницы)
+    clipboard: Vec<Box<dyn UIElement>>,
+    // Сколько раз подряд вставлен буфер: каждая вставка смещается дальше
+    paste_count: u32,
+    // Точка правого клика, куда вставляются элементы из контекстного меню
+    context_menu_pos: Option<egui::Pos2>,
+    // Сетка и привязка на холсте
+    grid: GridSettings,
+    // Ширина экрана, выбранная на холсте (None - основная); совпадает с Site::active_breakpoint
+    breakpoint: Option<String>,
+    // Ширина экрана, на которой скопирован буфер
+    clipboard_breakpoint: Option<String>,
+    // Прокрутка и масштаб холста
+    viewport: Viewport,
+    // Текущее перетаскивание на холсте
+    canvas_drag: Option<CanvasDrag>,
+    // Состояние перетаскиваемых элементов до начала перетаскивания
+    drag_snapshots: Vec<ElementSnapshot>,
+    // Слой, перетаскиваемый в панели слоев
+    layer_drag: Option<String>,
+    // Свернутые в панели слоев контейнеры
+    collapsed_layers: HashSet<String>,
+    // История изменений для отмены и повтора
+    history: History,
     // Свойства кнопки
     button_property: ButtonProperty,
-}
-
-impl Default for Editor {
-    fn default() -> Self {
-        Self {
-            selected_element_id: None,
-            selected_element_type: None,
-            dragging_new_element: false,
-            mouse_pos: None,
-            button_property: ButtonProperty::new(),
-        }
-    }
+    // Свойства текста
+    text_property: TextProperty,
+    // Свойства изображения
+    image_property: ImageProperty,
+    // Свойства контейнера
+    container_property: ContainerProperty,
+    // Свойства формы
+    form_property: FormProperty,
+    // Свойства поля формы
+    form_field_property: FormFieldProperty,
+    // Свойства ссылки
+    link_property: LinkProperty,
+    // Общий инспектор свойств по схеме элемента
+    property_inspector: PropertyInspector,
+    // Выбранный элемент до правки в панели свойств и номер состояния истории, когда он снят
+    property_snapshot: Option<(u64, Box<dyn UIElement>)>,
+    // Открыто ли окно настроек сайта
+    show_site_settings: bool,
+    // Палитры для цветов сайта в окне настроек
+    site_color_pickers: Vec<ColorPicker>,
+    // Путь к файлу текущего проекта
+    project_path: Option<PathBuf>,
+    // Папка последнего экспорта
+    export_path: Option<PathBuf>,
+    // Открытый диалог выбора пути
+    file_dialog: Option<FileDialog>,
+    // Сообщение о результате последней операции (текст, признак ошибки)
+    status_message: Option<(String, bool)>,
 }
 
 impl Editor {
     pub fn new() -> Self {
         Self {
-            selected_element_id: None,
-            selected_element_type: Some(ElementType::Button), // По умолчанию выбран тип "Кнопка"
-            dragging_new_element: false,
-            mouse_pos: None,
+            selected_ids: Vec::new(),
+            palette_search: String::new(),
+            palette_drag: None,
+            new_style_key: String::new(),
+            new_style_value: String::new(),
+            clipboard: Vec::new(),
+            paste_count: 0,
+            context_menu_pos: None,
+            grid: GridSettings::default(),
+            breakpoint: None,
+            clipboard_breakpoint: None,
+            viewport: Viewport::default(),
+            canvas_drag: None,
+            drag_snapshots: Vec::new(),
+            layer_drag: None,
+            collapsed_layers: HashSet::new(),
+            history: History::new(),
             button_property: ButtonProperty::new(),
+            text_property: TextProperty::new(),
+            image_property: ImageProperty::new(),
+            container_property: ContainerProperty::new(),
+            form_property: FormProperty::new(),
+            form_field_property: FormFieldProperty::new(),
+            link_property: LinkProperty::new(),
+            property_inspector: PropertyInspector::new(),
+            property_snapshot: None,
+            show_site_settings: false,
+            site_color_pickers: Vec::new(),
+            project_path: None,
+            export_path: None,
+            file_dialog: None,
+            status_message: None,
         }
     }
     
     // Основной метод отображения редактора
-    pub fn show(&mut self, ctx: &Context, _ui: &mut Ui, page: &mut Page) {
+    pub fn show(&mut self, ctx: &Context, _ui: &mut Ui, site: &mut Site) {
+        self.handle_history_shortcuts(ctx, site);
+        
         egui::TopBottomPanel::top("toolbar").show(ctx, |ui| {
-            self.show_toolbar(ui, page);
+            self.show_toolbar(ui, site);
         });
         
+        self.show_site_settings_window(ctx, site);
+        self.show_file_dialog(ctx, site);
+        color_picker::set_site_palette(ctx, &site.settings.palette);
+        
         egui::SidePanel::right("properties").resizable(true).min_width(300.0).show(ctx, |ui| {
-            self.show_properties(ui, page);
+            // Страницы сайта нужны только для выбора цели ссылки у кнопки или ссылки
+            let pages = if self.link_selected(site.current_page()) {
+                PageInfo::collect(site)
+            } else {
+                Vec::new()
+            };
+            let breakpoint_name = site.active_breakpoint.as_deref()
+                .and_then(|id| site.settings.breakpoint(Some(id)))
+                .map(|b| b.name.clone());
+            self.show_properties(ui, site.current_page_mut(), &pages, breakpoint_name.as_deref());
         });
         
         egui::SidePanel::left("elements").resizable(true).min_width(200.0).show(ctx, |ui| {
-            self.show_elements_panel(ui, page);
+            self.show_pages_panel(ui, site);
+            ui.separator();
+            self.show_elements_panel(ui, site.current_page_mut());
+            ui.separator();
+            self.show_layers_panel(ui, site.current_page_mut());
         });
         
         egui::CentralPanel::default().show(ctx, |ui| {
-            self.show_editor_area(ui, page);
+            let page_width = canvas_page_width(site);
+            self.show_editor_area(ui, site.current_page_mut(), page_width);
+        });
+        
+        // Отпускание мыши завершает непрерывное изменение (ползунок, перетаскивание)
+        if ctx.input(|i| i.pointer.any_released()) {
+            self.history.seal();
+        }
+    }
+    
+    // Ctrl+Z - отмена, Ctrl+Shift+Z или Ctrl+Y - повтор
+    fn handle_history_shortcuts(&mut self, ctx: &Context, site: &mut Site) {
+        // Пока редактируется текст, сочетания клавиш обрабатывает поле ввода
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        
+        let redo = ctx.input_mut(|i| {
+            i.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z))
+                || i.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND, Key::Y))
         });
+        if redo {
+            self.redo(site);
+        } else if ctx.input_mut(|i| i.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND, Key::Z))) {
+            self.undo(site);
+        }
+    }
+    
+    fn undo(&mut self, site: &mut Site) {
+        if self.history.undo(site) {
+            self.breakpoint = site.active_breakpoint.clone();
+            self.forget_missing_selection(site);
+        }
+    }
+    
+    fn redo(&mut self, site: &mut Site) {
+        if self.history.redo(site) {
+            self.breakpoint = site.active_breakpoint.clone();
+            self.forget_missing_selection(site);
+        }
+    }
+    
+    // Переключает холст на ширину экрана (None - основная)
+    fn select_breakpoint(&mut self, site: &mut Site, breakpoint_id: Option<String>) {
+        site.set_breakpoint(breakpoint_id);
+        self.breakpoint = site.active_breakpoint.clone();
+        self.history.set_breakpoint(self.breakpoint.clone());
+    }
+    
+    // Убирает из выделения элементы, которых больше нет на текущей странице
+    fn forget_missing_selection(&mut self, site: &Site) {
+        let page = site.current_page();
+        self.selected_ids.retain(|id| page.find_element(id).is_some());
     }
     
     // Показать панель инструментов
-    fn show_toolbar(&mut self, ui: &mut Ui, page: &mut Page) {
+    fn show_toolbar(&mut self, ui: &mut Ui, site: &mut Site) {
         ui.horizontal(|ui| {
             ui.heading("Генератор сайтов");
             
             ui.separator();
             
+            if ui.button("Открыть").clicked() {
+                self.open_file_dialog(FileAction::OpenProject);
+            }
+            
             if ui.button("Сохранить").clicked() {
-                // Логика сохранения
-                println!("Сохранение проекта");
+                match self.project_path.clone() {
+                    Some(path) => self.save_project(site, path),
+                    None => self.open_file_dialog(FileAction::SaveProject),
+                }
+            }
+            
+            if ui.button("Сохранить как").clicked() {
+                self.open_file_dialog(FileAction::SaveProject);
             }
             
             if ui.button("Экспорт").clicked() {
-                // Логика экспорта
-                println!("Экспорт проекта");
+                self.open_file_dialog(FileAction::ExportSite);
+            }
+            
+            if ui.button("Настройки сайта").clicked() {
+                self.show_site_settings = !self.show_site_settings;
             }
             
             ui.separator();
             
-            ui.label("Страница:");
-            ui.text_edit_singleline(&mut page.title);
-        });
-    }
-    
-    fn show_elements_panel(&mut self, ui: &mut Ui, _page: &mut Page) {
-        ui.heading("Элементы");
-        
-        ui.separator();
-        
-        ui.vertical(|ui| {
-            // Настраиваем сенсор для кнопки, чтобы явно разрешить определение перетаскивания
-            let response = ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
-                ui.add(egui::Button::new("Кнопка").sense(egui::Sense::click_and_drag()))
-            }).inner;
+            ui.menu_button("Сетка", |ui| {
+                ui.checkbox(&mut self.grid.visible, "Показывать сетку");
+                ui.checkbox(&mut self.grid.snap_to_grid, "Привязка к сетке");
+                ui.horizontal(|ui| {
+                    ui.label("Шаг:");
+                    ui.add(egui::DragValue::new(&mut self.grid.size).clamp_range(2.0..=200.0).suffix(" px"));
+                });
+                ui.checkbox(&mut self.grid.smart_guides, "Умные направляющие");
+                ui.label("Alt при перетаскивании отключает привязку");
+            });
             
-            // Отслеживаем наведение курсора на элемент (сделаем вывод только при изменении)
-            static mut WAS_HOVERED: bool = false;
-            if response.hovered() {
-                if unsafe { !WAS_HOVERED } {
-                    println!("Пользователь навелся на элемент в списке: Кнопка");
-                    unsafe { WAS_HOVERED = true; }
-                }
-            } else {
-                unsafe { WAS_HOVERED = false; }
+            // Масштаб холста
+            if ui.button("−").on_hover_text("Уменьшить (Ctrl+-)").clicked() {
+                self.zoom_by(1.0 / viewport::ZOOM_STEP);
+            }
+            ui.label(format!("{:.0}%", self.viewport.zoom() * 100.0));
+            if ui.button("+").on_hover_text("Увеличить (Ctrl+=)").clicked() {
+                self.zoom_by(viewport::ZOOM_STEP);
+            }
+            if ui.button("100%").on_hover_text("Ctrl+0").clicked() {
+                self.viewport.reset_zoom();
+            }
+            if ui.button("По размеру").on_hover_text("Вся страница (Shift+1)").clicked() {
+                self.zoom_to_fit(site.current_page(), canvas_page_width(site));
             }
             
-            // Обрабатываем клик на кнопке
-            if response.clicked() {
-                self.selected_element_type = Some(ElementType::Button);
-                println!("Пользователь кликнул на элемент в списке: Кнопка");
+            ui.separator();
+            
+            // Переключатель ширины холста: основная ширина и более узкие экраны
+            ui.label("Ширина:");
+            let settings = &site.settings;
+            let mut clicked_breakpoint = None;
+            for breakpoint in settings.base_breakpoint().into_iter().chain(settings.narrower_breakpoints()) {
+                let is_base = Some(breakpoint) == settings.base_breakpoint();
+                let selected = if is_base {
+                    self.breakpoint.is_none()
+                } else {
+                    self.breakpoint.as_deref() == Some(breakpoint.id.as_str())
+                };
+                let label = format!("{} {}px", breakpoint.name, breakpoint.width);
+                if ui.selectable_label(selected, label).clicked() && !selected {
+                    clicked_breakpoint = Some((!is_base).then(|| breakpoint.id.clone()));
+                }
+            }
+            if let Some(breakpoint_id) = clicked_breakpoint {
+                self.select_breakpoint(site, breakpoint_id);
             }
             
-            // Проверяем состояние мыши напрямую
-            let is_button_down = ui.input(|i| i.pointer.primary_down());
-            let is_dragging = ui.input(|i| i.pointer.any_down() && i.pointer.is_moving());
+            ui.separator();
             
-            // Обнаружение начала перетаскивания
-            if response.hovered() && is_button_down && !self.dragging_new_element {
-                self.dragging_new_element = true;
-                self.selected_element_type = Some(ElementType::Button);
-                println!("Пользователь зажал ЛКМ на элементе в списке: Кнопка");
+            if ui.add_enabled(self.history.can_undo(), egui::Button::new("Отменить")).clicked() {
+                self.undo(site);
             }
             
-            // Проверяем перетаскивание
-            if self.dragging_new_element && is_dragging {
-                if let Some(pos) = ui.ctx().pointer_interact_pos() {
-                    self.mouse_pos = Some((pos.x, pos.y));
-                    println!("Перетаскивание элемента Кнопка на позицию: ({:.1}, {:.1})", pos.x, pos.y);
-                }
+            if ui.add_enabled(self.history.can_redo(), egui::Button::new("Повторить")).clicked() {
+                self.redo(site);
+            }
+            
+            if let Some((message, is_error)) = &self.status_message {
+                ui.separator();
+                let color = if *is_error { Color32::RED } else { ui.visuals().text_color() };
+                ui.colored_label(color, message);
             }
         });
     }
     
-    // Показать редактор свойств
-    fn show_properties(&mut self, ui: &mut Ui, page: &mut Page) {
-        ui.heading("Свойства");
+    // Открывает диалог выбора пути, подставляя путь текущего проекта
+    fn open_file_dialog(&mut self, action: FileAction) {
+        let default_path = match action {
+            FileAction::ExportSite => self.export_path.as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| "site".to_string()),
+            _ => self.project_path.as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| format!("site.{}", project::PROJECT_EXTENSION)),
+        };
+        self.file_dialog = Some(FileDialog::new(action, default_path));
+    }
+    
+    // Показывает диалог выбора пути и выполняет выбранное действие
+    fn show_file_dialog(&mut self, ctx: &Context, site: &mut Site) {
+        let Some(dialog) = self.file_dialog.as_mut() else {
+            return;
+        };
+        
+        match dialog.show(ctx) {
+            FileDialogResult::None => {}
+            FileDialogResult::Cancelled => self.file_dialog = None,
+            FileDialogResult::Confirmed(path) => {
+                let action = dialog.action;
+                self.file_dialog = None;
+                match action {
+                    FileAction::OpenProject => self.open_project(site, path),
+                    FileAction::SaveProject => self.save_project(site, path),
+                    FileAction::ExportSite => self.export_site(site, path),
+                }
+            }
+        }
+    }
+    
+    // Сохраняет проект и запоминает путь к нему
+    fn save_project(&mut self, site: &mut Site, path: PathBuf) {
+        match project::save_project(site, self.project_path.as_deref(), &path) {
+            Ok(()) => {
+                // Пути к ресурсам элементов могли измениться
+                self.property_snapshot = None;
+                self.status_message = Some((format!("Проект сохранен: {}", path.display()), false));
+                self.project_path = Some(path);
+            }
+            Err(e) => {
+                self.status_message = Some((format!("Ошибка сохранения: {}", e), true));
+            }
+        }
+    }
+    
+    // Экспортирует сайт в выбранную папку
+    fn export_site(&mut self, site: &Site, path: PathBuf) {
+        let project_dir = self.project_path.as_deref().and_then(Path::parent);
+        match HtmlGenerator::export_project(site, project_dir, &path) {
+            Ok(warnings) if warnings.is_empty() => {
+                self.status_message = Some((format!("Сайт экспортирован: {}", path.display()), false));
+                self.export_path = Some(path);
+            }
+            // Отсутствующие файлы не прерывают экспорт, но о них нужно сообщить
+            Ok(warnings) => {
+                self.status_message = Some((format!(
+                    "Сайт экспортирован: {}, пропущено файлов: {} ({})",
+                    path.display(), warnings.len(), warnings.join("; ")
+                ), true));
+                self.export_path = Some(path);
+            }
+            Err(e) => {
+                self.status_message = Some((format!("Ошибка экспорта: {}", e), true));
+            }
+        }
+    }
+    
+    // Загружает проект, заменяя текущий сайт
+    fn open_project(&mut self, site: &mut Site, path: PathBuf) {
+        match project::load_project(&path) {
+            Ok(loaded) => {
+                *site = loaded;
+                self.selected_ids.clear();
+                self.history.clear();
+                self.breakpoint = None;
+                self.status_message = Some((format!("Проект открыт: {}", path.display()), false));
+                self.project_path = Some(path);
+            }
+            Err(e) => {
+                self.status_message = Some((format!("Ошибка открытия: {}", e), true));
+            }
+        }
+    }
+    
+    // Окно общих настроек сайта
+    fn show_site_settings_window(&mut self, ctx: &Context, site: &mut Site) {
+        let settings = &mut site.settings;
+        let before = settings.clone();
+        let mut removed_breakpoint = None;
         
-        if let Some(element_id) = &self.selected_element_id {
-            if let Some(element) = page.find_element_mut(element_id) {
+        egui::Window::new("Настройки сайта")
+            .open(&mut self.show_site_settings)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("site_settings_grid").num_columns(2).show(ui, |ui| {
+                    ui.label("Название:");
+                    ui.text_edit_singleline(&mut settings.name);
+                    ui.end_row();
+                    
+                    ui.label("Базовый URL:");
+                    ui.text_edit_singleline(&mut settings.base_url);
+                    ui.end_row();
+                    
+                    ui.label("Язык по умолчанию:");
+                    ui.text_edit_singleline(&mut settings.default_language);
+                    ui.end_row();
+                    
+                    // Пустая строка означает отсутствие иконки
+                    ui.label("Иконка (favicon):");
+                    let mut favicon = settings.favicon.clone().unwrap_or_default();
+                    if ui.text_edit_singleline(&mut favicon).changed() {
+                        settings.favicon = if favicon.trim().is_empty() { None } else { Some(favicon) };
+                    }
+                    ui.end_row();
+                });
+                
                 ui.separator();
+                removed_breakpoint = Self::show_breakpoint_settings(ui, settings);
                 
-                match element.get_element_type() {
-                    ElementType::Button => {
-                        // Используем ButtonProperty для отображения свойств кнопки
-                        self.button_property.show(ui, element);
-                    },
-                    _ => {
-                        ui.label("Редактирование этого типа элемента пока не поддерживается");
-                    }
+                ui.separator();
+                Self::show_palette_settings(ui, settings, &mut self.site_color_pickers);
+            });
+        
+        // Удаленная ширина больше не может быть выбрана на холсте
+        if let Some(active) = &self.breakpoint
+            && !site.settings.narrower_breakpoints().iter().any(|b| b.id == *active) {
+            self.select_breakpoint(site, None);
+        }
+        
+        // Отличия элементов на удаленной ширине убираются тем же шагом истории,
+        // чтобы отмена вернула и ширину, и отличия
+        if let Some(breakpoint_id) = removed_breakpoint {
+            let overrides = site.take_breakpoint_overrides(&breakpoint_id);
+            self.history.push(EditCommand::RemoveBreakpoint {
+                before,
+                after: site.settings.clone(),
+                breakpoint_id,
+                overrides,
+            });
+        } else if site.settings != before {
+            let after = site.settings.clone();
+            self.history.push_merged("settings".to_string(), EditCommand::UpdateSettings { before, after });
+        }
+    }
+    
+    // Список ширин экрана: первая - основная, остальные уже нее.
+    // Возвращает идентификатор удаленной ширины
+    fn show_breakpoint_settings(ui: &mut Ui, settings: &mut SiteSettings) -> Option<String> {
+        ui.label("Ширины экрана (первая - основная):");
+        
+        let base_width = settings.base_breakpoint().map(|b| b.width).unwrap_or(f32::INFINITY);
+        let mut remove = None;
+        egui::Grid::new("breakpoints_grid").num_columns(3).show(ui, |ui| {
+            for (index, breakpoint) in settings.breakpoints.iter_mut().enumerate() {
+                ui.text_edit_singleline(&mut breakpoint.name);
+                // Остальные ширины должны быть меньше основной
+                let range = if index == 0 { 320.0..=3840.0 } else { 200.0..=(base_width - 1.0).max(200.0) };
+                ui.add(egui::DragValue::new(&mut breakpoint.width).speed(1.0).clamp_range(range).suffix(" px"));
+                if index > 0 && ui.small_button("✕").clicked() {
+                    remove = Some(index);
                 }
-            } else {
-                ui.label("Элемент не найден");
-                self.selected_element_id = None;
+                ui.end_row();
             }
-        } else {
-            ui.label("Выберите элемент для редактирования");
+        });
+        
+        if ui.button("Добавить ширину").clicked() {
+            let width = settings.narrower_breakpoints().last().map(|b| b.width).unwrap_or(base_width);
+            settings.breakpoints.push(Breakpoint::new("Новая ширина".to_string(), (width / 2.0).round().max(200.0)));
         }
+        remove.map(|index| settings.breakpoints.remove(index).id)
     }
     
-    // Показать область редактирования
-    fn show_editor_area(&mut self, ui: &mut Ui, page: &mut Page) {
-        // Отладочный вывод состояния перетаскивания
-        if self.dragging_new_element {
-            println!("Перетаскивание активно: {:?}", self.mouse_pos);
-        }
+    // Цвета сайта: образцы в палитрах выбора цвета всех редакторов свойств
+    fn show_palette_settings(ui: &mut Ui, settings: &mut SiteSettings, pickers: &mut Vec<ColorPicker>) {
+        ui.label("Цвета сайта:");
         
-        let (response, painter) = ui.allocate_painter(
-            ui.available_size(),
-            egui::Sense::click_and_drag()
-        );
+        pickers.resize_with(settings.palette.len(), ColorPicker::new);
+        let mut remove = None;
+        for (index, (color, picker)) in settings.palette.iter_mut().zip(pickers.iter_mut()).enumerate() {
+            ui.horizontal(|ui| {
+                if let Some(new_color) = picker.show(ui, Some(color)) {
+                    *color = new_color;
+                }
+                if ui.small_button("✕").clicked() {
+                    remove = Some(index);
+                }
+            });
+        }
         
-        let rect = response.rect;
+        if let Some(index) = remove {
+            settings.palette.remove(index);
+            pickers.remove(index);
+        }
+        if ui.button("Добавить цвет").clicked() {
+            settings.palette.push("#000000".to_string());
+        }
+    }
+    
+    // Панель страниц сайта: выбор, добавление, дублирование, порядок и переименование
+    fn show_pages_panel(&mut self, ui: &mut Ui, site: &mut Site) {
+        ui.heading("Страницы");
         
-        // Отрисовываем фон рабочей области
-        painter.rect_filled(rect, 0.0, Color32::WHITE);
+        ui.separator();
         
-        // Получаем текущую позицию мыши
-        if let Some(pos) = ui.ctx().pointer_interact_pos() {
-            self.mouse_pos = Some((pos.x, pos.y));
+        let mut clicked_page = None;
+        for (index, page) in site.pages.iter().enumerate() {
+            let label = format!("{} ({})", page.title, page.file_name);
+            if ui.selectable_label(index == site.current_page, label).clicked() {
+                clicked_page = Some(index);
+            }
         }
         
-        // Отрисовываем элементы страницы
-        for element in &page.elements {
-            let selected = Some(element.get_id().to_string()) == self.selected_element_id;
-            element.render(&painter, selected);
+        if let Some(index) = clicked_page
+            && index != site.current_page {
+            site.select_page(index);
+            self.selected_ids.clear();
         }
         
-        // Если перетаскиваем новый элемент, отображаем его предпросмотр
-        if self.dragging_new_element && !ui.input(|i| i.pointer.primary_down()) {
-            println!("Пользователь завершил перетаскивание элемента");
-            if let Some(pos) = self.mouse_pos {
-                // Проверяем, что позиция находится внутри области редактирования
-                if rect.contains(egui::Pos2::new(pos.0, pos.1)) {
-                    match self.selected_element_type {
-                        Some(ElementType::Button) => {
-                            // Создаем новую кнопку
-                            let mut button = Button::new();
-                            // Устанавливаем позицию, учитывая центр кнопки
-                            button.set_position((pos.0 - 50.0, pos.1 - 25.0));
-                            // Добавляем кнопку на страницу
-                            page.add_element(Box::new(button));
-                            println!("Добавлена новая кнопка в позиции ({:.1}, {:.1})", pos.0, pos.1);
-                        },
-                        _ => {
-                            // Здесь будет логика для других типов элементов
-                        }
-                    }
-                } else {
-                    println!("Перетаскивание завершено вне области редактирования");
+        let current = site.current_page;
+        let page_count = site.pages.len();
+        
+        let mut action = None;
+        ui.horizontal_wrapped(|ui| {
+            if ui.button("Добавить").clicked() {
+                action = Some(PageAction::Add);
+            }
+            
+            if ui.button("Дублировать").clicked() {
+                action = Some(PageAction::Duplicate);
+            }
+            
+            if ui.add_enabled(current > 0, egui::Button::new("Выше")).clicked() {
+                action = Some(PageAction::MoveUp);
+            }
+            
+            if ui.add_enabled(current + 1 < page_count, egui::Button::new("Ниже")).clicked() {
+                action = Some(PageAction::MoveDown);
+            }
+            
+            if ui.add_enabled(page_count > 1, egui::Button::new("Удалить")).clicked() {
+                action = Some(PageAction::Remove);
+            }
+        });
+        
+        // В историю записывается только измененная страница
+        if let Some(action) = action {
+            let inserted = |site: &Site, index: usize| EditCommand::InsertPage {
+                index,
+                page: site.pages[index].clone(),
+                before_current: current,
+            };
+            let command = match action {
+                PageAction::Add => {
+                    let index = site.add_page();
+                    Some(inserted(site, index))
+                },
+                PageAction::Duplicate => site.duplicate_page(current).map(|index| inserted(site, index)),
+                PageAction::MoveUp => {
+                    site.move_page(current, current - 1);
+                    Some(EditCommand::MovePage { from: current, to: current - 1 })
+                },
+                PageAction::MoveDown => {
+                    site.move_page(current, current + 1);
+                    Some(EditCommand::MovePage { from: current, to: current + 1 })
+                },
+                PageAction::Remove => {
+                    let page = site.current_page().clone();
+                    site.remove_page(current).then_some(EditCommand::RemovePage { index: current, page })
+                },
+            };
+            
+            if let Some(command) = command {
+                if !matches!(action, PageAction::MoveUp | PageAction::MoveDown) {
+                    self.selected_ids.clear();
                 }
+                self.history.push(command);
             }
-            // Завершаем перетаскивание
-            self.dragging_new_element = false;
         }
         
-        // Обработка событий мыши
-        if response.clicked() {
-            // Клик мыши
-            let pos = response.interact_pointer_pos.unwrap();
-            let click_pos = (pos.x, pos.y);
+        // Переименование текущей страницы
+        let page = site.current_page_mut();
+        let before = PageNames::of(page);
+        egui::Grid::new("page_settings_grid").num_columns(2).show(ui, |ui| {
+            ui.label("Имя:");
+            ui.text_edit_singleline(&mut page.name);
+            ui.end_row();
             
-            if let Some(element) = page.find_element_at_point(click_pos) {
-                // Выбираем элемент
-                self.selected_element_id = Some(element.get_id().to_string());
-                self.dragging_new_element = false;
-            } else {
-                // Сбрасываем выбор
-                self.selected_element_id = None;
-            }
-        } else if response.dragged() {
-            // Перетаскивание
-            if let Some(pos) = response.interact_pointer_pos {
-                if let Some(element_id) = &self.selected_element_id {
-                    if let Some(element) = page.find_element_mut(element_id) {
-                        // Перемещаем выбранный элемент к текущей позиции мыши
-                        let size = element.get_size();
-                        element.set_position((pos.x - size.0 / 2.0, pos.y - size.1 / 2.0));
-                    }
+            ui.label("Заголовок:");
+            ui.text_edit_singleline(&mut page.title);
+            ui.end_row();
+            
+            // Недопустимое имя исправляется, когда поле теряет фокус
+            ui.label("Файл:");
+            let response = ui.text_edit_singleline(&mut page.file_name);
+            if response.lost_focus() && !page::is_valid_file_name(&page.file_name) {
+                page.file_name = page::sanitize_file_name(&page.file_name);
+            }
+            ui.end_row();
+            
+            if !page::is_valid_file_name(&page.file_name) {
+                ui.label("");
+                ui.colored_label(Color32::from_rgb(230, 140, 0), "⚠ Путь внутри папки сайта без .. и \\");
+                ui.end_row();
+            }
+        });
+        
+        if PageNames::of(page) != before {
+            self.record_page_names(page, before);
+        }
+    }
+    
+    // Записывает переименование страницы; правки одной страницы подряд объединяются
+    fn record_page_names(&mut self, page: &Page, before: PageNames) {
+        self.history.push_merged(format!("page:{}", page.id), EditCommand::UpdatePage {
+            page_id: page.id.clone(),
+            before,
+            after: PageNames::of(page),
+        });
+    }
+    
+    // Общие стили нескольких выделенных элементов: показываются ключи, заданные у всех,
+    // изменение значения применяется ко всем сразу
+    fn show_shared_styles(&mut self, ui: &mut Ui, page: &mut Page) {
+        ui.separator();
+        ui.label(format!("Выбрано элементов: {}", self.selected_ids.len()));
+        ui.separator();
+        self.show_alignment_tools(ui, page);
+        
+        let before: Vec<Box<dyn UIElement>> = self.selected_ids.iter()
+            .filter_map(|id| page.find_element(id).map(|e| e.clone_box()))
+            .collect();
+        let Some(first) = before.first() else {
+            return;
+        };
+        
+        let mut keys: Vec<String> = first.base().styles.keys()
+            .filter(|key| before.iter().all(|e| e.base().styles.contains_key(*key)))
+            .cloned()
+            .collect();
+        keys.sort();
+        
+        ui.separator();
+        ui.heading("Общие стили");
+        
+        let mut edits: Vec<(String, Option<String>)> = Vec::new();
+        egui::Grid::new("shared_styles_grid").num_columns(3).show(ui, |ui| {
+            for key in &keys {
+                let values: Vec<&String> = before.iter().map(|e| &e.base().styles[key]).collect();
+                let same = values.iter().all(|v| *v == values[0]);
+                let mut value = if same { values[0].clone() } else { String::new() };
+                
+                ui.label(key);
+                let field = egui::TextEdit::singleline(&mut value)
+                    .hint_text(if same { "" } else { "разные значения" });
+                if ui.add(field).changed() {
+                    edits.push((key.clone(), Some(value)));
+                }
+                if ui.small_button("✕").on_hover_text("Убрать у всех").clicked() {
+                    edits.push((key.clone(), None));
                 }
+                ui.end_row();
             }
-        } else if response.drag_released() {
-            // Отпускание кнопки мыши после перетаскивания
-            if self.dragging_new_element {
-                println!("Пользователь завершил перетаскивание элемента");
-                if let Some(pos) = ui.ctx().pointer_interact_pos() {
-                    // Проверяем, что позиция находится внутри области редактирования
-                    if rect.contains(pos) {
-                        match self.selected_element_type {
-                            Some(ElementType::Button) => {
-                                // Создаем новую кнопку
-                                let mut button = Button::new();
-                                // Устанавливаем позицию, учитывая центр кнопки
-                                button.set_position((pos.x - 50.0, pos.y - 25.0));
-                                // Добавляем кнопку на страницу
-                                page.add_element(Box::new(button));
-                                println!("Добавлена новая кнопка в позиции ({}, {})", pos.x, pos.y);
-                            },
-                            _ => {
-                                // Здесь будет логика для других типов элементов
-                            }
+        });
+        
+        if keys.is_empty() {
+            ui.label("У выделенных элементов нет общих стилей");
+        }
+        
+        // Новый стиль для всех выделенных элементов
+        ui.separator();
+        ui.label("Добавить стиль всем:");
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.new_style_key).hint_text("свойство").desired_width(110.0));
+            ui.add(egui::TextEdit::singleline(&mut self.new_style_value).hint_text("значение").desired_width(110.0));
+            if ui.button("Добавить").clicked() && !self.new_style_key.trim().is_empty() {
+                edits.push((self.new_style_key.trim().to_string(), Some(self.new_style_value.trim().to_string())));
+                self.new_style_key.clear();
+                self.new_style_value.clear();
+            }
+        });
+        
+        if edits.is_empty() {
+            return;
+        }
+        
+        let page_id = page.id.clone();
+        let mut commands = Vec::new();
+        for old in before {
+            let Some(element) = page.find_element_mut(old.get_id()) else {
+                continue;
+            };
+            for (key, value) in &edits {
+                match value {
+                    Some(value) => element.base_mut().styles.insert(key.clone(), value.clone()),
+                    None => element.base_mut().styles.remove(key),
+                };
+            }
+            commands.push(EditCommand::UpdateElement {
+                page_id: page_id.clone(),
+                before: old,
+                after: element.clone_box(),
+            });
+        }
+        
+        // Правки общих стилей одного набора элементов объединяются до отпускания мыши
+        let key = format!("styles:{}", self.selected_ids.join(","));
+        self.history.push_merged(key, EditCommand::Group(commands));
+    }
+    
+    // Поля X/Y/W/H; положение задается относительно родителя
+    // parent_layout - раскладка родителя, если элемент стоит в его потоке
+    fn show_geometry(ui: &mut Ui, element: &mut Box<dyn UIElement>, parent_layout: Option<&FlowLayout>) -> bool {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Имя:");
+            let hint = element.base().display_name();
+            changed |= ui.add(egui::TextEdit::singleline(&mut element.base_mut().name).hint_text(hint)).changed();
+        });
+        
+        let (mut x, mut y) = element.get_position();
+        let (mut width, mut height) = element.get_size();
+        
+        egui::Grid::new("geometry_grid").num_columns(4).show(ui, |ui| {
+            ui.label("X:");
+            ui.add_enabled(parent_layout.is_none(), egui::DragValue::new(&mut x).speed(1.0).suffix(" px"));
+            ui.label("Y:");
+            ui.add_enabled(parent_layout.is_none(), egui::DragValue::new(&mut y).speed(1.0).suffix(" px"));
+            ui.end_row();
+            
+            ui.label("Ш:");
+            ui.add(egui::DragValue::new(&mut width).speed(1.0).clamp_range(1.0..=10000.0).suffix(" px"));
+            ui.label("В:");
+            ui.add(egui::DragValue::new(&mut height).speed(1.0).clamp_range(1.0..=10000.0).suffix(" px"));
+            ui.end_row();
+        });
+        
+        if (x, y) != element.get_position() {
+            element.set_position((x, y));
+            changed = true;
+        }
+        if (width, height) != element.get_size() {
+            element.set_size((width, height));
+            changed = true;
+        }
+        
+        if let Some(layout) = parent_layout {
+            ui.label("Положение задается раскладкой контейнера. Перетащите элемент, чтобы изменить порядок.");
+            if let FlowLayout::Grid(_) = layout {
+                ui.horizontal(|ui| {
+                    ui.label("Область сетки:");
+                    let styles = &mut element.base_mut().styles;
+                    let mut area = styles.get("grid-area").cloned().unwrap_or_default();
+                    if ui.text_edit_singleline(&mut area).changed() {
+                        if area.trim().is_empty() {
+                            styles.remove("grid-area");
+                        } else {
+                            styles.insert("grid-area".to_string(), area.trim().to_string());
                         }
-                    } else {
-                        println!("Перетаскивание завершено вне области редактирования");
+                        changed = true;
                     }
-                }
-                // Завершаем перетаскивание
-                self.dragging_new_element = false;
+                });
             }
         }
+        changed
+    }
+    
+    // Видимость элемента на сайте и его отличия на выбранной ширине экрана;
+    // in_flow - элемент стоит в потоке родителя, и его положение задает раскладка
+    fn show_breakpoint_options(ui: &mut Ui, element: &mut Box<dyn UIElement>, breakpoint_name: Option<&str>, in_flow: bool) -> bool {
+        let base = element.base_mut();
+        let mut changed = ui.checkbox(&mut base.site_hidden, "Не показывать на этой ширине экрана").changed();
+        
+        if let Some(name) = breakpoint_name {
+            ui.label(format!("Изменения положения, размера, видимости и стилей действуют только на ширине «{}»", name));
+            if ui.add_enabled(base.has_breakpoint_changes(in_flow), egui::Button::new("Сбросить к основной ширине")).clicked() {
+                base.reset_breakpoint_changes();
+                changed = true;
+            }
+        }
+        changed
+    }
+    
+    // Выбран один элемент с целью ссылки (кнопка или ссылка)
+    fn link_selected(&self, page: &Page) -> bool {
+        self.selected_ids.len() == 1 && self.primary_selection()
+            .and_then(|id| page.find_element(id))
+            .is_some_and(|element| matches!(element.get_element_type(), ElementType::Button | ElementType::Link))
+    }
+    
+    // Показать редактор свойств
+    fn show_properties(&mut self, ui: &mut Ui, page: &mut Page, pages: &[PageInfo], breakpoint_name: Option<&str>) {
+        ui.heading("Свойства");
         
-        // Отменяем перетаскивание, если кнопка мыши отпущена вне редактора
-        if !ui.input(|i| i.pointer.any_down()) {
-            if self.dragging_new_element {
-                println!("Перетаскивание отменено");
-                self.dragging_new_element = false;
+        if self.selected_ids.len() > 1 {
+            self.show_shared_styles(ui, page);
+        } else if let Some(element_id) = self.primary_selection().map(str::to_string) {
+            let page_id = page.id.clone();
+            let parent_layout = page.parent_id(&element_id)
+                .and_then(|parent_id| page.find_element(&parent_id))
+                .and_then(|parent| FlowLayout::from_styles(&parent.base().styles));
+            if let Some(element) = page.find_element_mut(&element_id) {
+                ui.separator();
+                
+                // Состояние до правки нужно для записи в историю. Копия снимается заново,
+                // только когда выбран другой элемент или элемент изменился вне панели
+                let revision = self.history.revision();
+                let fresh = self.property_snapshot.as_ref()
+                    .is_some_and(|(snapshot_revision, snapshot)| *snapshot_revision == revision && snapshot.get_id() == element_id);
+                if !fresh {
+                    self.property_snapshot = Some((revision, element.clone_box()));
+                }
+                
+                let mut changed = Self::show_geometry(ui, element, parent_layout.as_ref());
+                changed |= Self::show_breakpoint_options(ui, element, breakpoint_name, parent_layout.is_some());
+                ui.separator();
+                
+                // Собственные поля типа элемента (содержимое, цель ссылки, вид поля) - один раздел;
+                // стили редактируются только общим инспектором
+                let title = element.get_element_type().label();
+                let fields = egui::CollapsingHeader::new(title)
+                    .id_source("element_fields")
+                    .default_open(true)
+                    .show(ui, |ui| match element.get_element_type() {
+                        ElementType::Button => {
+                            // Используем ButtonProperty для отображения свойств кнопки
+                            self.button_property.show(ui, element, pages)
+                        },
+                        ElementType::Text => {
+                            self.text_property.show(ui, element)
+                        },
+                        ElementType::Image => {
+                            self.image_property.show(ui, element, self.project_path.as_deref())
+                        },
+                        ElementType::Container => {
+                            self.container_property.show(ui, element)
+                        },
+                        ElementType::Link => {
+                            self.link_property.show(ui, element, pages)
+                        },
+                        ElementType::Form => {
+                            self.form_property.show(ui, element)
+                        },
+                        ElementType::Input => {
+                            self.form_field_property.show(ui, element)
+                        },
+                        // Остальные типы редактируются только общим инспектором
+                        _ => false,
+                    });
+                changed |= fields.body_returned.unwrap_or(false);
+                
+                // Свойства из схемы элемента: стили и атрибуты по разделам
+                ui.separator();
+                changed |= self.property_inspector.show(ui, element, self.project_path.as_deref());
+                
+                // Правки свойств одного элемента объединяются до отпускания мыши
+                if changed && let Some((_, before)) = self.property_snapshot.take() {
+                    let after = element.clone_box();
+                    self.history.push_merged(format!("properties:{}", element_id), EditCommand::UpdateElement {
+                        page_id,
+                        before,
+                        after: after.clone_box(),
+                    });
+                    self.property_snapshot = Some((self.history.revision(), after));
+                }
+            } else {
+                ui.label("Элемент не найден");
+                self.selected_ids.clear();
             }
+        } else {
+            ui.label("Выберите элемент для редактирования");
         }
     }
-}
\ No newline at end of file
+}
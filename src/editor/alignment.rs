@@ -0,0 +1,180 @@
+// Выравнивание и равномерное распределение выделенных элементов
+
+use egui::{Rect, Ui, Vec2};
+
+use crate::models::page::Page;
+
+use super::Editor;
+use super::canvas::element_rect;
+use super::history::EditCommand;
+
+// Способ выравнивания по общей рамке выделения
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Align {
+    Left,
+    Center,
+    Right,
+    Top,
+    Middle,
+    Bottom,
+}
+
+impl Align {
+    const ALL: [(Align, &'static str); 6] = [
+        (Align::Left, "По левому краю"),
+        (Align::Center, "По центру"),
+        (Align::Right, "По правому краю"),
+        (Align::Top, "По верхнему краю"),
+        (Align::Middle, "По середине"),
+        (Align::Bottom, "По нижнему краю"),
+    ];
+
+    // Сдвиг прямоугольника до выравнивания по рамке bounds
+    fn offset(&self, rect: Rect, bounds: Rect) -> Vec2 {
+        match self {
+            Align::Left => Vec2::new(bounds.left() - rect.left(), 0.0),
+            Align::Center => Vec2::new(bounds.center().x - rect.center().x, 0.0),
+            Align::Right => Vec2::new(bounds.right() - rect.right(), 0.0),
+            Align::Top => Vec2::new(0.0, bounds.top() - rect.top()),
+            Align::Middle => Vec2::new(0.0, bounds.center().y - rect.center().y),
+            Align::Bottom => Vec2::new(0.0, bounds.bottom() - rect.bottom()),
+        }
+    }
+}
+
+// Сдвиги, при которых промежутки между соседними элементами по оси становятся равными;
+// крайние элементы остаются на месте
+fn distribute(rects: &[Rect], horizontal: bool) -> Vec<Vec2> {
+    let start = |r: &Rect| if horizontal { r.left() } else { r.top() };
+    let length = |r: &Rect| if horizontal { r.width() } else { r.height() };
+
+    let mut order: Vec<usize> = (0..rects.len()).collect();
+    order.sort_by(|a, b| start(&rects[*a]).total_cmp(&start(&rects[*b])));
+
+    let mut offsets = vec![Vec2::ZERO; rects.len()];
+    let (Some(&first), Some(&last)) = (order.first(), order.last()) else {
+        return offsets;
+    };
+    let span = start(&rects[last]) + length(&rects[last]) - start(&rects[first]);
+    let total: f32 = rects.iter().map(length).sum();
+    let gap = (span - total) / (rects.len() as f32 - 1.0);
+
+    let mut cursor = start(&rects[first]);
+    for index in order {
+        let shift = cursor - start(&rects[index]);
+        offsets[index] = if horizontal { Vec2::new(shift, 0.0) } else { Vec2::new(0.0, shift) };
+        cursor += length(&rects[index]) + gap;
+    }
+    offsets
+}
+
+impl Editor {
+    // Кнопки выравнивания (от двух элементов) и распределения (от трех)
+    pub(super) fn show_alignment_tools(&mut self, ui: &mut Ui, page: &mut Page) {
+        let element_ids = self.unlocked_selection(page);
+        let rects: Vec<Rect> = element_ids.iter().filter_map(|id| element_rect(page, id)).collect();
+        if rects.len() < 2 || rects.len() != element_ids.len() {
+            return;
+        }
+
+        ui.heading("Выравнивание");
+        let mut offsets = None;
+        ui.horizontal_wrapped(|ui| {
+            for (align, label) in Align::ALL {
+                if ui.button(label).clicked() {
+                    let bounds = rects.iter().copied().reduce(|a, b| a.union(b)).unwrap_or(Rect::NOTHING);
+                    offsets = Some(rects.iter().map(|rect| align.offset(*rect, bounds)).collect::<Vec<_>>());
+                }
+            }
+        });
+
+        ui.horizontal_wrapped(|ui| {
+            let enabled = rects.len() >= 3;
+            if ui.add_enabled(enabled, egui::Button::new("Распределить по горизонтали")).clicked() {
+                offsets = Some(distribute(&rects, true));
+            }
+            if ui.add_enabled(enabled, egui::Button::new("Распределить по вертикали")).clicked() {
+                offsets = Some(distribute(&rects, false));
+            }
+        });
+        ui.separator();
+
+        let Some(offsets) = offsets else {
+            return;
+        };
+
+        // Выравнивание всех элементов - один шаг истории
+        let page_id = page.id.clone();
+        let mut commands = Vec::new();
+        for (element_id, offset) in element_ids.iter().zip(offsets) {
+            if offset == Vec2::ZERO {
+                continue;
+            }
+            if let Some(element) = page.find_element_mut(element_id) {
+                let before = element.clone_box();
+                let (x, y) = element.get_position();
+                element.set_position((x + offset.x, y + offset.y));
+                commands.push(EditCommand::UpdateElement {
+                    page_id: page_id.clone(),
+                    before,
+                    after: element.clone_box(),
+                });
+            }
+        }
+        if !commands.is_empty() {
+            self.history.push(EditCommand::Group(commands));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::Pos2;
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> Rect {
+        Rect::from_min_size(Pos2::new(x, y), Vec2::new(width, height))
+    }
+
+    #[test]
+    fn distribute_makes_equal_gaps() {
+        // Порядок в выделении не совпадает с порядком на странице
+        let rects = [rect(300.0, 0.0, 50.0, 10.0), rect(0.0, 0.0, 100.0, 10.0), rect(120.0, 0.0, 20.0, 10.0)];
+        let offsets = distribute(&rects, true);
+        assert_eq!(offsets, vec![Vec2::ZERO, Vec2::ZERO, Vec2::new(70.0, 0.0)]);
+
+        let moved: Vec<Rect> = rects.iter().zip(&offsets).map(|(r, o)| r.translate(*o)).collect();
+        assert_eq!(moved[2].left() - moved[1].right(), moved[0].left() - moved[2].right());
+    }
+
+    #[test]
+    fn distribute_vertically_keeps_outer_elements() {
+        let rects = [
+            rect(0.0, 0.0, 10.0, 20.0),
+            rect(0.0, 30.0, 10.0, 20.0),
+            rect(0.0, 50.0, 10.0, 40.0),
+            rect(0.0, 280.0, 10.0, 20.0),
+        ];
+        // Промежуток: (300 - 100) / 3
+        let gap = 200.0 / 3.0;
+        let offsets = distribute(&rects, false);
+        assert_eq!(offsets[0], Vec2::ZERO);
+        assert_eq!(offsets[3], Vec2::ZERO);
+        assert!((offsets[1].y - (20.0 + gap - 30.0)).abs() < 1e-4);
+        assert!((offsets[2].y - (40.0 + 2.0 * gap - 50.0)).abs() < 1e-4);
+        assert!(offsets.iter().all(|o| o.x == 0.0));
+    }
+
+    #[test]
+    fn align_moves_to_selection_bounds() {
+        let a = rect(10.0, 10.0, 20.0, 20.0);
+        let b = rect(100.0, 50.0, 40.0, 60.0);
+        let bounds = a.union(b);
+        assert_eq!(Align::Left.offset(b, bounds), Vec2::new(-90.0, 0.0));
+        assert_eq!(Align::Right.offset(a, bounds), Vec2::new(110.0, 0.0));
+        assert_eq!(Align::Center.offset(a, bounds), Vec2::new(55.0, 0.0));
+        assert_eq!(Align::Middle.offset(a, bounds), Vec2::new(0.0, 40.0));
+        assert_eq!(Align::Bottom.offset(a, bounds), Vec2::new(0.0, 80.0));
+        assert_eq!(Align::Top.offset(b, bounds), Vec2::new(0.0, -40.0));
+    }
+}
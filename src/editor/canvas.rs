@@ -0,0 +1,685 @@
+// Холст редактора: отрисовка страницы, выделение, перемещение и изменение размера элементов
+
+use egui::{Color32, CursorIcon, Key, Modifiers, PointerButton, Pos2, Rect, Response, Sense, Stroke, Ui, Vec2};
+use std::path::Path;
+
+use crate::elements::UIElement;
+use crate::elements::canvas::CanvasContext;
+use crate::elements::{color_picker, css};
+use crate::elements::layout::{self, FlowLayout};
+use crate::models::page::Page;
+
+use super::Editor;
+use super::history::EditCommand;
+use super::resize::ResizeHandle;
+use super::rulers::{self, RULER_SIZE};
+use super::snapping::{self, Snapper};
+use super::viewport::{self, SCROLLBAR_WIDTH, ZOOM_STEP};
+
+// Перетаскивание мышью по холсту
+pub(super) enum CanvasDrag {
+    // Перемещение выделенных элементов; starts - их исходные позиции в координатах родителя
+    Move { pointer: Pos2, starts: Vec<(String, (f32, f32))>, moved: bool },
+    // Изменение размера за маркер; start - прямоугольник элемента на странице в начале
+    Resize { handle: ResizeHandle, start: Rect, pointer: Pos2 },
+    // Рамка выделения; additive - добавлять к текущему выделению
+    Marquee { start: Pos2, additive: bool },
+    // Прокрутка холста с зажатым пробелом или средней кнопкой мыши
+    Pan,
+}
+
+// Положение и состояние элемента до начала перетаскивания
+pub(super) struct ElementSnapshot {
+    parent_id: Option<String>,
+    index: usize,
+    element: Box<dyn UIElement>,
+}
+
+impl ElementSnapshot {
+    pub(super) fn take(page: &Page, element_id: &str) -> Option<Self> {
+        let (parent_id, index) = page.element_location(element_id)?;
+        let element = page.find_element(element_id)?.clone_box();
+        Some(Self { parent_id, index, element })
+    }
+
+    // Перенос элемента в другое место дерева: удаление из старого места и вставка в новое
+    pub(super) fn relocation(self, page_id: &str, after: ElementSnapshot) -> EditCommand {
+        EditCommand::Group(vec![
+            EditCommand::RemoveElement {
+                page_id: page_id.to_string(),
+                parent_id: self.parent_id,
+                index: self.index,
+                element: self.element,
+            },
+            EditCommand::InsertElement {
+                page_id: page_id.to_string(),
+                parent_id: after.parent_id,
+                index: after.index,
+                element: after.element,
+            },
+        ])
+    }
+
+    // Элемент остался на том же месте дерева
+    pub(super) fn same_place(&self, other: &ElementSnapshot) -> bool {
+        self.parent_id == other.parent_id && self.index == other.index
+    }
+}
+
+// Прямоугольник элемента в координатах страницы
+pub(super) fn element_rect(page: &Page, element_id: &str) -> Option<Rect> {
+    let position = page.absolute_position(element_id)?;
+    let size = page.find_element(element_id)?.get_size();
+    Some(Rect::from_min_size(Pos2::new(position.0, position.1), Vec2::new(size.0, size.1)))
+}
+
+// Элементы, которые пересекает рамка выделения (origin - начало координат родителя на странице).
+// Контейнер, задетый рамкой частично, уступает выделение своим задетым дочерним элементам;
+// потомки уже выделенных элементов не выделяются
+fn marquee_hits(elements: &[Box<dyn UIElement>], origin: Pos2, marquee: Rect, selected: &[String], hits: &mut Vec<String>) {
+    for element in elements {
+        let (x, y) = element.get_position();
+        let (width, height) = element.get_size();
+        let rect = Rect::from_min_size(origin + Vec2::new(x, y), Vec2::new(width, height));
+        let id = element.get_id().to_string();
+        if !element.base().is_interactive() || !rect.intersects(marquee) || selected.contains(&id) {
+            continue;
+        }
+
+        let found = hits.len();
+        if let Some(children) = element.children()
+            && !marquee.contains_rect(rect) {
+            marquee_hits(children, rect.min, marquee, selected, hits);
+        }
+        if hits.len() == found {
+            hits.push(id);
+        }
+    }
+}
+
+// Заблокированный элемент нельзя двигать и менять его размер на холсте
+fn is_locked(page: &Page, element_id: &str) -> bool {
+    page.find_element(element_id).is_some_and(|e| e.base().locked)
+}
+
+// Цвет для пипетки в точке страницы: фон самого вложенного элемента с непрозрачным фоном
+// или цвет текста элемента, под которым фона нет; на пустом месте - белый фон страницы
+fn sample_color(page: &Page, point: Pos2) -> Color32 {
+    let mut element = page.find_element_at_point((point.x, point.y));
+    while let Some(found) = element {
+        let styles = &found.base().styles;
+        if let Some(color) = css::color(styles, "background-color").filter(|color| color.a() > 0)
+            .or_else(|| css::color(styles, "color")) {
+            return color;
+        }
+        element = page.parent_id(found.get_id()).and_then(|id| page.find_element(&id));
+    }
+    Color32::WHITE
+}
+
+impl Editor {
+    // Основной выделенный элемент (выбранный последним)
+    pub(super) fn primary_selection(&self) -> Option<&str> {
+        self.selected_ids.last().map(String::as_str)
+    }
+
+    pub(super) fn select_only(&mut self, element_id: String) {
+        self.selected_ids = vec![element_id];
+    }
+
+    // Shift/Ctrl-клик добавляет элемент к выделению или убирает из него
+    pub(super) fn toggle_selection(&mut self, element_id: String) {
+        match self.selected_ids.iter().position(|id| *id == element_id) {
+            Some(index) => {
+                self.selected_ids.remove(index);
+            },
+            None => self.selected_ids.push(element_id),
+        }
+    }
+
+    // Выделенные элементы без тех, чей предок тоже выделен: они перемещаются вместе с ним
+    pub(super) fn movable_selection(&self, page: &Page) -> Vec<String> {
+        self.selected_ids.iter()
+            .filter(|id| {
+                let mut parent = page.parent_id(id);
+                while let Some(parent_id) = parent {
+                    if self.selected_ids.contains(&parent_id) {
+                        return false;
+                    }
+                    parent = page.parent_id(&parent_id);
+                }
+                page.find_element(id).is_some()
+            })
+            .cloned()
+            .collect()
+    }
+
+    // Выделенные элементы, которые можно двигать на холсте: без заблокированных
+    pub(super) fn unlocked_selection(&self, page: &Page) -> Vec<String> {
+        self.movable_selection(page).into_iter()
+            .filter(|id| page.find_element(id).is_some_and(|e| !e.base().locked))
+            .collect()
+    }
+
+    // Записывает в историю добавленный элемент
+    pub(super) fn record_insert(&mut self, page: &Page, element_id: &str) {
+        if let Some(snapshot) = ElementSnapshot::take(page, element_id) {
+            self.history.push(EditCommand::InsertElement {
+                page_id: page.id.clone(),
+                parent_id: snapshot.parent_id,
+                index: snapshot.index,
+                element: snapshot.element,
+            });
+        }
+    }
+
+    // Элемент, брошенный во flex- или grid-контейнер, встает в его поток на место под курсором
+    pub(super) fn place_in_flow(page: &mut Page, element_id: &str, pos: Pos2) {
+        let Some((Some(parent_id), index)) = page.element_location(element_id) else {
+            return;
+        };
+        let Some(parent) = page.find_element(&parent_id) else {
+            return;
+        };
+        let Some(flow) = FlowLayout::from_styles(&parent.base().styles) else {
+            return;
+        };
+        let siblings: Vec<Rect> = parent.children().unwrap_or_default().iter()
+            .filter(|child| child.get_id() != element_id && !child.base().site_hidden)
+            .filter_map(|child| element_rect(page, child.get_id()))
+            .collect();
+
+        // Позиция среди остальных элементов; move_element считает ее с учетом самого элемента
+        let target = layout::insert_index(&flow, &siblings, pos);
+        let target = if index <= target { target + 1 } else { target };
+        page.move_element(element_id, Some(&parent_id), target);
+    }
+
+    // Начинает перетаскивание: за маркер единственного выделенного элемента - изменение размера,
+    // за элемент - перемещение всего выделения, по пустому месту - рамка выделения
+    fn begin_canvas_drag(&mut self, page: &Page, pos: Pos2, additive: bool) {
+        let selected_rect = match self.selected_ids.as_slice() {
+            [id] if !is_locked(page, id) => element_rect(page, id),
+            _ => None,
+        };
+
+        // Маркеры имеют постоянный экранный размер, поэтому попадание проверяется на экране
+        if let Some(rect) = selected_rect
+            && let Some(handle) = ResizeHandle::at_point(self.viewport.rect_to_screen(rect), self.viewport.to_screen(pos)) {
+            self.canvas_drag = Some(CanvasDrag::Resize { handle, start: rect, pointer: pos });
+        } else if let Some(element) = page.find_element_at_point((pos.x, pos.y)) {
+            let element_id = element.get_id().to_string();
+            if !self.selected_ids.contains(&element_id) {
+                if additive {
+                    self.selected_ids.push(element_id);
+                } else {
+                    self.select_only(element_id);
+                }
+            }
+
+            let starts = self.unlocked_selection(page).into_iter()
+                .filter_map(|id| page.find_element(&id).map(|e| e.get_position()).map(|p| (id, p)))
+                .collect();
+            self.canvas_drag = Some(CanvasDrag::Move { pointer: pos, starts, moved: false });
+        } else {
+            if !additive {
+                self.selected_ids.clear();
+            }
+            self.canvas_drag = Some(CanvasDrag::Marquee { start: pos, additive });
+            return;
+        }
+
+        // Запоминаем исходное состояние для истории
+        self.drag_snapshots = self.unlocked_selection(page).iter()
+            .filter_map(|id| ElementSnapshot::take(page, id))
+            .collect();
+    }
+
+    // Продолжение перетаскивания: pos - текущее положение курсора, area - рамка страницы
+    // (все в координатах страницы)
+    fn update_canvas_drag(&mut self, ui: &Ui, painter: &egui::Painter, page: &mut Page, pos: Pos2, area: Rect) {
+        // Alt временно отключает привязку
+        let snapping = !ui.input(|i| i.modifiers.alt);
+        let mut targets = Vec::new();
+        if snapping {
+            snapping::collect_rects(&page.elements, (0.0, 0.0), &self.selected_ids, &mut targets);
+        }
+        let snapper = Snapper::new(&self.grid, area, &targets, self.viewport.zoom());
+        let mut guides = Vec::new();
+
+        match &mut self.canvas_drag {
+            Some(CanvasDrag::Resize { handle, start, pointer }) => {
+                let Some(element_id) = self.selected_ids.last() else {
+                    return;
+                };
+                // Маркер тянет свои стороны, противоположные остаются на месте
+                let mut resized = handle.apply(*start, pos - *pointer);
+
+                // Подвижные стороны притягиваются к направляющим
+                if snapping {
+                    let (x, y) = handle.sides();
+                    let (side_x, side_y) = (
+                        if x < 0 { resized.min.x } else { resized.max.x },
+                        if y < 0 { resized.min.y } else { resized.max.y },
+                    );
+                    let (snapped_x, guide_x) = snapper.snap_x(side_x);
+                    let (snapped_y, guide_y) = snapper.snap_y(side_y);
+                    let delta = egui::vec2(
+                        if x != 0 { snapped_x - side_x } else { 0.0 },
+                        if y != 0 { snapped_y - side_y } else { 0.0 },
+                    );
+                    resized = handle.apply(*start, pos - *pointer + delta);
+                    guides.extend(guide_x.filter(|_| x != 0));
+                    guides.extend(guide_y.filter(|_| y != 0));
+                }
+
+                // Позиция элемента задается относительно его родителя
+                let origin = page.parent_origin(element_id);
+                if let Some(element) = page.find_element_mut(element_id) {
+                    element.set_position((resized.min.x - origin.0, resized.min.y - origin.1));
+                    element.set_size((resized.width(), resized.height()));
+                    ui.ctx().set_cursor_icon(handle.cursor());
+                }
+            },
+            Some(CanvasDrag::Move { pointer, starts, moved }) => {
+                // Все элементы сдвигаются на одно смещение, точка захвата остается под курсором
+                let mut delta = pos - *pointer;
+
+                // Общая рамка перемещаемых элементов притягивается к направляющим и сетке
+                if snapping {
+                    let bounds = starts.iter()
+                        .filter_map(|(id, start)| {
+                            let origin = page.parent_origin(id);
+                            let size = page.find_element(id)?.get_size();
+                            Some(Rect::from_min_size(
+                                Pos2::new(origin.0 + start.0 + delta.x, origin.1 + start.1 + delta.y),
+                                egui::vec2(size.0, size.1)
+                            ))
+                        })
+                        .reduce(|a, b| a.union(b));
+                    if let Some(bounds) = bounds {
+                        let (snap, snapped_guides) = snapper.snap_move(bounds);
+                        delta += snap;
+                        guides = snapped_guides;
+                    }
+                }
+
+                for (id, start) in starts.iter() {
+                    if let Some(element) = page.find_element_mut(id) {
+                        element.set_position((start.0 + delta.x, start.1 + delta.y));
+                    }
+                }
+                *moved = true;
+
+                // Подсвечиваем контейнер, в который попадет единственный перемещаемый элемент
+                if let [(element_id, _)] = starts.as_slice()
+                    && let Some(container) = page.find_container_at_point((pos.x, pos.y), Some(element_id))
+                    && let Some(target) = element_rect(page, container.get_id()) {
+                    painter.rect_stroke(self.viewport.rect_to_screen(target), 0.0, Stroke::new(2.0, Color32::from_rgb(76, 175, 80)));
+                }
+            },
+            Some(CanvasDrag::Marquee { start, .. }) => {
+                let marquee = self.viewport.rect_to_screen(Rect::from_two_pos(*start, pos));
+                painter.rect_filled(marquee, 0.0, Color32::from_rgba_unmultiplied(33, 150, 243, 30));
+                painter.rect_stroke(marquee, 0.0, Stroke::new(1.0, Color32::from_rgb(33, 150, 243)));
+            },
+            Some(CanvasDrag::Pan) | None => {}
+        }
+
+        snapping::draw_guides(painter, &self.viewport, area, &guides);
+    }
+
+    // Завершение перетаскивания
+    fn end_canvas_drag(&mut self, page: &mut Page, pos: Option<Pos2>) {
+        match self.canvas_drag.take() {
+            Some(CanvasDrag::Move { starts, moved, .. }) => {
+                // Единственный перемещенный элемент вкладывается в контейнер под курсором
+                // или извлекается на страницу
+                if moved
+                    && let [(element_id, _)] = starts.as_slice()
+                    && let Some(pos) = pos {
+                    let target = page.find_container_at_point((pos.x, pos.y), Some(element_id))
+                        .map(|container| container.get_id().to_string());
+                    page.move_to_parent(element_id, target.as_deref());
+                    Self::place_in_flow(page, element_id, pos);
+                }
+                // Элементы в потоке возвращаются на места, заданные раскладкой
+                layout::arrange_elements(&mut page.elements);
+                self.record_drag(page);
+            },
+            Some(CanvasDrag::Resize { .. }) => self.record_drag(page),
+            Some(CanvasDrag::Marquee { start, additive }) => {
+                let Some(pos) = pos else {
+                    return;
+                };
+                let marquee = Rect::from_two_pos(start, pos);
+                if !additive {
+                    self.selected_ids.clear();
+                }
+                let mut hits = Vec::new();
+                marquee_hits(&page.elements, Pos2::ZERO, marquee, &self.selected_ids, &mut hits);
+                // Выделенные раньше потомки новых элементов входят в них и из выделения убираются
+                let inside_hit = |id: &str| {
+                    let mut parent = page.parent_id(id);
+                    while let Some(parent_id) = parent {
+                        if hits.contains(&parent_id) {
+                            return true;
+                        }
+                        parent = page.parent_id(&parent_id);
+                    }
+                    false
+                };
+                self.selected_ids.retain(|id| !inside_hit(id));
+                self.selected_ids.extend(hits);
+            },
+            Some(CanvasDrag::Pan) | None => {}
+        }
+    }
+
+    // Записывает завершенное перетаскивание одним шагом: перемещение внутри
+    // родителя - как изменение элемента, перенос в другой контейнер - как удаление и вставку
+    fn record_drag(&mut self, page: &Page) {
+        let mut commands = Vec::new();
+
+        for snapshot in std::mem::take(&mut self.drag_snapshots) {
+            let element_id = snapshot.element.get_id().to_string();
+            let Some(after) = ElementSnapshot::take(page, &element_id) else {
+                continue;
+            };
+
+            if snapshot.parent_id == after.parent_id && snapshot.index == after.index {
+                if snapshot.element.get_position() == after.element.get_position()
+                    && snapshot.element.get_size() == after.element.get_size() {
+                    continue;
+                }
+                commands.push(EditCommand::UpdateElement {
+                    page_id: page.id.clone(),
+                    before: snapshot.element,
+                    after: after.element,
+                });
+            } else {
+                commands.push(snapshot.relocation(&page.id, after));
+            }
+        }
+
+        match commands.len() {
+            0 => {},
+            1 => self.history.push(commands.remove(0)),
+            _ => self.history.push(EditCommand::Group(commands)),
+        }
+    }
+
+    // Сдвигает выделенные элементы стрелками: на 1px, с Shift - на 10px
+    fn nudge_selected(&mut self, ui: &Ui, page: &mut Page) {
+        if ui.ctx().wants_keyboard_input() || self.selected_ids.is_empty() {
+            return;
+        }
+
+        let (delta, shift) = ui.input(|i| {
+            let mut delta = Vec2::ZERO;
+            if i.key_pressed(Key::ArrowLeft) {
+                delta.x -= 1.0;
+            }
+            if i.key_pressed(Key::ArrowRight) {
+                delta.x += 1.0;
+            }
+            if i.key_pressed(Key::ArrowUp) {
+                delta.y -= 1.0;
+            }
+            if i.key_pressed(Key::ArrowDown) {
+                delta.y += 1.0;
+            }
+            (delta, i.modifiers.shift)
+        });
+        if delta == Vec2::ZERO {
+            return;
+        }
+        let delta = if shift { delta * 10.0 } else { delta };
+
+        let element_ids = self.unlocked_selection(page);
+        let page_id = page.id.clone();
+        let mut commands = Vec::new();
+        for element_id in &element_ids {
+            if let Some(element) = page.find_element_mut(element_id) {
+                let before = element.clone_box();
+                let (x, y) = element.get_position();
+                element.set_position((x + delta.x, y + delta.y));
+                commands.push(EditCommand::UpdateElement {
+                    page_id: page_id.clone(),
+                    before,
+                    after: element.clone_box(),
+                });
+            }
+        }
+
+        // Серия нажатий подряд отменяется одним шагом
+        let command = if commands.len() == 1 { commands.remove(0) } else { EditCommand::Group(commands) };
+        self.history.push_merged(format!("nudge:{}", element_ids.join(",")), command);
+    }
+
+    // Рисует маркеры изменения размера единственного выделенного элемента
+    fn draw_selection_handles(&self, ui: &Ui, painter: &egui::Painter, page: &Page) {
+        let [element_id] = self.selected_ids.as_slice() else {
+            return;
+        };
+        if is_locked(page, element_id) {
+            return;
+        }
+        let Some(rect) = element_rect(page, element_id) else {
+            return;
+        };
+
+        // Маркеры рисуются в экранных точках и не меняют размер при масштабировании
+        let rect = self.viewport.rect_to_screen(rect);
+        let stroke = Stroke::new(1.0, Color32::from_rgb(33, 150, 243));
+        for handle in ResizeHandle::ALL {
+            let handle_rect = handle.rect(rect);
+            painter.rect_filled(handle_rect, 0.0, Color32::WHITE);
+            painter.rect_stroke(handle_rect, 0.0, stroke);
+        }
+
+        // Курсор подсказывает направление изменения размера
+        if self.canvas_drag.is_none()
+            && let Some(pos) = ui.ctx().pointer_hover_pos()
+            && let Some(handle) = ResizeHandle::at_point(rect, pos) {
+            ui.ctx().set_cursor_icon(handle.cursor());
+        }
+    }
+
+    // Вписывает страницу в холст целиком
+    pub(super) fn zoom_to_fit(&mut self, page: &Page, page_width: f32) {
+        self.viewport.zoom_to_fit(page_width, viewport::content_height(&page.elements));
+    }
+
+    // Масштаб в factor раз относительно центра холста
+    pub(super) fn zoom_by(&mut self, factor: f32) {
+        let center = self.viewport.area().center();
+        self.viewport.zoom_around(factor, center);
+    }
+
+    // Колесо мыши прокручивает холст (с Shift - по горизонтали), Ctrl+колесо и щипок
+    // масштабируют вокруг курсора. Ctrl+0 - 100%, Shift+1 - вся страница, Ctrl+= и Ctrl+- - шаг масштаба
+    fn handle_view_input(&mut self, ui: &Ui, response: &Response, page: &Page, page_width: f32) {
+        if let Some(pos) = response.hover_pos() {
+            let (scroll, zoom) = ui.input(|i| (i.scroll_delta, i.zoom_delta()));
+            if zoom != 1.0 {
+                self.viewport.zoom_around(zoom, pos);
+            }
+            if scroll != Vec2::ZERO {
+                self.viewport.pan(scroll);
+            }
+        }
+
+        if ui.ctx().wants_keyboard_input() {
+            return;
+        }
+        let (reset, fit, zoom_in, zoom_out) = ui.input_mut(|i| (
+            i.consume_key(Modifiers::COMMAND, Key::Num0),
+            i.consume_key(Modifiers::SHIFT, Key::Num1),
+            i.consume_key(Modifiers::COMMAND, Key::PlusEquals),
+            i.consume_key(Modifiers::COMMAND, Key::Minus),
+        ));
+        if reset {
+            self.viewport.reset_zoom();
+        }
+        if fit {
+            self.zoom_to_fit(page, page_width);
+        }
+        if zoom_in {
+            self.zoom_by(ZOOM_STEP);
+        }
+        if zoom_out {
+            self.zoom_by(1.0 / ZOOM_STEP);
+        }
+    }
+
+    // Зажатый пробел переключает перетаскивание мышью на прокрутку холста
+    fn space_held(ui: &Ui) -> bool {
+        !ui.ctx().wants_keyboard_input() && ui.input(|i| i.key_down(Key::Space))
+    }
+
+    // Пипетка палитры цвета: образец под курсором, щелчок отдает цвет палитре, Esc - отмена
+    fn update_eyedropper(&self, ui: &Ui, response: &Response, page: &Page) {
+        if ui.input(|i| i.key_pressed(Key::Escape)) {
+            color_picker::finish_eyedropper(ui.ctx(), None);
+            return;
+        }
+        let Some(pos) = response.hover_pos() else {
+            return;
+        };
+        let color = sample_color(page, self.viewport.to_page(pos));
+        ui.ctx().set_cursor_icon(CursorIcon::Crosshair);
+
+        let swatch = Rect::from_min_size(pos + Vec2::new(12.0, 12.0), Vec2::splat(24.0));
+        let painter = ui.painter();
+        egui::color_picker::show_color_at(painter, color, swatch);
+        painter.rect_stroke(swatch, 0.0, Stroke::new(1.0, Color32::from_gray(60)));
+
+        if response.clicked() {
+            color_picker::finish_eyedropper(ui.ctx(), Some(color));
+        }
+    }
+
+    // Показать область редактирования: линейки сверху и слева, полосы прокрутки справа и снизу
+    // page_width - ширина страницы на выбранной ширине экрана
+    pub(super) fn show_editor_area(&mut self, ui: &mut Ui, page: &mut Page, page_width: f32) {
+        let (rect, _) = ui.allocate_exact_size(ui.available_size(), Sense::hover());
+        let area = Rect::from_min_max(rect.min + Vec2::splat(RULER_SIZE), rect.max - Vec2::splat(SCROLLBAR_WIDTH));
+        let response = ui.interact(area, ui.id().with("editor_canvas"), Sense::click_and_drag());
+        let painter = ui.painter_at(area);
+
+        // Элементы flex- и grid-контейнеров расставляются раскладкой
+        layout::arrange_elements(&mut page.elements);
+
+        // Положение и масштаб страницы на холсте; координаты элементов отсчитываются от ее левого верхнего угла
+        self.viewport.update(area, page_width, viewport::content_height(&page.elements));
+        self.handle_view_input(ui, &response, page, page_width);
+        self.viewport.show_scrollbars(ui);
+        let page_rect = self.viewport.page_rect();
+
+        // Отрисовываем фон рабочей области: рамка страницы выбранной ширины на сером поле
+        let page_screen_rect = self.viewport.rect_to_screen(page_rect);
+        painter.rect_filled(area, 0.0, Color32::from_gray(225));
+        painter.rect_filled(page_screen_rect, 0.0, Color32::WHITE);
+        painter.rect_stroke(page_screen_rect, 0.0, Stroke::new(1.0, Color32::from_gray(190)));
+        self.grid.draw(&painter, &self.viewport, page_rect);
+
+        // Отрисовываем элементы страницы
+        let canvas = CanvasContext::new(self.viewport.origin(), self.viewport.zoom(), &self.selected_ids)
+            .with_project_dir(self.project_path.as_deref().and_then(Path::parent));
+        canvas.render_elements(&painter, &page.elements);
+        self.draw_selection_handles(ui, &painter, page);
+        self.nudge_selected(ui, page);
+        self.handle_clipboard_shortcuts(ui, page);
+        self.show_context_menu(&response, page);
+        self.update_palette_drag(ui, page);
+
+        // Shift или Ctrl добавляют к выделению
+        let additive = ui.input(|i| i.modifiers.shift || i.modifiers.command);
+        let space_held = Self::space_held(ui);
+
+        // Обработка событий мыши; пока палитра ждет цвет с холста, щелчок берет цвет, а не выделяет
+        if color_picker::eyedropper_active(ui.ctx()) {
+            self.update_eyedropper(ui, &response, page);
+        } else if response.clicked() && !space_held {
+            // Клик мыши
+            let pos = self.viewport.to_page(response.interact_pointer_pos.unwrap());
+            let click_pos = (pos.x, pos.y);
+
+            if let Some(element) = page.find_element_at_point(click_pos) {
+                // Выбираем элемент
+                let element_id = element.get_id().to_string();
+                if additive {
+                    self.toggle_selection(element_id);
+                } else {
+                    self.select_only(element_id);
+                }
+            } else if !additive {
+                // Сбрасываем выбор
+                self.selected_ids.clear();
+            }
+        } else if response.drag_started() {
+            if space_held || response.drag_started_by(PointerButton::Middle) {
+                self.canvas_drag = Some(CanvasDrag::Pan);
+            } else if let Some(pos) = ui.input(|i| i.pointer.press_origin()) {
+                self.begin_canvas_drag(page, self.viewport.to_page(pos), additive);
+            }
+        } else if response.dragged() {
+            // Перетаскивание
+            if matches!(self.canvas_drag, Some(CanvasDrag::Pan)) {
+                self.viewport.pan(response.drag_delta());
+                ui.ctx().set_cursor_icon(CursorIcon::Grabbing);
+            } else if let Some(pos) = response.interact_pointer_pos {
+                self.update_canvas_drag(ui, &painter, page, self.viewport.to_page(pos), page_rect);
+            }
+        } else if response.drag_released() {
+            let pos = response.interact_pointer_pos.or(ui.ctx().pointer_interact_pos());
+            self.end_canvas_drag(page, pos.map(|pos| self.viewport.to_page(pos)));
+        }
+
+        if space_held && response.hovered() && self.canvas_drag.is_none() {
+            ui.ctx().set_cursor_icon(CursorIcon::Grab);
+        }
+
+        rulers::draw_rulers(ui.painter(), &self.viewport, response.hover_pos());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::containers::container::Container;
+    use crate::elements::texts::text::Text;
+
+    fn hits(elements: &[Box<dyn UIElement>], marquee: Rect, selected: &[String]) -> Vec<String> {
+        let mut hits = Vec::new();
+        marquee_hits(elements, Pos2::ZERO, marquee, selected, &mut hits);
+        hits
+    }
+
+    #[test]
+    fn marquee_reaches_children_of_containers() {
+        // Контейнер 0..300 x 0..200 и текст в нем 10..110 x 10..50
+        let mut container = Container::new();
+        container.base.position = (0.0, 0.0);
+        let mut text = Text::new();
+        text.base.position = (10.0, 10.0);
+        text.base.size = (100.0, 40.0);
+        let text_id = text.base.id.clone();
+        container.children.push(Box::new(text));
+        let container_id = container.base.id.clone();
+        let elements: Vec<Box<dyn UIElement>> = vec![Box::new(container)];
+
+        // Рамка внутри контейнера задевает текст
+        let inner = Rect::from_min_max(Pos2::new(5.0, 5.0), Pos2::new(50.0, 30.0));
+        assert_eq!(hits(&elements, inner, &[]), vec![text_id.clone()]);
+        // Рамка вокруг контейнера выделяет только его, без потомков
+        let outer = Rect::from_min_max(Pos2::new(-10.0, -10.0), Pos2::new(400.0, 300.0));
+        assert_eq!(hits(&elements, outer, &[]), vec![container_id.clone()]);
+        // Задет только пустой угол контейнера - выделяется сам контейнер
+        let corner = Rect::from_min_max(Pos2::new(250.0, 150.0), Pos2::new(350.0, 250.0));
+        assert_eq!(hits(&elements, corner, &[]), vec![container_id.clone()]);
+        // Потомки уже выделенного контейнера не выделяются
+        assert!(hits(&elements, inner, &[container_id]).is_empty());
+    }
+}
@@ -0,0 +1,211 @@
+// Удаление, копирование, вставка и дублирование выделенных элементов
+
+use egui::{Key, KeyboardShortcut, Modifiers, Pos2, Response, Ui};
+
+use crate::elements::UIElement;
+use crate::elements::breakpoints::switch_breakpoint;
+use crate::models::page::Page;
+
+use super::Editor;
+use super::history::EditCommand;
+
+// Смещение копии относительно оригинала
+const PASTE_OFFSET: f32 = 10.0;
+
+impl Editor {
+    // Delete/Backspace, Ctrl+C, Ctrl+X, Ctrl+V, Ctrl+D
+    pub(super) fn handle_clipboard_shortcuts(&mut self, ui: &Ui, page: &mut Page) {
+        if ui.ctx().wants_keyboard_input() {
+            return;
+        }
+
+        let (delete, copy, cut, paste, duplicate) = ui.input_mut(|i| (
+            i.consume_key(Modifiers::NONE, Key::Delete) || i.consume_key(Modifiers::NONE, Key::Backspace),
+            i.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND, Key::C)),
+            i.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND, Key::X)),
+            i.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND, Key::V)),
+            i.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND, Key::D)),
+        ));
+
+        if copy || cut {
+            self.copy_selection(page);
+        }
+        if delete || cut {
+            self.delete_selection(page);
+        }
+        if paste {
+            self.paste(page, None);
+        }
+        if duplicate {
+            self.duplicate_selection(page);
+        }
+    }
+
+    // Контекстное меню холста; вставка - в точку правого клика на странице
+    pub(super) fn show_context_menu(&mut self, response: &Response, page: &mut Page) {
+        // Правый клик по невыделенному элементу выделяет его
+        if response.secondary_clicked()
+            && let Some(pos) = response.interact_pointer_pos {
+            let pos = self.viewport.to_page(pos);
+            self.context_menu_pos = Some(pos);
+            match page.find_element_at_point((pos.x, pos.y)) {
+                Some(element) if !self.selected_ids.iter().any(|id| id == element.get_id()) => {
+                    self.select_only(element.get_id().to_string());
+                },
+                None => self.selected_ids.clear(),
+                _ => {}
+            }
+        }
+
+        let has_selection = !self.selected_ids.is_empty();
+        response.clone().context_menu(|ui| {
+            if ui.add_enabled(has_selection, egui::Button::new("Копировать").shortcut_text("Ctrl+C")).clicked() {
+                self.copy_selection(page);
+                ui.close_menu();
+            }
+            if ui.add_enabled(has_selection, egui::Button::new("Вырезать").shortcut_text("Ctrl+X")).clicked() {
+                self.copy_selection(page);
+                self.delete_selection(page);
+                ui.close_menu();
+            }
+            if ui.add_enabled(!self.clipboard.is_empty(), egui::Button::new("Вставить").shortcut_text("Ctrl+V")).clicked() {
+                let pos = self.context_menu_pos;
+                self.paste(page, pos);
+                ui.close_menu();
+            }
+            if ui.add_enabled(has_selection, egui::Button::new("Дублировать").shortcut_text("Ctrl+D")).clicked() {
+                self.duplicate_selection(page);
+                ui.close_menu();
+            }
+            ui.separator();
+            if ui.add_enabled(has_selection, egui::Button::new("На передний план")).clicked() {
+                self.reorder_selection(page, true);
+                ui.close_menu();
+            }
+            if ui.add_enabled(has_selection, egui::Button::new("На задний план")).clicked() {
+                self.reorder_selection(page, false);
+                ui.close_menu();
+            }
+            ui.separator();
+            if ui.add_enabled(has_selection, egui::Button::new("Удалить").shortcut_text("Del")).clicked() {
+                self.delete_selection(page);
+                ui.close_menu();
+            }
+        });
+    }
+
+    // Копирует выделенные элементы в буфер; позиции сохраняются в координатах страницы,
+    // поэтому копию можно вставить на любую страницу
+    fn copy_selection(&mut self, page: &Page) {
+        let copied: Vec<Box<dyn UIElement>> = self.movable_selection(page).iter()
+            .filter_map(|id| {
+                let mut element = page.find_element(id)?.clone_box();
+                element.set_position(page.absolute_position(id)?);
+                Some(element)
+            })
+            .collect();
+
+        if !copied.is_empty() {
+            self.clipboard = copied;
+            self.clipboard_breakpoint = self.breakpoint.clone();
+            self.paste_count = 0;
+        }
+    }
+
+    // Удаляет выделенные элементы вместе с потомками
+    fn delete_selection(&mut self, page: &mut Page) {
+        let mut commands = Vec::new();
+        for element_id in self.movable_selection(page) {
+            let Some((parent_id, index)) = page.element_location(&element_id) else {
+                continue;
+            };
+            if let Some(element) = page.remove_element(&element_id) {
+                commands.push(EditCommand::RemoveElement {
+                    page_id: page.id.clone(),
+                    parent_id,
+                    index,
+                    element,
+                });
+            }
+        }
+
+        self.selected_ids.clear();
+        if !commands.is_empty() {
+            self.history.push(EditCommand::Group(commands));
+        }
+    }
+
+    // Вставляет копии из буфера на страницу: в точку at или со смещением от оригинала
+    fn paste(&mut self, page: &mut Page, at: Option<Pos2>) {
+        if self.clipboard.is_empty() {
+            return;
+        }
+
+        let offset = match at {
+            Some(pos) => {
+                // Левый верхний угол группы копий попадает в точку вставки
+                let left = self.clipboard.iter().map(|e| e.get_position().0).fold(f32::INFINITY, f32::min);
+                let top = self.clipboard.iter().map(|e| e.get_position().1).fold(f32::INFINITY, f32::min);
+                (pos.x - left, pos.y - top)
+            },
+            None => {
+                self.paste_count += 1;
+                let shift = PASTE_OFFSET * self.paste_count as f32;
+                (shift, shift)
+            }
+        };
+
+        let copies: Vec<Box<dyn UIElement>> = self.clipboard.iter()
+            .map(|element| {
+                let mut copy = element.clone();
+                // Копия, сделанная на другой ширине экрана, переводится на выбранную
+                switch_breakpoint(copy.as_mut(), self.clipboard_breakpoint.as_deref(), self.breakpoint.as_deref());
+                let (x, y) = copy.get_position();
+                copy.set_position((x + offset.0, y + offset.1));
+                copy
+            })
+            .collect();
+        self.insert_copies(page, copies.into_iter().map(|copy| (None, copy)).collect());
+    }
+
+    // Дублирует выделенные элементы в тех же контейнерах
+    fn duplicate_selection(&mut self, page: &mut Page) {
+        let copies = self.movable_selection(page).iter()
+            .filter_map(|id| {
+                let mut copy = page.find_element(id)?.clone_box();
+                let (x, y) = copy.get_position();
+                copy.set_position((x + PASTE_OFFSET, y + PASTE_OFFSET));
+                Some((page.parent_id(id), copy))
+            })
+            .collect();
+        self.insert_copies(page, copies);
+    }
+
+    // Вставляет копии с новыми идентификаторами, выделяет их и записывает одним шагом
+    fn insert_copies(&mut self, page: &mut Page, copies: Vec<(Option<String>, Box<dyn UIElement>)>) {
+        let mut commands = Vec::new();
+        let mut inserted = Vec::new();
+
+        for (parent_id, mut copy) in copies {
+            copy.regenerate_ids();
+            let element_id = copy.get_id().to_string();
+            page.insert_element(parent_id.as_deref(), copy);
+
+            if let Some((parent_id, index)) = page.element_location(&element_id)
+                && let Some(element) = page.find_element(&element_id) {
+                commands.push(EditCommand::InsertElement {
+                    page_id: page.id.clone(),
+                    parent_id,
+                    index,
+                    element: element.clone_box(),
+                });
+            }
+            inserted.push(element_id);
+        }
+
+        if !commands.is_empty() {
+            self.selected_ids = inserted;
+            self.history.push(EditCommand::Group(commands));
+        }
+    }
+}
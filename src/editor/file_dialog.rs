@@ -0,0 +1,67 @@
+use egui::Context;
+use std::path::PathBuf;
+
+// Действие, для которого запрашивается путь
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FileAction {
+    OpenProject,
+    SaveProject,
+    ExportSite,
+}
+
+impl FileAction {
+    fn title(&self) -> &'static str {
+        match self {
+            FileAction::OpenProject => "Открыть проект",
+            FileAction::SaveProject => "Сохранить проект",
+            FileAction::ExportSite => "Экспорт сайта в папку",
+        }
+    }
+}
+
+// Результат работы диалога за один кадр
+pub enum FileDialogResult {
+    None,
+    Confirmed(PathBuf),
+    Cancelled,
+}
+
+// Простое окно ввода пути к файлу или папке
+pub struct FileDialog {
+    pub action: FileAction,
+    pub path: String,
+}
+
+impl FileDialog {
+    pub fn new(action: FileAction, path: String) -> Self {
+        Self { action, path }
+    }
+
+    pub fn show(&mut self, ctx: &Context) -> FileDialogResult {
+        let mut result = FileDialogResult::None;
+
+        egui::Window::new(self.action.title())
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("Путь:");
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.path).desired_width(400.0)
+                );
+                let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+                ui.horizontal(|ui| {
+                    let path_valid = !self.path.trim().is_empty();
+                    if (ui.add_enabled(path_valid, egui::Button::new("OK")).clicked() || submitted) && path_valid {
+                        result = FileDialogResult::Confirmed(PathBuf::from(self.path.trim()));
+                    }
+
+                    if ui.button("Отмена").clicked() {
+                        result = FileDialogResult::Cancelled;
+                    }
+                });
+            });
+
+        result
+    }
+}
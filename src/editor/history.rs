@@ -0,0 +1,532 @@
+// История изменений редактора: каждое действие записывается командой,
+// которую можно отменить и повторить. Непрерывные изменения одного свойства
+// (перетаскивание ползунка, набор текста) объединяются в один шаг.
+// Шаг отменяется на той ширине экрана, на которой был сделан.
+
+use std::collections::VecDeque;
+
+use crate::elements::UIElement;
+use crate::elements::breakpoints::BreakpointOverride;
+use crate::models::page::Page;
+use crate::models::site::{Site, SiteSettings};
+
+// Максимальное число шагов отмены
+const HISTORY_LIMIT: usize = 200;
+
+// Имя, заголовок и файл страницы
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageNames {
+    pub name: String,
+    pub title: String,
+    pub file_name: String,
+}
+
+impl PageNames {
+    pub fn of(page: &Page) -> Self {
+        Self {
+            name: page.name.clone(),
+            title: page.title.clone(),
+            file_name: page.file_name.clone(),
+        }
+    }
+
+    fn apply(&self, page: &mut Page) {
+        page.name = self.name.clone();
+        page.title = self.title.clone();
+        page.file_name = self.file_name.clone();
+    }
+}
+
+// Обратимое изменение сайта
+pub enum EditCommand {
+    // Элемент добавлен в контейнер (или на страницу) на позицию index
+    InsertElement {
+        page_id: String,
+        parent_id: Option<String>,
+        index: usize,
+        element: Box<dyn UIElement>,
+    },
+    // Элемент удален из контейнера (или со страницы) с позиции index
+    RemoveElement {
+        page_id: String,
+        parent_id: Option<String>,
+        index: usize,
+        element: Box<dyn UIElement>,
+    },
+    // Состояние элемента до и после изменения: перемещение, размер, свойства, стили
+    UpdateElement {
+        page_id: String,
+        before: Box<dyn UIElement>,
+        after: Box<dyn UIElement>,
+    },
+    // Имя, заголовок или файл страницы
+    UpdatePage {
+        page_id: String,
+        before: PageNames,
+        after: PageNames,
+    },
+    // Общие настройки сайта
+    UpdateSettings {
+        before: SiteSettings,
+        after: SiteSettings,
+    },
+    // Ширина экрана удалена из настроек вместе с отличиями элементов на ней
+    // (идентификатор страницы, идентификатор элемента, отличия)
+    RemoveBreakpoint {
+        before: SiteSettings,
+        after: SiteSettings,
+        breakpoint_id: String,
+        overrides: Vec<(String, String, BreakpointOverride)>,
+    },
+    // Страница добавлена (новая или копия) на позицию index; before_current - текущая
+    // страница до добавления
+    InsertPage {
+        index: usize,
+        page: Page,
+        before_current: usize,
+    },
+    // Страница удалена с позиции index
+    RemovePage {
+        index: usize,
+        page: Page,
+    },
+    // Страница перемещена с позиции from на позицию to
+    MovePage {
+        from: usize,
+        to: usize,
+    },
+    // Несколько команд, которые отменяются как одна
+    Group(Vec<EditCommand>),
+}
+
+impl EditCommand {
+    // Применяет изменение (повтор)
+    fn redo(&self, site: &mut Site) {
+        match self {
+            EditCommand::InsertElement { page_id, parent_id, index, element } => {
+                if let Some(page) = page_mut(site, page_id) {
+                    page.insert_element_at(parent_id.as_deref(), *index, element.clone());
+                }
+            },
+            EditCommand::RemoveElement { page_id, element, .. } => {
+                if let Some(page) = page_mut(site, page_id) {
+                    page.remove_element(element.get_id());
+                }
+            },
+            EditCommand::UpdateElement { page_id, after, .. } => {
+                if let Some(page) = page_mut(site, page_id) {
+                    page.replace_element(after.clone());
+                }
+            },
+            EditCommand::UpdatePage { page_id, after, .. } => {
+                if let Some(page) = page_mut(site, page_id) {
+                    after.apply(page);
+                }
+            },
+            EditCommand::UpdateSettings { after, .. } => {
+                site.settings = after.clone();
+            },
+            EditCommand::RemoveBreakpoint { after, breakpoint_id, .. } => {
+                site.settings = after.clone();
+                site.take_breakpoint_overrides(breakpoint_id);
+            },
+            EditCommand::InsertPage { index, page, .. } => {
+                let index = (*index).min(site.pages.len());
+                site.pages.insert(index, page.clone());
+                site.select_page(index);
+            },
+            EditCommand::RemovePage { index, .. } => {
+                site.remove_page(*index);
+            },
+            EditCommand::MovePage { from, to } => {
+                site.move_page(*from, *to);
+            },
+            EditCommand::Group(commands) => {
+                for command in commands {
+                    command.redo(site);
+                }
+            },
+        }
+    }
+
+    // Возвращает сайт в состояние до изменения
+    fn undo(&self, site: &mut Site) {
+        match self {
+            EditCommand::InsertElement { page_id, element, .. } => {
+                if let Some(page) = page_mut(site, page_id) {
+                    page.remove_element(element.get_id());
+                }
+            },
+            EditCommand::RemoveElement { page_id, parent_id, index, element } => {
+                if let Some(page) = page_mut(site, page_id) {
+                    page.insert_element_at(parent_id.as_deref(), *index, element.clone());
+                }
+            },
+            EditCommand::UpdateElement { page_id, before, .. } => {
+                if let Some(page) = page_mut(site, page_id) {
+                    page.replace_element(before.clone());
+                }
+            },
+            EditCommand::UpdatePage { page_id, before, .. } => {
+                if let Some(page) = page_mut(site, page_id) {
+                    before.apply(page);
+                }
+            },
+            EditCommand::UpdateSettings { before, .. } => {
+                site.settings = before.clone();
+            },
+            EditCommand::RemoveBreakpoint { before, breakpoint_id, overrides, .. } => {
+                site.settings = before.clone();
+                site.restore_breakpoint_overrides(breakpoint_id, overrides);
+            },
+            EditCommand::InsertPage { index, before_current, .. } => {
+                if site.remove_page(*index) {
+                    site.select_page(*before_current);
+                }
+            },
+            EditCommand::RemovePage { index, page } => {
+                let index = (*index).min(site.pages.len());
+                site.pages.insert(index, page.clone());
+                site.select_page(index);
+            },
+            EditCommand::MovePage { from, to } => {
+                site.move_page(*to, *from);
+            },
+            EditCommand::Group(commands) => {
+                for command in commands.iter().rev() {
+                    command.undo(site);
+                }
+            },
+        }
+    }
+
+    // Присоединяет следующее изменение того же объекта: остается исходное
+    // состояние этой команды и конечное состояние следующей
+    fn merge(&mut self, next: &EditCommand) -> bool {
+        match (self, next) {
+            (EditCommand::UpdateElement { after, .. }, EditCommand::UpdateElement { after: next_after, .. }) => {
+                *after = next_after.clone();
+                true
+            },
+            (EditCommand::UpdatePage { after, .. }, EditCommand::UpdatePage { after: next_after, .. }) => {
+                *after = next_after.clone();
+                true
+            },
+            (EditCommand::UpdateSettings { after, .. }, EditCommand::UpdateSettings { after: next_after, .. }) => {
+                *after = next_after.clone();
+                true
+            },
+            (EditCommand::Group(commands), EditCommand::Group(next_commands)) => {
+                commands.len() == next_commands.len()
+                    && commands.iter_mut().zip(next_commands).all(|(command, next)| command.merge(next))
+            },
+            _ => false,
+        }
+    }
+}
+
+// Страница с изменением становится текущей, чтобы результат отмены был виден
+fn page_mut<'a>(site: &'a mut Site, page_id: &str) -> Option<&'a mut Page> {
+    let index = site.page_index(page_id)?;
+    site.select_page(index);
+    Some(&mut site.pages[index])
+}
+
+// Шаг истории; шаги с одинаковым ключом объединяются, пока ключ не сброшен
+struct HistoryEntry {
+    command: EditCommand,
+    merge_key: Option<String>,
+    // Ширина экрана, выбранная при изменении
+    breakpoint: Option<String>,
+}
+
+#[derive(Default)]
+pub struct History {
+    // Старые шаги убираются с начала, когда их больше HISTORY_LIMIT
+    undo_stack: VecDeque<HistoryEntry>,
+    redo_stack: Vec<HistoryEntry>,
+    // Ширина экрана, выбранная в редакторе сейчас
+    breakpoint: Option<String>,
+    // Номер состояния: растет при каждой записи, отмене, повторе и смене ширины,
+    // по нему редактор узнает, что снятые раньше копии элементов устарели
+    revision: u64,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Записывает уже выполненное изменение отдельным шагом
+    pub fn push(&mut self, command: EditCommand) {
+        self.push_entry(command, None);
+    }
+
+    // Записывает изменение, объединяя его с предыдущим шагом с тем же ключом
+    pub fn push_merged(&mut self, merge_key: String, command: EditCommand) {
+        if let Some(last) = self.undo_stack.back_mut()
+            && last.merge_key.as_deref() == Some(merge_key.as_str())
+            && last.command.merge(&command) {
+            self.redo_stack.clear();
+            self.revision += 1;
+            return;
+        }
+        self.push_entry(command, Some(merge_key));
+    }
+
+    // Завершает объединение: следующее изменение станет новым шагом
+    pub fn seal(&mut self) {
+        if let Some(last) = self.undo_stack.back_mut() {
+            last.merge_key = None;
+        }
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    // Выбор другой ширины экрана: следующие изменения относятся к ней
+    pub fn set_breakpoint(&mut self, breakpoint: Option<String>) {
+        self.seal();
+        self.breakpoint = breakpoint;
+        self.revision += 1;
+    }
+
+    // Отменяет последний шаг
+    pub fn undo(&mut self, site: &mut Site) -> bool {
+        let Some(entry) = self.undo_stack.pop_back() else {
+            return false;
+        };
+        self.switch_to(site, &entry);
+        entry.command.undo(site);
+        self.redo_stack.push(entry);
+        self.revision += 1;
+        true
+    }
+
+    // Повторяет последний отмененный шаг
+    pub fn redo(&mut self, site: &mut Site) -> bool {
+        let Some(mut entry) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.switch_to(site, &entry);
+        entry.command.redo(site);
+        entry.merge_key = None;
+        self.undo_stack.push_back(entry);
+        self.revision += 1;
+        true
+    }
+
+    // Очищает историю (например, после открытия другого проекта)
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.breakpoint = None;
+        self.revision += 1;
+    }
+
+    // Переключает сайт на ширину экрана, на которой был сделан шаг
+    fn switch_to(&mut self, site: &mut Site, entry: &HistoryEntry) {
+        site.set_breakpoint(entry.breakpoint.clone());
+        self.breakpoint = site.active_breakpoint.clone();
+    }
+
+    fn push_entry(&mut self, command: EditCommand, merge_key: Option<String>) {
+        self.seal();
+        let breakpoint = self.breakpoint.clone();
+        self.undo_stack.push_back(HistoryEntry { command, merge_key, breakpoint });
+        self.redo_stack.clear();
+        self.revision += 1;
+        if self.undo_stack.len() > HISTORY_LIMIT {
+            self.undo_stack.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::texts::text::Text;
+
+    fn site_with_text() -> (Site, String, String) {
+        let mut site = Site::new();
+        let text = Text::new();
+        let element_id = text.base.id.clone();
+        site.current_page_mut().add_element(Box::new(text));
+        let page_id = site.current_page().id.clone();
+        (site, page_id, element_id)
+    }
+
+    // Перемещает элемент и возвращает команду с состояниями до и после
+    fn move_element(site: &mut Site, page_id: &str, element_id: &str, position: (f32, f32)) -> EditCommand {
+        let element = site.current_page_mut().find_element_mut(element_id).unwrap();
+        let before = element.clone_box();
+        element.set_position(position);
+        EditCommand::UpdateElement { page_id: page_id.to_string(), before, after: element.clone_box() }
+    }
+
+    fn position(site: &Site, element_id: &str) -> (f32, f32) {
+        site.current_page().find_element(element_id).unwrap().get_position()
+    }
+
+    #[test]
+    fn undo_and_redo_restore_states() {
+        let (mut site, page_id, element_id) = site_with_text();
+        let mut history = History::new();
+        let command = move_element(&mut site, &page_id, &element_id, (10.0, 20.0));
+        history.push(command);
+
+        assert!(history.undo(&mut site));
+        assert_eq!(position(&site, &element_id), (100.0, 100.0));
+        assert!(!history.can_undo());
+        assert!(history.redo(&mut site));
+        assert_eq!(position(&site, &element_id), (10.0, 20.0));
+        assert!(!history.redo(&mut site));
+    }
+
+    #[test]
+    fn new_step_clears_redo() {
+        let (mut site, page_id, element_id) = site_with_text();
+        let mut history = History::new();
+        let command = move_element(&mut site, &page_id, &element_id, (10.0, 20.0));
+        history.push(command);
+        history.undo(&mut site);
+
+        let command = move_element(&mut site, &page_id, &element_id, (30.0, 40.0));
+        history.push(command);
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn steps_with_same_key_merge_until_sealed() {
+        let (mut site, page_id, element_id) = site_with_text();
+        let mut history = History::new();
+        for x in [10.0, 20.0, 30.0] {
+            let command = move_element(&mut site, &page_id, &element_id, (x, 0.0));
+            history.push_merged("drag".to_string(), command);
+        }
+        // Отпускание мыши завершает шаг
+        history.seal();
+        let command = move_element(&mut site, &page_id, &element_id, (40.0, 0.0));
+        history.push_merged("drag".to_string(), command);
+        let command = move_element(&mut site, &page_id, &element_id, (50.0, 0.0));
+        history.push_merged("other".to_string(), command);
+
+        history.undo(&mut site);
+        assert_eq!(position(&site, &element_id), (40.0, 0.0));
+        history.undo(&mut site);
+        assert_eq!(position(&site, &element_id), (30.0, 0.0));
+        history.undo(&mut site);
+        assert_eq!(position(&site, &element_id), (100.0, 100.0));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn group_is_undone_in_reverse_order() {
+        let (mut site, page_id, element_id) = site_with_text();
+        let mut history = History::new();
+        let text = Text::new();
+        let second_id = text.base.id.clone();
+        site.current_page_mut().add_element(Box::new(text));
+        let insert = EditCommand::InsertElement {
+            page_id: page_id.clone(),
+            parent_id: None,
+            index: 1,
+            element: site.current_page().elements[1].clone_box(),
+        };
+        let first_move = move_element(&mut site, &page_id, &element_id, (1.0, 1.0));
+        let second_move = move_element(&mut site, &page_id, &second_id, (2.0, 2.0));
+        history.push(EditCommand::Group(vec![insert, first_move, second_move]));
+
+        history.undo(&mut site);
+        assert_eq!(site.current_page().elements.len(), 1);
+        assert_eq!(position(&site, &element_id), (100.0, 100.0));
+        history.redo(&mut site);
+        assert_eq!(position(&site, &second_id), (2.0, 2.0));
+        assert_eq!(position(&site, &element_id), (1.0, 1.0));
+    }
+
+    #[test]
+    fn oldest_steps_are_dropped_over_limit() {
+        let (mut site, page_id, element_id) = site_with_text();
+        let mut history = History::new();
+        for step in 0..HISTORY_LIMIT + 5 {
+            let command = move_element(&mut site, &page_id, &element_id, (step as f32, 0.0));
+            history.push(command);
+        }
+
+        let mut steps = 0;
+        while history.undo(&mut site) {
+            steps += 1;
+        }
+        assert_eq!(steps, HISTORY_LIMIT);
+        // Первые пять шагов отменить уже нельзя
+        assert_eq!(position(&site, &element_id), (4.0, 0.0));
+    }
+
+    #[test]
+    fn page_commands_keep_only_changed_page() {
+        let mut site = Site::new();
+        let mut history = History::new();
+        let index = site.add_page();
+        history.push(EditCommand::InsertPage { index, page: site.pages[index].clone(), before_current: 0 });
+        site.move_page(1, 0);
+        history.push(EditCommand::MovePage { from: 1, to: 0 });
+        let page = site.pages[1].clone();
+        site.remove_page(1);
+        history.push(EditCommand::RemovePage { index: 1, page });
+        let names = |site: &Site| site.pages.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&site), vec!["page"]);
+
+        history.undo(&mut site);
+        assert_eq!(names(&site), vec!["page", "home"]);
+        assert_eq!(site.current_page, 1);
+        history.undo(&mut site);
+        assert_eq!(names(&site), vec!["home", "page"]);
+        history.undo(&mut site);
+        assert_eq!(names(&site), vec!["home"]);
+        assert_eq!(site.current_page, 0);
+
+        history.redo(&mut site);
+        history.redo(&mut site);
+        history.redo(&mut site);
+        assert_eq!(names(&site), vec!["page"]);
+    }
+    #[test]
+    fn removed_breakpoint_takes_overrides_along() {
+        let (mut site, _, element_id) = site_with_text();
+        let mut history = History::new();
+        site.set_breakpoint(Some("mobile".to_string()));
+        site.current_page_mut().find_element_mut(&element_id).unwrap().set_position((5.0, 5.0));
+        site.set_breakpoint(None);
+
+        let before = site.settings.clone();
+        site.settings.breakpoints.retain(|b| b.id != "mobile");
+        let overrides = site.take_breakpoint_overrides("mobile");
+        assert_eq!(overrides.len(), 1);
+        history.push(EditCommand::RemoveBreakpoint {
+            before,
+            after: site.settings.clone(),
+            breakpoint_id: "mobile".to_string(),
+            overrides,
+        });
+        let has_override = |site: &Site| site.current_page().find_element(&element_id).unwrap()
+            .base().overrides.contains_key("mobile");
+        assert!(!has_override(&site));
+
+        history.undo(&mut site);
+        assert!(site.settings.breakpoints.iter().any(|b| b.id == "mobile"));
+        assert!(has_override(&site));
+        history.redo(&mut site);
+        assert!(!site.settings.breakpoints.iter().any(|b| b.id == "mobile"));
+        assert!(!has_override(&site));
+    }
+}
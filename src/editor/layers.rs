@@ -0,0 +1,285 @@
+// Панель слоев: дерево элементов страницы (верхний слой - первым), изменение порядка
+// и вложенности перетаскиванием, скрытие и блокировка элементов
+
+use std::collections::HashSet;
+
+use egui::{Color32, Pos2, Rect, RichText, Sense, Stroke, Ui, Vec2};
+
+use crate::elements::UIElement;
+use crate::models::page::Page;
+
+use super::Editor;
+use super::canvas::ElementSnapshot;
+use super::history::EditCommand;
+
+// Место стрелки раскрытия перед именем слоя
+const ARROW_SIZE: Vec2 = Vec2::new(16.0, 16.0);
+
+// Куда будет брошен перетаскиваемый слой: родитель и позиция в его списке
+struct DropTarget {
+    parent_id: Option<String>,
+    index: usize,
+}
+
+// Действие, выбранное в панели; выполняется после отрисовки дерева
+enum LayerAction {
+    Select { element_id: String, additive: bool },
+    ToggleHidden(String),
+    ToggleLocked(String),
+    ToggleCollapsed(String),
+}
+
+// Состояние отрисовки дерева слоев за один кадр
+struct LayerTree<'a> {
+    selected_ids: &'a [String],
+    collapsed: &'a HashSet<String>,
+    dragged_id: Option<&'a str>,
+    pointer: Option<Pos2>,
+    actions: Vec<LayerAction>,
+    drop_target: Option<DropTarget>,
+    drag_started: Option<String>,
+}
+
+impl LayerTree<'_> {
+    // Строки списка элементов: последний в списке (верхний слой) показывается первым.
+    // droppable - можно ли бросить слой рядом с этими строками (нельзя внутрь самого перетаскиваемого)
+    fn show_rows(&mut self, ui: &mut Ui, elements: &[Box<dyn UIElement>], parent_id: Option<&str>, droppable: bool) {
+        for (index, element) in elements.iter().enumerate().rev() {
+            self.show_row(ui, element.as_ref(), parent_id, index, droppable);
+        }
+    }
+
+    fn show_row(&mut self, ui: &mut Ui, element: &dyn UIElement, parent_id: Option<&str>, index: usize, droppable: bool) {
+        let base = element.base();
+        let element_id = base.id.clone();
+        let children = element.children();
+        let collapsed = self.collapsed.contains(&element_id);
+        let selected = self.selected_ids.contains(&element_id);
+
+        let row = ui.horizontal(|ui| {
+            if children.is_some() {
+                let arrow = if collapsed { "▶" } else { "▼" };
+                if ui.add_sized(ARROW_SIZE, egui::Button::new(arrow).frame(false)).clicked() {
+                    self.actions.push(LayerAction::ToggleCollapsed(element_id.clone()));
+                }
+            } else {
+                ui.allocate_exact_size(ARROW_SIZE, Sense::hover());
+            }
+
+            if ui.selectable_label(!base.hidden, "👁").on_hover_text("Показать или скрыть на холсте").clicked() {
+                self.actions.push(LayerAction::ToggleHidden(element_id.clone()));
+            }
+            if ui.selectable_label(base.locked, "🔒").on_hover_text("Заблокировать на холсте").clicked() {
+                self.actions.push(LayerAction::ToggleLocked(element_id.clone()));
+            }
+
+            let mut name = RichText::new(base.display_name());
+            if base.hidden || base.site_hidden {
+                name = name.weak();
+            }
+            let response = ui.add(egui::SelectableLabel::new(selected, name)).interact(Sense::drag());
+            if response.clicked() {
+                let additive = ui.input(|i| i.modifiers.shift || i.modifiers.command);
+                self.actions.push(LayerAction::Select { element_id: element_id.clone(), additive });
+            }
+            if response.drag_started() {
+                self.drag_started = Some(element_id.clone());
+            }
+        });
+
+        let is_dragged = self.dragged_id == Some(element_id.as_str());
+        if droppable && !is_dragged {
+            self.update_drop_target(ui, row.response.rect, &element_id, children.is_some(), parent_id, index);
+        }
+
+        if let Some(children) = children
+            && !collapsed {
+            ui.indent(&element_id, |ui| {
+                self.show_rows(ui, children, Some(&element_id), droppable && !is_dragged);
+            });
+        }
+    }
+
+    // Если курсор над строкой, определяет место вставки и рисует подсказку:
+    // верхняя часть строки - над слоем, нижняя - под ним, середина контейнера - внутрь
+    fn update_drop_target(
+        &mut self,
+        ui: &Ui,
+        rect: Rect,
+        element_id: &str,
+        is_container: bool,
+        parent_id: Option<&str>,
+        index: usize,
+    ) {
+        let (Some(_), Some(pointer)) = (self.dragged_id, self.pointer) else {
+            return;
+        };
+        if !rect.y_range().contains(pointer.y) {
+            return;
+        }
+
+        let t = (pointer.y - rect.top()) / rect.height().max(1.0);
+        let (above, below) = if is_container { (0.25, 0.75) } else { (0.5, 0.5) };
+        let stroke = Stroke::new(2.0, Color32::from_rgb(33, 150, 243));
+        let x_range = rect.left()..=ui.max_rect().right();
+
+        // Слой выше в панели - дальше в списке родителя
+        if t < above {
+            ui.painter().hline(x_range, rect.top(), stroke);
+            self.drop_target = Some(DropTarget { parent_id: parent_id.map(str::to_string), index: index + 1 });
+        } else if t > below {
+            ui.painter().hline(x_range, rect.bottom(), stroke);
+            self.drop_target = Some(DropTarget { parent_id: parent_id.map(str::to_string), index });
+        } else {
+            ui.painter().rect_stroke(rect, 2.0, stroke);
+            self.drop_target = Some(DropTarget { parent_id: Some(element_id.to_string()), index: usize::MAX });
+        }
+    }
+}
+
+impl Editor {
+    pub(super) fn show_layers_panel(&mut self, ui: &mut Ui, page: &mut Page) {
+        ui.heading("Слои");
+
+        ui.horizontal(|ui| {
+            let has_selection = !self.selected_ids.is_empty();
+            if ui.add_enabled(has_selection, egui::Button::new("На передний план")).clicked() {
+                self.reorder_selection(page, true);
+            }
+            if ui.add_enabled(has_selection, egui::Button::new("На задний план")).clicked() {
+                self.reorder_selection(page, false);
+            }
+        });
+
+        ui.separator();
+
+        if page.elements.is_empty() {
+            ui.label("На странице нет элементов");
+            return;
+        }
+
+        let mut tree = LayerTree {
+            selected_ids: &self.selected_ids,
+            collapsed: &self.collapsed_layers,
+            dragged_id: self.layer_drag.as_deref(),
+            pointer: ui.ctx().pointer_latest_pos(),
+            actions: Vec::new(),
+            drop_target: None,
+            drag_started: None,
+        };
+        egui::ScrollArea::vertical().id_source("layers").show(ui, |ui| {
+            tree.show_rows(ui, &page.elements, None, true);
+        });
+        let LayerTree { actions, drop_target, drag_started, .. } = tree;
+
+        for action in actions {
+            self.apply_layer_action(page, action);
+        }
+
+        if drag_started.is_some() {
+            self.layer_drag = drag_started;
+        }
+        if self.layer_drag.is_some() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::Grabbing);
+            if ui.input(|i| i.pointer.any_released())
+                && let Some(element_id) = self.layer_drag.take()
+                && let Some(target) = drop_target {
+                self.move_layer(page, &element_id, target.parent_id.as_deref(), target.index);
+            }
+        }
+    }
+
+    fn apply_layer_action(&mut self, page: &mut Page, action: LayerAction) {
+        match action {
+            LayerAction::Select { element_id, additive } => {
+                if additive {
+                    self.toggle_selection(element_id);
+                } else {
+                    self.select_only(element_id);
+                }
+            },
+            LayerAction::ToggleHidden(element_id) => {
+                self.update_layer(page, &element_id, |element| {
+                    let base = element.base_mut();
+                    base.hidden = !base.hidden;
+                });
+                // Скрытый элемент не остается выделенным на холсте
+                if page.find_element(&element_id).is_some_and(|e| e.base().hidden) {
+                    self.selected_ids.retain(|id| *id != element_id);
+                }
+            },
+            LayerAction::ToggleLocked(element_id) => {
+                self.update_layer(page, &element_id, |element| {
+                    let base = element.base_mut();
+                    base.locked = !base.locked;
+                });
+            },
+            LayerAction::ToggleCollapsed(element_id) => {
+                if !self.collapsed_layers.remove(&element_id) {
+                    self.collapsed_layers.insert(element_id);
+                }
+            },
+        }
+    }
+
+    // Изменяет элемент и записывает изменение в историю
+    fn update_layer(&mut self, page: &mut Page, element_id: &str, change: impl FnOnce(&mut Box<dyn UIElement>)) {
+        let page_id = page.id.clone();
+        let Some(element) = page.find_element_mut(element_id) else {
+            return;
+        };
+        let before = element.clone_box();
+        change(element);
+        self.history.push(EditCommand::UpdateElement {
+            page_id,
+            before,
+            after: element.clone_box(),
+        });
+    }
+
+    // Переносит слой в другое место дерева с записью в историю
+    fn move_layer(&mut self, page: &mut Page, element_id: &str, parent_id: Option<&str>, index: usize) {
+        let Some(before) = ElementSnapshot::take(page, element_id) else {
+            return;
+        };
+        if !page.move_element(element_id, parent_id, index) {
+            return;
+        }
+        if let Some(after) = ElementSnapshot::take(page, element_id)
+            && !before.same_place(&after) {
+            self.history.push(before.relocation(&page.id, after));
+        }
+    }
+
+    // Переносит выделенные элементы на передний или задний план внутри их родителей,
+    // сохраняя взаимный порядок
+    pub(super) fn reorder_selection(&mut self, page: &mut Page, to_front: bool) {
+        let mut element_ids: Vec<(usize, String)> = self.movable_selection(page).into_iter()
+            .filter_map(|id| page.element_location(&id).map(|(_, index)| (index, id)))
+            .collect();
+        element_ids.sort_by_key(|(index, _)| *index);
+        if !to_front {
+            element_ids.reverse();
+        }
+
+        let index = if to_front { usize::MAX } else { 0 };
+        let mut commands = Vec::new();
+        for (_, element_id) in element_ids {
+            let Some(before) = ElementSnapshot::take(page, &element_id) else {
+                continue;
+            };
+            let parent_id = page.parent_id(&element_id);
+            page.move_element(&element_id, parent_id.as_deref(), index);
+            if let Some(after) = ElementSnapshot::take(page, &element_id)
+                && !before.same_place(&after) {
+                commands.push(before.relocation(&page.id, after));
+            }
+        }
+
+        match commands.len() {
+            0 => {},
+            1 => self.history.push(commands.remove(0)),
+            _ => self.history.push(EditCommand::Group(commands)),
+        }
+    }
+}
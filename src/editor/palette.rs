@@ -0,0 +1,157 @@
+// Палитра элементов: виды элементов из реестра по разделам с поиском.
+// Щелчок добавляет элемент в центр видимой части холста, перетаскивание - под курсор,
+// пока кнопка мыши зажата, за курсором следует полупрозрачный предпросмотр элемента
+
+use egui::{Color32, CursorIcon, Id, LayerId, Order, Pos2, Rect, Sense, Stroke, Ui, Vec2};
+
+use crate::elements::UIElement;
+use crate::elements::breakpoints::switch_breakpoint;
+use crate::elements::canvas::CanvasContext;
+use crate::elements::registry::{self, ElementKind};
+use crate::models::page::Page;
+
+use super::Editor;
+use super::canvas::element_rect;
+
+// Высота строки палитры
+const ITEM_HEIGHT: f32 = 24.0;
+
+// Элемент, перетаскиваемый из палитры на холст
+pub(super) struct PaletteDrag {
+    element: Box<dyn UIElement>,
+}
+
+impl Editor {
+    pub(super) fn show_elements_panel(&mut self, ui: &mut Ui, page: &mut Page) {
+        ui.heading("Элементы");
+        ui.add(egui::TextEdit::singleline(&mut self.palette_search).hint_text("Поиск элементов"));
+
+        ui.separator();
+
+        let kinds: Vec<ElementKind> = registry::element_kinds().into_iter()
+            .filter(|kind| kind.matches(&self.palette_search))
+            .collect();
+        if kinds.is_empty() {
+            ui.label("Ничего не найдено");
+            return;
+        }
+
+        // Разделы в порядке регистрации первого вида в них; при поиске все раскрыты
+        let mut categories: Vec<&str> = Vec::new();
+        for kind in &kinds {
+            if !categories.contains(&kind.category.as_str()) {
+                categories.push(&kind.category);
+            }
+        }
+        let searching = !self.palette_search.trim().is_empty();
+
+        let mut clicked = None;
+        for category in categories {
+            egui::CollapsingHeader::new(category)
+                .id_source(("palette_category", category))
+                .default_open(true)
+                .open(searching.then_some(true))
+                .show(ui, |ui| {
+                    for kind in kinds.iter().filter(|kind| kind.category == category) {
+                        if self.show_palette_item(ui, kind) {
+                            clicked = Some(kind);
+                        }
+                    }
+                });
+        }
+
+        if let Some(kind) = clicked {
+            let center = self.viewport.to_page(self.viewport.area().center());
+            self.insert_new_element(page, kind.create(), center);
+        }
+    }
+
+    // Строка палитры; возвращает, был ли по ней щелчок
+    fn show_palette_item(&mut self, ui: &mut Ui, kind: &ElementKind) -> bool {
+        let label = format!("{}  {}", kind.icon, kind.name);
+        let button = egui::Button::new(label).sense(Sense::click_and_drag());
+        let response = ui.add_sized([ui.available_width(), ITEM_HEIGHT], button)
+            .on_hover_text("Щелчок - добавить в центр холста, перетаскивание - в нужное место");
+
+        if response.drag_started() {
+            self.palette_drag = Some(PaletteDrag { element: kind.create() });
+        }
+        if response.dragged() {
+            ui.ctx().set_cursor_icon(CursorIcon::Grabbing);
+        }
+        response.clicked()
+    }
+
+    // Добавляет на страницу новый элемент с центром в точке pos (в координатах страницы)
+    // и выделяет его. Элемент, брошенный на контейнер, становится его дочерним элементом
+    fn insert_new_element(&mut self, page: &mut Page, mut element: Box<dyn UIElement>, pos: Pos2) {
+        // Значения нового элемента становятся основными, дальнейшие правки - отличиями выбранной ширины
+        switch_breakpoint(element.as_mut(), None, self.breakpoint.as_deref());
+
+        let parent = page.find_container_at_point((pos.x, pos.y), None)
+            .map(|container| container.get_id().to_string());
+        let origin = parent.as_deref()
+            .and_then(|id| page.absolute_position(id))
+            .unwrap_or((0.0, 0.0));
+
+        let size = element.get_size();
+        element.set_position((pos.x - origin.0 - size.0 / 2.0, pos.y - origin.1 - size.1 / 2.0));
+        let element_id = element.get_id().to_string();
+        page.insert_element(parent.as_deref(), element);
+        Self::place_in_flow(page, &element_id, pos);
+        self.record_insert(page, &element_id);
+        self.select_only(element_id);
+    }
+
+    // Перетаскивание из палитры: пока кнопка мыши зажата, рисуется предпросмотр,
+    // отпускание над холстом добавляет элемент, в другом месте - отменяет перетаскивание
+    pub(super) fn update_palette_drag(&mut self, ui: &Ui, page: &mut Page) {
+        if self.palette_drag.is_none() {
+            return;
+        }
+        let pointer = ui.ctx().pointer_latest_pos();
+        let over_canvas = pointer.filter(|pos| self.viewport.area().contains(*pos));
+
+        if ui.input(|i| i.pointer.primary_down()) {
+            if let Some(pos) = pointer {
+                self.draw_palette_ghost(ui, page, pos, over_canvas.is_some());
+            }
+            return;
+        }
+
+        if let Some(drag) = self.palette_drag.take()
+            && let Some(pos) = over_canvas {
+            self.insert_new_element(page, drag.element, self.viewport.to_page(pos));
+        }
+    }
+
+    // Предпросмотр перетаскиваемого элемента под курсором в масштабе холста.
+    // Над холстом подсвечивается контейнер, в который попадет элемент
+    fn draw_palette_ghost(&self, ui: &Ui, page: &Page, pos: Pos2, over_canvas: bool) {
+        let Some(drag) = &self.palette_drag else {
+            return;
+        };
+        let zoom = self.viewport.zoom();
+
+        if over_canvas {
+            let point = self.viewport.to_page(pos);
+            if let Some(container) = page.find_container_at_point((point.x, point.y), None)
+                && let Some(target) = element_rect(page, container.get_id()) {
+                let painter = ui.painter().with_clip_rect(self.viewport.area());
+                painter.rect_stroke(self.viewport.rect_to_screen(target), 0.0, Stroke::new(2.0, Color32::from_rgb(76, 175, 80)));
+            }
+        }
+
+        // Центр элемента совпадает с курсором
+        let element = &drag.element;
+        let (position, size) = (element.get_position(), element.get_size());
+        let origin = pos.to_vec2() - (Vec2::new(position.0, position.1) + Vec2::new(size.0, size.1) / 2.0) * zoom;
+        let canvas = CanvasContext::new(origin, zoom, &[]);
+        let painter = ui.ctx().layer_painter(LayerId::new(Order::Tooltip, Id::new("palette_ghost")));
+        element.render(&painter, &canvas);
+
+        let rect = Rect::from_center_size(pos, Vec2::new(size.0, size.1) * zoom);
+        painter.rect_filled(rect, 0.0, Color32::from_white_alpha(110));
+        painter.rect_stroke(rect, 0.0, Stroke::new(1.0, Color32::from_rgb(33, 150, 243)));
+    }
+}
@@ -0,0 +1,98 @@
+// Маркеры изменения размера выделенного элемента на холсте
+
+use egui::{CursorIcon, Pos2, Rect, Vec2};
+
+// Половина стороны квадратного маркера
+pub const HANDLE_RADIUS: f32 = 4.0;
+// Минимальный размер элемента при изменении мышью
+const MIN_SIZE: f32 = 10.0;
+
+// Угол или середина стороны, за которую тянут элемент
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ResizeHandle {
+    TopLeft,
+    Top,
+    TopRight,
+    Right,
+    BottomRight,
+    Bottom,
+    BottomLeft,
+    Left,
+}
+
+impl ResizeHandle {
+    pub const ALL: [ResizeHandle; 8] = [
+        ResizeHandle::TopLeft, ResizeHandle::Top, ResizeHandle::TopRight, ResizeHandle::Right,
+        ResizeHandle::BottomRight, ResizeHandle::Bottom, ResizeHandle::BottomLeft, ResizeHandle::Left,
+    ];
+    
+    // Какие стороны двигает маркер: (-1 - левая/верхняя, 1 - правая/нижняя, 0 - никакая)
+    pub fn sides(&self) -> (i8, i8) {
+        match self {
+            ResizeHandle::TopLeft => (-1, -1),
+            ResizeHandle::Top => (0, -1),
+            ResizeHandle::TopRight => (1, -1),
+            ResizeHandle::Right => (1, 0),
+            ResizeHandle::BottomRight => (1, 1),
+            ResizeHandle::Bottom => (0, 1),
+            ResizeHandle::BottomLeft => (-1, 1),
+            ResizeHandle::Left => (-1, 0),
+        }
+    }
+    
+    // Центр маркера на прямоугольнике элемента
+    pub fn position(&self, rect: Rect) -> Pos2 {
+        let (x, y) = self.sides();
+        let px = match x {
+            -1 => rect.left(),
+            1 => rect.right(),
+            _ => rect.center().x,
+        };
+        let py = match y {
+            -1 => rect.top(),
+            1 => rect.bottom(),
+            _ => rect.center().y,
+        };
+        Pos2::new(px, py)
+    }
+    
+    // Прямоугольник маркера для отрисовки и попадания мышью
+    pub fn rect(&self, rect: Rect) -> Rect {
+        Rect::from_center_size(self.position(rect), Vec2::splat(HANDLE_RADIUS * 2.0))
+    }
+    
+    // Маркер под курсором (с небольшим запасом вокруг)
+    pub fn at_point(rect: Rect, point: Pos2) -> Option<ResizeHandle> {
+        ResizeHandle::ALL.into_iter()
+            .find(|handle| handle.rect(rect).expand(2.0).contains(point))
+    }
+    
+    pub fn cursor(&self) -> CursorIcon {
+        match self {
+            ResizeHandle::TopLeft | ResizeHandle::BottomRight => CursorIcon::ResizeNwSe,
+            ResizeHandle::TopRight | ResizeHandle::BottomLeft => CursorIcon::ResizeNeSw,
+            ResizeHandle::Top | ResizeHandle::Bottom => CursorIcon::ResizeVertical,
+            ResizeHandle::Left | ResizeHandle::Right => CursorIcon::ResizeHorizontal,
+        }
+    }
+    
+    // Новый прямоугольник после сдвига маркера на delta; противоположная сторона остается на месте
+    pub fn apply(&self, start: Rect, delta: Vec2) -> Rect {
+        let (x, y) = self.sides();
+        let mut min = start.min;
+        let mut max = start.max;
+        
+        match x {
+            -1 => min.x = (min.x + delta.x).min(max.x - MIN_SIZE),
+            1 => max.x = (max.x + delta.x).max(min.x + MIN_SIZE),
+            _ => {}
+        }
+        match y {
+            -1 => min.y = (min.y + delta.y).min(max.y - MIN_SIZE),
+            1 => max.y = (max.y + delta.y).max(min.y + MIN_SIZE),
+            _ => {}
+        }
+        
+        Rect::from_min_max(min, max)
+    }
+}
@@ -0,0 +1,121 @@
+// Линейки вдоль верхнего и левого края холста с делениями в пикселях страницы
+
+use egui::epaint::TextShape;
+use egui::{Color32, Painter, Pos2, Rect, Stroke};
+
+use super::viewport::Viewport;
+
+// Толщина линеек
+pub const RULER_SIZE: f32 = 20.0;
+
+// Наименьшее расстояние между подписанными делениями на экране
+const MIN_LABEL_SPACING: f32 = 50.0;
+
+// Шаг подписанных делений в пикселях страницы (1, 2 или 5 с нужным числом нулей)
+// и число мелких делений в нем
+fn ruler_step(zoom: f32) -> (f32, u32) {
+    let mut power = 0.01;
+    loop {
+        for (mantissa, subdivisions) in [(1.0, 5), (2.0, 4), (5.0, 5)] {
+            let step = power * mantissa;
+            if step * zoom >= MIN_LABEL_SPACING {
+                return (step, subdivisions);
+            }
+        }
+        power *= 10.0;
+    }
+}
+
+// Подпись деления без лишних нулей
+fn label(value: f32) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    if rounded.fract() == 0.0 {
+        format!("{}", rounded as i64)
+    } else {
+        format!("{}", rounded)
+    }
+}
+
+// Рисует линейки над холстом и слева от него; pointer - курсор, отмечаемый на линейках
+pub fn draw_rulers(painter: &Painter, viewport: &Viewport, pointer: Option<Pos2>) {
+    let area = viewport.area();
+    let top = Rect::from_min_max(Pos2::new(area.left(), area.top() - RULER_SIZE), Pos2::new(area.right(), area.top()));
+    let left = Rect::from_min_max(Pos2::new(area.left() - RULER_SIZE, area.top()), Pos2::new(area.left(), area.bottom()));
+    draw_ruler(&painter.with_clip_rect(top), top, true, viewport, pointer);
+    draw_ruler(&painter.with_clip_rect(left), left, false, viewport, pointer);
+
+    // Уголок между линейками
+    let corner = Rect::from_min_max(area.min - egui::Vec2::splat(RULER_SIZE), area.min);
+    painter.rect_filled(corner, 0.0, Color32::from_gray(245));
+    painter.rect_stroke(corner, 0.0, Stroke::new(1.0, Color32::from_gray(200)));
+}
+
+// Одна линейка: horizontal - верхняя, иначе левая
+fn draw_ruler(painter: &Painter, ruler: Rect, horizontal: bool, viewport: &Viewport, pointer: Option<Pos2>) {
+    // Координата точки вдоль линейки
+    let along = |pos: Pos2| if horizontal { pos.x } else { pos.y };
+
+    // Участок, занятый страницей, светлее остальной линейки
+    painter.rect_filled(ruler, 0.0, Color32::from_gray(235));
+    let page = viewport.rect_to_screen(viewport.page_rect());
+    let page_span = if horizontal {
+        Rect::from_x_y_ranges(page.left()..=page.right(), ruler.top()..=ruler.bottom())
+    } else {
+        Rect::from_x_y_ranges(ruler.left()..=ruler.right(), page.top()..=page.bottom())
+    };
+    painter.rect_filled(page_span.intersect(ruler), 0.0, Color32::from_gray(250));
+
+    let (step, subdivisions) = ruler_step(viewport.zoom());
+    let minor = step / subdivisions as f32;
+    let start = along(viewport.to_page(ruler.min));
+    let end = along(viewport.to_page(ruler.max));
+    let stroke = Stroke::new(1.0, Color32::from_gray(130));
+    let font = egui::FontId::proportional(9.0);
+
+    let first = (start / minor).floor() as i64;
+    let last = (end / minor).ceil() as i64;
+    for index in first..=last {
+        let value = index as f32 * minor;
+        let screen = viewport.to_screen(Pos2::new(value, value));
+        let major = index % subdivisions as i64 == 0;
+        let length = if major { RULER_SIZE } else { RULER_SIZE * 0.25 };
+
+        if horizontal {
+            let x = screen.x;
+            painter.line_segment([Pos2::new(x, ruler.bottom() - length), Pos2::new(x, ruler.bottom())], stroke);
+            if major {
+                painter.text(Pos2::new(x + 2.0, ruler.top() + 1.0), egui::Align2::LEFT_TOP, label(value), font.clone(), stroke.color);
+            }
+        } else {
+            let y = screen.y;
+            painter.line_segment([Pos2::new(ruler.right() - length, y), Pos2::new(ruler.right(), y)], stroke);
+            if major {
+                // Подписи левой линейки повернуты и читаются снизу вверх
+                let galley = painter.layout_no_wrap(label(value), font.clone(), stroke.color);
+                let mut text = TextShape::new(Pos2::new(ruler.left() + 1.0, y - 2.0), galley);
+                text.angle = -std::f32::consts::FRAC_PI_2;
+                painter.add(text);
+            }
+        }
+    }
+
+    // Граница линейки со стороны холста
+    let edge = if horizontal {
+        [ruler.left_bottom(), ruler.right_bottom()]
+    } else {
+        [ruler.right_top(), ruler.right_bottom()]
+    };
+    painter.line_segment(edge, Stroke::new(1.0, Color32::from_gray(200)));
+
+    // Положение курсора
+    if let Some(pos) = pointer.filter(|pos| viewport.area().contains(*pos)) {
+        let marker = Stroke::new(1.0, Color32::from_rgb(233, 30, 99));
+        let at = along(pos);
+        let line = if horizontal {
+            [Pos2::new(at, ruler.top()), Pos2::new(at, ruler.bottom())]
+        } else {
+            [Pos2::new(ruler.left(), at), Pos2::new(ruler.right(), at)]
+        };
+        painter.line_segment(line, marker);
+    }
+}
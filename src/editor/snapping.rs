@@ -0,0 +1,229 @@
+// Сетка и умные направляющие: привязка перемещаемых элементов к сетке,
+// краям и центрам других элементов и страницы
+
+use egui::{Color32, Painter, Pos2, Rect, Stroke};
+
+use crate::elements::UIElement;
+
+use super::viewport::Viewport;
+
+// Расстояние в экранных точках, на котором срабатывает привязка к направляющей
+const SNAP_DISTANCE: f32 = 6.0;
+
+// Настройки сетки холста
+pub struct GridSettings {
+    pub visible: bool,
+    pub snap_to_grid: bool,
+    pub size: f32,
+    // Привязка к краям и центрам других элементов
+    pub smart_guides: bool,
+}
+
+impl Default for GridSettings {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            snap_to_grid: false,
+            size: 10.0,
+            smart_guides: true,
+        }
+    }
+}
+
+impl GridSettings {
+    // Рисует линии сетки в пределах области страницы area (узлы сетки отсчитываются от начала страницы)
+    // При мелком масштабе, когда линии сливаются, сетка не рисуется
+    pub fn draw(&self, painter: &Painter, viewport: &Viewport, area: Rect) {
+        let step = self.size * viewport.zoom();
+        if !self.visible || step < 4.0 {
+            return;
+        }
+
+        let stroke = Stroke::new(1.0, Color32::from_gray(235));
+        let area = viewport.rect_to_screen(area).intersect(painter.clip_rect());
+        let origin = viewport.origin();
+        let mut x = ((area.left() - origin.x) / step).ceil() * step + origin.x;
+        while x <= area.right() {
+            painter.line_segment([Pos2::new(x, area.top()), Pos2::new(x, area.bottom())], stroke);
+            x += step;
+        }
+        let mut y = ((area.top() - origin.y) / step).ceil() * step + origin.y;
+        while y <= area.bottom() {
+            painter.line_segment([Pos2::new(area.left(), y), Pos2::new(area.right(), y)], stroke);
+            y += step;
+        }
+    }
+}
+
+// Направляющая, к которой привязался элемент
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Guide {
+    Vertical(f32),
+    Horizontal(f32),
+}
+
+// Линии, к которым можно привязаться: края и центры других элементов и страницы
+pub struct Snapper<'a> {
+    grid: &'a GridSettings,
+    vertical: Vec<f32>,
+    horizontal: Vec<f32>,
+    // Расстояние привязки в пикселях страницы при текущем масштабе
+    distance: f32,
+}
+
+impl<'a> Snapper<'a> {
+    // page - прямоугольник страницы, targets - прямоугольники остальных элементов,
+    // zoom - масштаб холста
+    pub fn new(grid: &'a GridSettings, page: Rect, targets: &[Rect], zoom: f32) -> Self {
+        let mut vertical = Vec::new();
+        let mut horizontal = Vec::new();
+        if grid.smart_guides {
+            for rect in std::iter::once(&page).chain(targets) {
+                vertical.extend([rect.left(), rect.center().x, rect.right()]);
+                horizontal.extend([rect.top(), rect.center().y, rect.bottom()]);
+            }
+        }
+        Self { grid, vertical, horizontal, distance: SNAP_DISTANCE / zoom }
+    }
+
+    // Сдвиг по одной оси: ближайшая направляющая, иначе узел сетки для первой из линий
+    fn snap_axis(&self, lines: &[f32], targets: &[f32]) -> Option<(f32, Option<f32>)> {
+        let best = lines.iter()
+            .flat_map(|line| targets.iter().map(move |target| (target - line, *target)))
+            .filter(|(delta, _)| delta.abs() <= self.distance)
+            .min_by(|a, b| a.0.abs().total_cmp(&b.0.abs()));
+        if let Some((delta, target)) = best {
+            return Some((delta, Some(target)));
+        }
+
+        if self.grid.snap_to_grid && self.grid.size >= 1.0 {
+            let line = *lines.first()?;
+            let snapped = (line / self.grid.size).round() * self.grid.size;
+            return Some((snapped - line, None));
+        }
+        None
+    }
+
+    // Привязка перемещаемого прямоугольника: возвращает сдвиг и сработавшие направляющие
+    pub fn snap_move(&self, rect: Rect) -> (egui::Vec2, Vec<Guide>) {
+        let mut delta = egui::Vec2::ZERO;
+        let mut guides = Vec::new();
+
+        if let Some((dx, guide)) = self.snap_axis(&[rect.left(), rect.center().x, rect.right()], &self.vertical) {
+            delta.x = dx;
+            guides.extend(guide.map(Guide::Vertical));
+        }
+        if let Some((dy, guide)) = self.snap_axis(&[rect.top(), rect.center().y, rect.bottom()], &self.horizontal) {
+            delta.y = dy;
+            guides.extend(guide.map(Guide::Horizontal));
+        }
+        (delta, guides)
+    }
+
+    // Привязка отдельной вертикальной линии (края при изменении размера)
+    pub fn snap_x(&self, x: f32) -> (f32, Option<Guide>) {
+        match self.snap_axis(&[x], &self.vertical) {
+            Some((dx, guide)) => (x + dx, guide.map(Guide::Vertical)),
+            None => (x, None),
+        }
+    }
+
+    // Привязка отдельной горизонтальной линии
+    pub fn snap_y(&self, y: f32) -> (f32, Option<Guide>) {
+        match self.snap_axis(&[y], &self.horizontal) {
+            Some((dy, guide)) => (y + dy, guide.map(Guide::Horizontal)),
+            None => (y, None),
+        }
+    }
+}
+
+// Рисует сработавшие направляющие через всю область (направляющие и область - в координатах страницы)
+pub fn draw_guides(painter: &Painter, viewport: &Viewport, area: Rect, guides: &[Guide]) {
+    let stroke = Stroke::new(1.0, Color32::from_rgb(233, 30, 99));
+    for guide in guides {
+        let [from, to] = match *guide {
+            Guide::Vertical(x) => [Pos2::new(x, area.top()), Pos2::new(x, area.bottom())],
+            Guide::Horizontal(y) => [Pos2::new(area.left(), y), Pos2::new(area.right(), y)],
+        };
+        painter.line_segment([viewport.to_screen(from), viewport.to_screen(to)], stroke);
+    }
+}
+
+// Прямоугольники элементов в координатах страницы, кроме исключенных (вместе с их потомками)
+pub fn collect_rects(elements: &[Box<dyn UIElement>], origin: (f32, f32), exclude: &[String], rects: &mut Vec<Rect>) {
+    for element in elements {
+        if element.base().hidden || exclude.iter().any(|id| id == element.get_id()) {
+            continue;
+        }
+        let position = element.get_position();
+        let size = element.get_size();
+        let absolute = (origin.0 + position.0, origin.1 + position.1);
+        rects.push(Rect::from_min_size(Pos2::new(absolute.0, absolute.1), egui::vec2(size.0, size.1)));
+        if let Some(children) = element.children() {
+            collect_rects(children, absolute, exclude, rects);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::vec2;
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> Rect {
+        Rect::from_min_size(Pos2::new(x, y), vec2(width, height))
+    }
+
+    fn page() -> Rect {
+        rect(0.0, 0.0, 1000.0, 800.0)
+    }
+
+    #[test]
+    fn move_snaps_to_nearest_edge_or_center() {
+        let grid = GridSettings::default();
+        let target = rect(300.0, 300.0, 100.0, 100.0);
+        let snapper = Snapper::new(&grid, page(), &[target], 1.0);
+
+        // Левый край в 4px от правого края цели, верх в 2px от ее верха
+        let (delta, guides) = snapper.snap_move(rect(404.0, 302.0, 50.0, 50.0));
+        assert_eq!(delta, vec2(-4.0, -2.0));
+        assert_eq!(guides, vec![Guide::Vertical(400.0), Guide::Horizontal(300.0)]);
+
+        // Центр ближе к центру цели (1px), чем левый край к ее краю (5px)
+        let (delta, guides) = snapper.snap_move(rect(326.0, 600.0, 50.0, 20.0));
+        assert_eq!(delta.x, -1.0);
+        assert_eq!(guides.first(), Some(&Guide::Vertical(350.0)));
+    }
+
+    #[test]
+    fn snap_distance_shrinks_with_zoom() {
+        let grid = GridSettings::default();
+        let target = rect(300.0, 300.0, 100.0, 100.0);
+        let lines = |zoom: f32| Snapper::new(&grid, page(), &[target], zoom).snap_x(404.0);
+
+        assert_eq!(lines(1.0).0, 400.0);
+        // При масштабе 2 привязка срабатывает только ближе 3px страницы
+        assert_eq!(lines(2.0).0, 404.0);
+        assert!(lines(2.0).1.is_none());
+    }
+
+    #[test]
+    fn grid_snaps_first_line_without_guides() {
+        let grid = GridSettings { snap_to_grid: true, smart_guides: false, ..GridSettings::default() };
+        let snapper = Snapper::new(&grid, page(), &[], 1.0);
+
+        let (delta, guides) = snapper.snap_move(rect(23.0, 47.0, 30.0, 30.0));
+        assert_eq!(delta, vec2(-3.0, 3.0));
+        assert!(guides.is_empty());
+        assert_eq!(snapper.snap_y(14.0), (10.0, None));
+    }
+
+    #[test]
+    fn nothing_snaps_far_from_lines() {
+        let grid = GridSettings::default();
+        let snapper = Snapper::new(&grid, page(), &[], 1.0);
+        let (delta, guides) = snapper.snap_move(rect(120.0, 130.0, 40.0, 40.0));
+        assert_eq!(delta, egui::Vec2::ZERO);
+        assert!(guides.is_empty());
+    }
+}
@@ -0,0 +1,321 @@
+// Система координат холста. Элементы хранят положение в координатах страницы:
+// (0, 0) - левый верхний угол рамки страницы, где бы холст ни находился на экране.
+// Экранные точки (курсор) переводятся в координаты страницы при чтении,
+// координаты страницы в экранные - при отрисовке. Между ними - прокрутка и масштаб холста
+
+use egui::{Color32, Pos2, Rect, Sense, Ui, Vec2};
+
+use crate::elements::UIElement;
+
+// Поле вокруг рамки страницы на холсте (в экранных точках)
+const PAGE_MARGIN: f32 = 20.0;
+
+// Свободное место на странице под нижним элементом
+const PAGE_BOTTOM_SPACE: f32 = 100.0;
+
+// Пределы масштаба холста
+pub const MIN_ZOOM: f32 = 0.1;
+pub const MAX_ZOOM: f32 = 8.0;
+
+// Шаг масштаба кнопками и сочетаниями клавиш
+pub const ZOOM_STEP: f32 = 1.25;
+
+// Толщина полос прокрутки
+pub const SCROLLBAR_WIDTH: f32 = 10.0;
+
+// Положение и масштаб страницы на экране. Сохраняется между кадрами
+#[derive(Clone, Copy, Debug)]
+pub struct Viewport {
+    // Экранная область холста
+    area: Rect,
+    // Экранная точка начала координат страницы
+    origin: Pos2,
+    // Сколько экранных точек в одном пикселе страницы
+    zoom: f32,
+    // Размер рамки страницы
+    page_size: Vec2,
+    // Ширина страницы, для которой выбрано положение (бесконечная - по ширине холста)
+    page_width: Option<f32>,
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Viewport {
+    pub fn new() -> Self {
+        Self {
+            area: Rect::NOTHING,
+            origin: Pos2::ZERO,
+            zoom: 1.0,
+            page_size: Vec2::ZERO,
+            page_width: None,
+        }
+    }
+
+    // Обновление в начале кадра. area - экранная область холста, page_width - ширина страницы
+    // (бесконечная - по ширине холста), content_height - нижний край элементов страницы.
+    // При смене ширины страница заново выравнивается по центру холста
+    pub fn update(&mut self, area: Rect, page_width: f32, content_height: f32) {
+        // Холст сдвинулся вместе с панелями - страница сдвигается вместе с ним
+        if self.area.is_positive() {
+            self.origin += area.min - self.area.min;
+        }
+        self.area = area;
+        self.page_size = self.fit_page_size(page_width, content_height);
+
+        if self.page_width != Some(page_width) {
+            self.page_width = Some(page_width);
+            self.center_page();
+        }
+        self.clamp_scroll();
+    }
+
+    // Размер страницы: заданная ширина (или ширина холста) и высота не меньше видимой части холста
+    fn fit_page_size(&self, page_width: f32, content_height: f32) -> Vec2 {
+        let available = (self.area.size() - Vec2::splat(2.0 * PAGE_MARGIN)) / self.zoom;
+        let width = if page_width.is_finite() { page_width.max(1.0) } else { available.x.max(1.0) };
+        let height = available.y.max(content_height + PAGE_BOTTOM_SPACE);
+        Vec2::new(width, height)
+    }
+
+    // Страница по центру холста по горизонтали, верхний край - у верха холста
+    fn center_page(&mut self) {
+        let width = self.page_size.x * self.zoom;
+        let left = self.area.left() + PAGE_MARGIN + ((self.area.width() - 2.0 * PAGE_MARGIN - width) / 2.0).max(0.0);
+        self.origin = Pos2::new(left, self.area.top() + PAGE_MARGIN);
+    }
+
+    // Экранная область, которую можно прокручивать: рамка страницы с полями вокруг
+    pub fn content_rect(self) -> Rect {
+        self.rect_to_screen(self.page_rect()).expand(PAGE_MARGIN)
+    }
+
+    // Ограничивает прокрутку: страница, которая помещается на холсте, не уходит за его края,
+    // а большая страница не отрывается от них
+    fn clamp_scroll(&mut self) {
+        let content = self.content_rect();
+        let clamp = |start: f32, size: f32, area_start: f32, area_size: f32| -> f32 {
+            let (min, max) = if size <= area_size {
+                (area_start, area_start + area_size - size)
+            } else {
+                (area_start + area_size - size, area_start)
+            };
+            start.clamp(min, max) - start
+        };
+        let shift = Vec2::new(
+            clamp(content.left(), content.width(), self.area.left(), self.area.width()),
+            clamp(content.top(), content.height(), self.area.top(), self.area.height()),
+        );
+        self.origin += shift;
+    }
+
+    pub fn area(self) -> Rect {
+        self.area
+    }
+
+    pub fn zoom(self) -> f32 {
+        self.zoom
+    }
+
+    // Прокрутка холста на delta экранных точек
+    pub fn pan(&mut self, delta: Vec2) {
+        self.origin += delta;
+        self.clamp_scroll();
+    }
+
+    // Изменение масштаба в factor раз; точка страницы под anchor остается на месте
+    pub fn zoom_around(&mut self, factor: f32, anchor: Pos2) {
+        let zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        if zoom == self.zoom {
+            return;
+        }
+        let point = self.to_page(anchor);
+        self.zoom = zoom;
+        self.origin = anchor - point.to_vec2() * zoom;
+        self.clamp_scroll();
+    }
+
+    // Масштаб 100% с сохранением центра видимой области
+    pub fn reset_zoom(&mut self) {
+        self.zoom_around(1.0 / self.zoom, self.area.center());
+    }
+
+    // Масштаб, при котором страница целиком помещается на холсте
+    pub fn zoom_to_fit(&mut self, page_width: f32, content_height: f32) {
+        let available = self.area.size() - Vec2::splat(2.0 * PAGE_MARGIN);
+        let height = content_height + PAGE_BOTTOM_SPACE;
+        let mut zoom = available.y / height;
+        if page_width.is_finite() {
+            zoom = zoom.min(available.x / page_width.max(1.0));
+        }
+        self.zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+        self.page_size = self.fit_page_size(page_width, content_height);
+        self.center_page();
+        self.clamp_scroll();
+    }
+
+    // Смещение начала координат страницы относительно экрана
+    pub fn origin(self) -> Vec2 {
+        self.origin.to_vec2()
+    }
+
+    // Рамка страницы в координатах страницы
+    pub fn page_rect(self) -> Rect {
+        Rect::from_min_size(Pos2::ZERO, self.page_size)
+    }
+
+    pub fn to_page(self, screen: Pos2) -> Pos2 {
+        Pos2::ZERO + (screen - self.origin) / self.zoom
+    }
+
+    pub fn to_screen(self, point: Pos2) -> Pos2 {
+        self.origin + point.to_vec2() * self.zoom
+    }
+
+    pub fn rect_to_screen(self, rect: Rect) -> Rect {
+        Rect::from_min_max(self.to_screen(rect.min), self.to_screen(rect.max))
+    }
+
+    // Полосы прокрутки справа и снизу от холста. Ползунок перетаскивается мышью,
+    // щелчок по полосе прокручивает на видимую часть холста
+    pub fn show_scrollbars(&mut self, ui: &Ui) {
+        let area = self.area;
+        let content = self.content_rect().union(area);
+        let painter = ui.painter();
+
+        // (ось, полоса, начало и длина содержимого, начало и длина холста)
+        let bars = [
+            (0, Rect::from_min_max(Pos2::new(area.left(), area.bottom()), Pos2::new(area.right(), area.bottom() + SCROLLBAR_WIDTH)),
+                content.left(), content.width(), area.left(), area.width()),
+            (1, Rect::from_min_max(Pos2::new(area.right(), area.top()), Pos2::new(area.right() + SCROLLBAR_WIDTH, area.bottom())),
+                content.top(), content.height(), area.top(), area.height()),
+        ];
+
+        for (axis, track, content_start, content_size, area_start, area_size) in bars {
+            painter.rect_filled(track, 0.0, Color32::from_gray(240));
+            let length = if axis == 0 { track.width() } else { track.height() };
+            let ratio = length / content_size.max(1.0);
+            let thumb_start = (area_start - content_start) * ratio;
+            let thumb_size = (area_size * ratio).max(SCROLLBAR_WIDTH * 2.0).min(length);
+            let thumb = if axis == 0 {
+                Rect::from_min_size(Pos2::new(track.left() + thumb_start, track.top()), Vec2::new(thumb_size, track.height()))
+            } else {
+                Rect::from_min_size(Pos2::new(track.left(), track.top() + thumb_start), Vec2::new(track.width(), thumb_size))
+            };
+
+            let response = ui.interact(track, ui.id().with(("canvas_scrollbar", axis)), Sense::click_and_drag());
+            let active = response.hovered() || response.dragged();
+            let color = if active { Color32::from_gray(150) } else { Color32::from_gray(190) };
+            painter.rect_filled(thumb.shrink(2.0), 3.0, color);
+
+            // Перетаскивание ползунка на d точек прокручивает содержимое на d / ratio
+            let mut delta = 0.0;
+            if response.dragged() {
+                let drag = response.drag_delta();
+                delta = -(if axis == 0 { drag.x } else { drag.y }) / ratio;
+            } else if response.clicked()
+                && let Some(pos) = response.interact_pointer_pos() {
+                let (pointer, thumb_min, thumb_max) = if axis == 0 {
+                    (pos.x, thumb.left(), thumb.right())
+                } else {
+                    (pos.y, thumb.top(), thumb.bottom())
+                };
+                if pointer < thumb_min {
+                    delta = area_size;
+                } else if pointer > thumb_max {
+                    delta = -area_size;
+                }
+            }
+            if delta != 0.0 {
+                self.pan(if axis == 0 { Vec2::new(delta, 0.0) } else { Vec2::new(0.0, delta) });
+            }
+        }
+
+        // Уголок между полосами
+        let corner = Rect::from_min_size(area.right_bottom(), Vec2::splat(SCROLLBAR_WIDTH));
+        painter.rect_filled(corner, 0.0, Color32::from_gray(240));
+    }
+}
+
+// Нижний край элементов верхнего уровня в координатах страницы
+pub fn content_height(elements: &[Box<dyn UIElement>]) -> f32 {
+    elements.iter()
+        .map(|element| element.get_position().1 + element.get_size().1)
+        .fold(0.0, f32::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Pos2, b: Pos2) -> bool {
+        (a - b).length() < 1e-3
+    }
+
+    // Холст 800x600 со страницей шириной 1200 и высоким содержимым, чтобы было куда прокручивать
+    fn viewport() -> Viewport {
+        let mut viewport = Viewport::new();
+        viewport.update(Rect::from_min_size(Pos2::new(200.0, 50.0), Vec2::new(800.0, 600.0)), 1200.0, 3000.0);
+        viewport
+    }
+
+    #[test]
+    fn screen_and_page_points_round_trip() {
+        let points = [Pos2::ZERO, Pos2::new(10.5, 20.25), Pos2::new(1199.0, 2500.0), Pos2::new(-40.0, 7.0)];
+        for zoom in [0.25, 1.0, 1.25, 3.0] {
+            for pan in [Vec2::ZERO, Vec2::new(-150.0, -400.0), Vec2::new(30.0, 10.0)] {
+                let mut viewport = viewport();
+                viewport.zoom_around(zoom, viewport.area().center());
+                viewport.pan(pan);
+                for point in points {
+                    let screen = viewport.to_screen(point);
+                    assert!(close(viewport.to_page(screen), point), "zoom {} pan {:?} point {:?}", zoom, pan, point);
+                }
+                let screen = Pos2::new(321.0, 456.0);
+                assert!(close(viewport.to_screen(viewport.to_page(screen)), screen));
+            }
+        }
+    }
+
+    #[test]
+    fn page_origin_follows_zoom_and_pan() {
+        let mut viewport = viewport();
+        let origin = viewport.to_screen(Pos2::ZERO);
+        assert_eq!(origin, Pos2::new(220.0, 70.0));
+
+        viewport.pan(Vec2::new(0.0, -100.0));
+        assert_eq!(viewport.to_screen(Pos2::ZERO), origin - Vec2::new(0.0, 100.0));
+        assert_eq!(viewport.to_screen(Pos2::new(10.0, 10.0)) - viewport.to_screen(Pos2::ZERO), Vec2::new(10.0, 10.0));
+
+        viewport.zoom_around(2.0, viewport.to_screen(Pos2::ZERO));
+        assert_eq!(viewport.to_screen(Pos2::new(10.0, 10.0)) - viewport.to_screen(Pos2::ZERO), Vec2::new(20.0, 20.0));
+    }
+
+    #[test]
+    fn zoom_keeps_point_under_cursor() {
+        let mut viewport = viewport();
+        viewport.pan(Vec2::new(-200.0, -300.0));
+        let cursor = Pos2::new(600.0, 400.0);
+        let under_cursor = viewport.to_page(cursor);
+
+        // Страница больше холста при всех этих масштабах, поэтому ограничение прокрутки ее не сдвигает
+        for factor in [ZOOM_STEP, ZOOM_STEP, 1.0 / ZOOM_STEP, 1.0 / ZOOM_STEP] {
+            viewport.zoom_around(factor, cursor);
+            assert!(close(viewport.to_page(cursor), under_cursor), "zoom {}", viewport.zoom());
+        }
+    }
+
+    #[test]
+    fn zoom_is_clamped() {
+        let mut viewport = viewport();
+        viewport.zoom_around(100.0, viewport.area().center());
+        assert_eq!(viewport.zoom(), MAX_ZOOM);
+        viewport.zoom_around(0.0001, viewport.area().center());
+        assert_eq!(viewport.zoom(), MIN_ZOOM);
+        viewport.reset_zoom();
+        assert_eq!(viewport.zoom(), 1.0);
+    }
+}
@@ -0,0 +1,362 @@
+// Переопределения элементов для ширин экрана (breakpoints).
+// Значения ElementBase относятся к основной ширине сайта, для остальных ширин
+// хранятся только отличия. Пока в редакторе выбрана другая ширина, в ElementBase
+// подставлены ее значения, а основные временно лежат в base_layout.
+
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+
+use crate::elements::UIElement;
+use crate::elements::export::{CssRule, ExportContext};
+use crate::elements::{export_style_value, ElementBase};
+use crate::elements::layout::{declare_flow_item, is_flow, is_flow_display};
+use crate::models::site::SiteSettings;
+
+// Отличия элемента на одной ширине экрана от основной
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct BreakpointOverride {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<(f32, f32)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<(f32, f32)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub site_hidden: Option<bool>,
+    // Измененные и добавленные стили
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub styles: HashMap<String, String>,
+    // Стили основной ширины, убранные на этой ширине
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed_styles: Vec<String>,
+}
+
+impl BreakpointOverride {
+    // Отличия значений current от основных значений base. Положение элемента в потоке
+    // родителя вычисляет раскладка, поэтому у него оно отличием не считается
+    fn diff(base: &BaseLayout, current: &BaseLayout, in_flow: bool) -> Self {
+        let mut styles = HashMap::new();
+        for (key, value) in &current.styles {
+            if base.styles.get(key) != Some(value) {
+                styles.insert(key.clone(), value.clone());
+            }
+        }
+        let mut removed_styles: Vec<String> = base.styles.keys()
+            .filter(|key| !current.styles.contains_key(*key))
+            .cloned()
+            .collect();
+        removed_styles.sort();
+
+        Self {
+            position: (!in_flow && current.position != base.position).then_some(current.position),
+            size: (current.size != base.size).then_some(current.size),
+            site_hidden: (current.site_hidden != base.site_hidden).then_some(current.site_hidden),
+            styles,
+            removed_styles,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    // Накладывает отличия на основные значения элемента
+    fn apply(&self, base: &mut ElementBase) {
+        if let Some(position) = self.position {
+            base.position = position;
+        }
+        if let Some(size) = self.size {
+            base.size = size;
+        }
+        if let Some(site_hidden) = self.site_hidden {
+            base.site_hidden = site_hidden;
+        }
+        for key in &self.removed_styles {
+            base.styles.remove(key);
+        }
+        for (key, value) in &self.styles {
+            base.styles.insert(key.clone(), value.clone());
+        }
+    }
+
+    // Объявления CSS, которые отличаются от основной ширины; координаты - только вне потока
+    fn declare(&self, rule: &mut CssRule, with_position: bool, ctx: &ExportContext) {
+        let mut keys: Vec<&String> = self.styles.keys().collect();
+        keys.sort();
+        for key in keys {
+            rule.declare(key, export_style_value(&self.styles[key], ctx));
+        }
+        for key in &self.removed_styles {
+            rule.declare(key, "unset".to_string());
+        }
+        if let Some(position) = self.position.filter(|_| with_position) {
+            rule.declare("left", format!("{}px", position.0));
+            rule.declare("top", format!("{}px", position.1));
+        }
+        if let Some(size) = self.size {
+            rule.declare("width", format!("{}px", size.0));
+            rule.declare("height", format!("{}px", size.1));
+        }
+    }
+}
+
+// Основные значения элемента, которые могут отличаться на разных ширинах
+#[derive(Clone, Debug, PartialEq)]
+pub struct BaseLayout {
+    position: (f32, f32),
+    size: (f32, f32),
+    site_hidden: bool,
+    styles: HashMap<String, String>,
+}
+
+impl BaseLayout {
+    fn of(base: &ElementBase) -> Self {
+        Self {
+            position: base.position,
+            size: base.size,
+            site_hidden: base.site_hidden,
+            styles: base.styles.clone(),
+        }
+    }
+
+    pub fn styles_mut(&mut self) -> &mut HashMap<String, String> {
+        &mut self.styles
+    }
+
+    fn apply(self, base: &mut ElementBase) {
+        base.position = self.position;
+        base.size = self.size;
+        base.site_hidden = self.site_hidden;
+        base.styles = self.styles;
+    }
+}
+
+impl ElementBase {
+    // Подставляет значения ширины to вместо значений ширины from (None - основная ширина).
+    // Отличия покидаемой ширины запоминаются в overrides; in_flow - стоит ли элемент
+    // в потоке родителя на ширине from
+    pub fn switch_breakpoint(&mut self, from: Option<&str>, to: Option<&str>, in_flow: bool) {
+        if from == to {
+            return;
+        }
+
+        // Элемент без сохраненных основных значений создан на этой ширине:
+        // его значения и становятся основными
+        if let Some(from) = from
+            && let Some(base) = self.base_layout.take() {
+            let diff = BreakpointOverride::diff(&base, &BaseLayout::of(self), in_flow);
+            if diff.is_empty() {
+                self.overrides.remove(from);
+            } else {
+                self.overrides.insert(from.to_string(), diff);
+            }
+            base.apply(self);
+        }
+
+        if let Some(to) = to {
+            self.base_layout = Some(BaseLayout::of(self));
+            if let Some(values) = self.overrides.get(to).cloned() {
+                values.apply(self);
+            }
+        }
+    }
+
+    // Отличается ли элемент на выбранной ширине от основной
+    pub fn has_breakpoint_changes(&self, in_flow: bool) -> bool {
+        self.base_layout.as_ref()
+            .is_some_and(|base| !BreakpointOverride::diff(base, &BaseLayout::of(self), in_flow).is_empty())
+    }
+
+    // Возвращает элементу на выбранной ширине основные значения
+    pub fn reset_breakpoint_changes(&mut self) {
+        if let Some(base) = self.base_layout.clone() {
+            base.apply(self);
+        }
+    }
+}
+
+// Переключает ширину экрана у элемента вне потока и всех его потомков
+pub fn switch_breakpoint(element: &mut dyn UIElement, from: Option<&str>, to: Option<&str>) {
+    switch_tree(element, from, to, false);
+}
+
+pub fn switch_breakpoints(elements: &mut [Box<dyn UIElement>], from: Option<&str>, to: Option<&str>) {
+    for element in elements {
+        switch_breakpoint(element.as_mut(), from, to);
+    }
+}
+
+// Убирает у элементов и их потомков отличия ширины breakpoint_id и собирает их
+// в taken вместе с идентификаторами элементов
+pub fn take_overrides(elements: &mut [Box<dyn UIElement>], breakpoint_id: &str, taken: &mut Vec<(String, BreakpointOverride)>) {
+    for element in elements {
+        if let Some(values) = element.base_mut().overrides.remove(breakpoint_id) {
+            taken.push((element.get_id().to_string(), values));
+        }
+        if let Some(children) = element.children_mut() {
+            take_overrides(children, breakpoint_id, taken);
+        }
+    }
+}
+
+// Раскладку детей на ширине from задают стили родителя до переключения
+fn switch_tree(element: &mut dyn UIElement, from: Option<&str>, to: Option<&str>, in_flow: bool) {
+    let children_in_flow = is_flow(&element.base().styles);
+    element.base_mut().switch_breakpoint(from, to, in_flow);
+    if let Some(children) = element.children_mut() {
+        for child in children {
+            switch_tree(child.as_mut(), from, to, children_in_flow);
+        }
+    }
+}
+
+// Блоки @media с отличиями элементов на каждой ширине экрана.
+// Диапазоны ширин не пересекаются, поэтому каждый блок содержит только свои отличия
+pub fn breakpoints_css(elements: &[Box<dyn UIElement>], settings: &SiteSettings, ctx: &ExportContext) -> String {
+    let mut css = String::new();
+
+    for (breakpoint_id, query) in settings.media_queries() {
+        let mut rules = Vec::new();
+        collect_rules(elements, breakpoint_id, (false, false), ctx, &mut rules);
+        if rules.is_empty() {
+            continue;
+        }
+
+        match query {
+            Some(query) => {
+                css.push_str(&format!("@media {} {{\n", query));
+                for rule in rules {
+                    for line in rule.to_css().lines() {
+                        css.push_str(&format!("    {}\n", line));
+                    }
+                }
+                css.push_str("}\n\n");
+            },
+            // Других ширин нет: правила действуют всегда
+            None => {
+                for rule in rules {
+                    css.push_str(&rule.to_css());
+                    css.push('\n');
+                }
+            },
+        }
+    }
+
+    css
+}
+
+// Правила элементов для одной ширины (None - основная: только скрытие).
+// parent_flow - идут ли элементы в потоке родителя на основной и на этой ширине
+fn collect_rules(
+    elements: &[Box<dyn UIElement>],
+    breakpoint_id: Option<&str>,
+    parent_flow: (bool, bool),
+    ctx: &ExportContext,
+    rules: &mut Vec<CssRule>,
+) {
+    let (base_flow, flow) = parent_flow;
+    for element in elements {
+        let base = element.base();
+        let mut rule = CssRule::new(format!(".{}", ctx.class_name(&base.id)));
+
+        let values = breakpoint_id.and_then(|id| base.overrides.get(id));
+        // Родитель меняет раскладку на этой ширине: элемент входит в поток или выходит из него
+        if flow != base_flow {
+            if flow {
+                declare_flow_item(&mut rule);
+                rule.declare("left", "auto".to_string());
+                rule.declare("top", "auto".to_string());
+            } else {
+                let position = values.and_then(|v| v.position).unwrap_or(base.position);
+                rule.declare("position", "absolute".to_string());
+                rule.declare("left", format!("{}px", position.0));
+                rule.declare("top", format!("{}px", position.1));
+            }
+        }
+        if let Some(values) = values {
+            values.declare(&mut rule, !flow, ctx);
+        }
+        let site_hidden = values.and_then(|v| v.site_hidden).unwrap_or(base.site_hidden);
+        if site_hidden {
+            rule.declare("display", "none".to_string());
+        }
+
+        if !rule.declarations.is_empty() {
+            rules.push(rule);
+        }
+        if let Some(children) = element.children() {
+            let display = if values.is_some_and(|v| v.removed_styles.iter().any(|k| k == "display")) {
+                None
+            } else {
+                values.and_then(|v| v.styles.get("display")).or(base.styles.get("display"))
+            };
+            let children_flow = (is_flow(&base.styles), is_flow_display(display.map(String::as_str)));
+            collect_rules(children, breakpoint_id, children_flow, ctx, rules);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::ElementType;
+    use crate::elements::containers::container::Container;
+    use crate::elements::layout::arrange_elements;
+    use crate::elements::texts::text::Text;
+    use crate::models::site::Site;
+
+    #[test]
+    fn override_urls_point_to_exported_copies() {
+        let mut base = ElementBase::new(ElementType::Container);
+        let mut values = BreakpointOverride::default();
+        values.styles.insert("background-image".to_string(), "url(\"assets/bg.png\")".to_string());
+        base.overrides.insert("mobile".to_string(), values.clone());
+        assert_eq!(base.style_assets(), vec!["assets/bg.png".to_string()]);
+
+        let ctx = ExportContext {
+            assets: HashMap::from([("assets/bg.png".to_string(), "bg-2.png".to_string())]),
+            document: "css/index.css".to_string(),
+            ..ExportContext::default()
+        };
+        let mut rule = CssRule::new(".x".to_string());
+        values.declare(&mut rule, true, &ctx);
+        assert_eq!(rule.declarations, vec![
+            ("background-image".to_string(), "url(\"../img/bg-2.png\")".to_string()),
+        ]);
+    }
+    #[test]
+    fn switching_without_edits_leaves_no_overrides() {
+        let mut site = Site::new();
+        let mut container = Container::new();
+        container.base.styles.insert("display".to_string(), "flex".to_string());
+        container.base.styles.insert("gap".to_string(), "10px".to_string());
+        for _ in 0..3 {
+            container.children.push(Box::new(Text::new()));
+        }
+        site.current_page_mut().add_element(Box::new(container));
+
+        // Холст раскладывает элементы в потоке каждый кадр на выбранной ширине
+        for breakpoint in ["tablet", "mobile"] {
+            site.set_breakpoint(Some(breakpoint.to_string()));
+            arrange_elements(&mut site.current_page_mut().elements);
+            let container = &site.current_page().elements[0];
+            assert!(container.children().unwrap().iter().all(|child| !child.base().has_breakpoint_changes(true)));
+        }
+        site.set_breakpoint(None);
+
+        let container = &site.current_page().elements[0];
+        assert!(container.base().overrides.is_empty());
+        assert!(container.children().unwrap().iter().all(|child| child.base().overrides.is_empty()));
+    }
+
+    #[test]
+    fn position_outside_flow_is_kept_as_override() {
+        let mut base = ElementBase::new(ElementType::Text);
+        base.switch_breakpoint(None, Some("mobile"), false);
+        base.position = (5.0, 5.0);
+        assert!(base.has_breakpoint_changes(false));
+        assert!(!base.has_breakpoint_changes(true));
+        base.switch_breakpoint(Some("mobile"), None, false);
+        assert_eq!(base.overrides["mobile"].position, Some((5.0, 5.0)));
+        assert_eq!(base.position, (100.0, 100.0));
+    }
+}
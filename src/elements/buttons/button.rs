@@ -1,12 +1,33 @@
 use serde::{Serialize, Deserialize};
-use egui::{Pos2, Rect, Vec2, Color32, Stroke};
+use egui::{Color32, Stroke};
 use crate::elements::{ElementBase, ElementType, UIElement};
+use crate::elements::canvas::{paint_stroke, CanvasContext};
+use crate::elements::css::{self, Border};
+use crate::elements::export::{CssRule, ExportContext};
+use crate::elements::links::link::anchor_attributes;
+use crate::elements::links::link_target::LinkTarget;
+use crate::utils::html_generator::escape_html;
 use std::any::Any;
+
+// Размер шрифта кнопки в браузере по умолчанию
+const BUTTON_FONT_SIZE: f32 = 13.333;
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Button {
     pub base: ElementBase,
     pub content: String,
     pub onclick: Option<String>,
+    // Кнопка-ссылка экспортируется как <a>
+    #[serde(default)]
+    pub link: LinkTarget,
+    #[serde(default)]
+    pub new_tab: bool,
+}
+
+impl Default for Button {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Button {
@@ -22,39 +43,21 @@ impl Button {
             base,
             content: "Кнопка".to_string(),
             onclick: None,
+            link: LinkTarget::None,
+            new_tab: false,
         }
     }
-    
-    // Возвращает HTML-представление кнопки
-    pub fn to_html(&self) -> String {
-        let mut style = String::new();
-        for (key, value) in &self.base.styles {
-            style.push_str(&format!("{}:{};", key, value));
-        }
-        
-        let mut attributes = String::new();
-        for (key, value) in &self.base.attributes {
-            if key != "style" {
-                attributes.push_str(&format!(" {}=\"{}\"", key, value));
-            }
-        }
-        
-        // Добавляем обработчик события onclick
-        let onclick_attr = if let Some(handler) = &self.onclick {
-            format!(" onclick=\"{}\"", handler)
-        } else {
-            String::new()
-        };
-        
-        // Формируем HTML-код кнопки
-        format!(
-            "<button id=\"{}\" style=\"{}\" {}{}>{}</button>",
-            self.base.id, style, attributes, onclick_attr, self.content
-        )
-    }
 }
 
 impl UIElement for Button {
+    fn base(&self) -> &ElementBase {
+        &self.base
+    }
+    
+    fn base_mut(&mut self) -> &mut ElementBase {
+        &mut self.base
+    }
+    
     fn get_id(&self) -> &str {
         &self.base.id
     }
@@ -78,6 +81,57 @@ impl UIElement for Button {
     fn set_size(&mut self, size: (f32, f32)) {
         self.base.size = size;
     }
+    
+    fn clone_box(&self) -> Box<dyn UIElement> {
+        Box::new(self.clone())
+    }
+    
+    fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(self)
+    }
+    
+    // Возвращает HTML-представление кнопки
+    fn to_html(&self, ctx: &ExportContext) -> String {
+        // Добавляем обработчик события onclick
+        let onclick_attr = if let Some(handler) = &self.onclick {
+            format!(" onclick=\"{}\"", escape_html(handler))
+        } else {
+            String::new()
+        };
+        
+        // Кнопка со ссылкой становится ссылкой, оформленной как кнопка
+        if !self.link.is_none() {
+            return format!(
+                "<a{}{}{}>{}</a>",
+                self.base.attributes_html(ctx),
+                anchor_attributes(&self.link, self.new_tab, ctx),
+                onclick_attr,
+                escape_html(&self.content)
+            );
+        }
+        
+        // Формируем HTML-код кнопки
+        format!(
+            "<button{}{}>{}</button>",
+            self.base.attributes_html(ctx),
+            onclick_attr,
+            escape_html(&self.content)
+        )
+    }
+    
+    fn to_css(&self, ctx: &ExportContext) -> Vec<CssRule> {
+        let mut rule = self.base.css_rule(ctx);
+        if !self.link.is_none() {
+            // Выравниваем текст ссылки так же, как у <button>
+            rule.declare("display", "flex".to_string());
+            rule.declare("align-items", "center".to_string());
+            rule.declare("justify-content", "center".to_string());
+            rule.declare("text-decoration", "none".to_string());
+            rule.declare("box-sizing", "border-box".to_string());
+        }
+        vec![rule]
+    }
+    
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -90,31 +144,16 @@ impl UIElement for Button {
         self.base.contains_point(point)
     }
     
-    fn render(&self, painter: &egui::Painter, selected: bool) {
-        let element_rect = Rect::from_min_size(
-            Pos2::new(self.base.position.0, self.base.position.1),
-            Vec2::new(self.base.size.0, self.base.size.1)
-        );
+    fn render(&self, painter: &egui::Painter, canvas: &CanvasContext) {
+        let element_rect = canvas.rect(&self.base);
+        let selected = canvas.is_selected(&self.base.id);
+        
+        let styles = &self.base.styles;
+        let font_size = css::font_size(styles, BUTTON_FONT_SIZE);
         
         // Определяем цвет фона
-        let bg_color_string = self.base.styles.get("background-color")
-            .map(|s| s.clone())
-            .unwrap_or_else(|| "#4CAF50".to_string());
-            
-        let base_color = match bg_color_string.as_str() {
-            "#4CAF50" => Color32::from_rgb(76, 175, 80),
-            _ => {
-                // Простой парсер для цветов в формате HEX
-                if bg_color_string.starts_with('#') && bg_color_string.len() >= 7 {
-                    let r = u8::from_str_radix(&bg_color_string[1..3], 16).unwrap_or(76);
-                    let g = u8::from_str_radix(&bg_color_string[3..5], 16).unwrap_or(175);
-                    let b = u8::from_str_radix(&bg_color_string[5..7], 16).unwrap_or(80);
-                    Color32::from_rgb(r, g, b)
-                } else {
-                    Color32::from_rgb(76, 175, 80) // По умолчанию зеленый
-                }
-            }
-        };
+        let base_color = css::color(styles, "background-color")
+            .unwrap_or(Color32::from_rgb(76, 175, 80)); // По умолчанию зеленый
         
         // Применяем выделение, сохраняя базовый цвет
         let fill_color = if selected {
@@ -124,170 +163,43 @@ impl UIElement for Button {
             base_color
         };
         
-        // Проверяем наличие индивидуальных скруглений углов
-        let has_custom_radius = self.base.styles.contains_key("border-top-left-radius") ||
-            self.base.styles.contains_key("border-top-right-radius") ||
-            self.base.styles.contains_key("border-bottom-left-radius") ||
-            self.base.styles.contains_key("border-bottom-right-radius");
+        // Цвет текста
+        let text_color = css::color(styles, "color").unwrap_or(Color32::WHITE);
         
-        if has_custom_radius {
-            // Получаем значения для каждого угла
-            let top_left = self.base.styles.get("border-top-left-radius")
-                .map(|s| s.replace("px", "").parse::<f32>().unwrap_or(4.0))
-                .unwrap_or(4.0);
-            let top_right = self.base.styles.get("border-top-right-radius")
-                .map(|s| s.replace("px", "").parse::<f32>().unwrap_or(4.0))
-                .unwrap_or(4.0);
-            let bottom_left = self.base.styles.get("border-bottom-left-radius")
-                .map(|s| s.replace("px", "").parse::<f32>().unwrap_or(4.0))
-                .unwrap_or(4.0);
-            let bottom_right = self.base.styles.get("border-bottom-right-radius")
-                .map(|s| s.replace("px", "").parse::<f32>().unwrap_or(4.0))
-                .unwrap_or(4.0);
-            
-            // Для простоты используем среднее значение радиуса для основной фигуры
-            let avg_radius = (top_left + top_right + bottom_left + bottom_right) / 4.0;
-            
-            // Рисуем основной прямоугольник
-            painter.rect_filled(element_rect, avg_radius, fill_color);
-            
-            // Определяем параметры обводки
-            let border_width = self.base.styles.get("border-width")
-                .map(|s| s.replace("px", "").parse::<f32>().unwrap_or(1.0))
-                .unwrap_or(1.0);
-            
-            let border_color_string = self.base.styles.get("border-color")
-                .map(|s| s.clone())
-                .unwrap_or_else(|| "#000000".to_string());
-            
-            let border_color = if border_color_string.starts_with('#') && border_color_string.len() >= 7 {
-                let r = u8::from_str_radix(&border_color_string[1..3], 16).unwrap_or(0);
-                let g = u8::from_str_radix(&border_color_string[3..5], 16).unwrap_or(0);
-                let b = u8::from_str_radix(&border_color_string[5..7], 16).unwrap_or(0);
-                Color32::from_rgb(r, g, b)
-            } else {
-                Color32::BLACK
-            };
-            
-            // Рисуем рамку с усредненным радиусом
-            if border_width > 0.0 {
-                let stroke = if selected {
-                    // При выделении добавляем дополнительную рамку
-                    Stroke::new(border_width + 1.0, border_color) 
-                } else {
-                    Stroke::new(border_width, border_color)
-                };
-                
-                painter.rect_stroke(element_rect, avg_radius, stroke);
-            } else if selected {
-                // Если обводка выключена, но кнопка выделена, рисуем рамку выделения
-                painter.rect_stroke(element_rect, avg_radius, Stroke::new(1.0, Color32::BLUE));
-            }
-            
-            // Дополнительно рисуем маленькие дуги в углах для наглядного отображения скруглений
-            if selected {
-                // Рисуем дуги в каждом углу для визуализации фактического скругления
-                // Верхний левый угол
-                if top_left > 0.0 {
-                    painter.circle_stroke(
-                        Pos2::new(element_rect.min.x + top_left, element_rect.min.y + top_left),
-                        top_left,
-                        Stroke::new(1.0, Color32::LIGHT_BLUE),
-                    );
-                }
-                
-                // Верхний правый угол
-                if top_right > 0.0 {
-                    painter.circle_stroke(
-                        Pos2::new(element_rect.max.x - top_right, element_rect.min.y + top_right),
-                        top_right,
-                        Stroke::new(1.0, Color32::LIGHT_BLUE),
-                    );
-                }
-                
-                // Нижний левый угол
-                if bottom_left > 0.0 {
-                    painter.circle_stroke(
-                        Pos2::new(element_rect.min.x + bottom_left, element_rect.max.y - bottom_left),
-                        bottom_left,
-                        Stroke::new(1.0, Color32::LIGHT_BLUE),
-                    );
-                }
-                
-                // Нижний правый угол
-                if bottom_right > 0.0 {
-                    painter.circle_stroke(
-                        Pos2::new(element_rect.max.x - bottom_right, element_rect.max.y - bottom_right),
-                        bottom_right,
-                        Stroke::new(1.0, Color32::LIGHT_BLUE),
-                    );
-                }
-            }
+        // Рамка: у <button> браузер рисует свою рамку, у ссылки-кнопки ее нет
+        let initial_border = if self.link.is_none() {
+            Border::uniform(2.0, Color32::from_gray(118))
         } else {
-            // Получаем значение скругления углов
-            let border_radius = self.base.styles.get("border-radius")
-                .map(|s| s.replace("px", "").parse::<f32>().unwrap_or(4.0))
-                .unwrap_or(4.0);
-            
-            // Рисуем фон кнопки
-            painter.rect_filled(element_rect, border_radius, fill_color);
-            
-            // Определяем параметры обводки
-            let border_width = self.base.styles.get("border-width")
-                .map(|s| s.replace("px", "").parse::<f32>().unwrap_or(1.0))
-                .unwrap_or(1.0);
-            
-            let border_color_string = self.base.styles.get("border-color")
-                .map(|s| s.clone())
-                .unwrap_or_else(|| "#000000".to_string());
-            
-            let border_color = if border_color_string.starts_with('#') && border_color_string.len() >= 7 {
-                let r = u8::from_str_radix(&border_color_string[1..3], 16).unwrap_or(0);
-                let g = u8::from_str_radix(&border_color_string[3..5], 16).unwrap_or(0);
-                let b = u8::from_str_radix(&border_color_string[5..7], 16).unwrap_or(0);
-                Color32::from_rgb(r, g, b)
+            Border::none()
+        };
+        let border = Border::from_styles(styles, font_size, initial_border);
+        
+        // Скругление каждого угла отдельно, как в браузере
+        let rounding = canvas.scale_rounding(css::rounding(styles, font_size, self.base.size));
+        
+        // Рисуем фон кнопки
+        painter.rect_filled(element_rect, rounding, fill_color);
+        
+        // Рисуем рамку кнопки, если она видима
+        if let Some(stroke) = border.stroke(text_color) {
+            let stroke = if selected {
+                // При выделении добавляем дополнительную рамку
+                Stroke::new(stroke.width * canvas.scale + 1.0, stroke.color)
             } else {
-                Color32::BLACK
+                Stroke::new(stroke.width * canvas.scale, stroke.color)
             };
-            
-            // Рисуем рамку кнопки, если ширина обводки больше 0
-            if border_width > 0.0 {
-                let stroke = if selected {
-                    // При выделении добавляем дополнительную рамку
-                    Stroke::new(border_width + 1.0, border_color) 
-                } else {
-                    Stroke::new(border_width, border_color)
-                };
-                
-                painter.rect_stroke(element_rect, border_radius, stroke);
-            } else if selected {
-                // Если обводка выключена, но кнопка выделена, рисуем рамку выделения
-                painter.rect_stroke(element_rect, border_radius, Stroke::new(1.0, Color32::BLUE));
-            }
+            paint_stroke(painter, element_rect, rounding, stroke);
+        } else if selected {
+            // Если обводка выключена, но кнопка выделена, рисуем рамку выделения
+            painter.rect_stroke(element_rect, rounding, Stroke::new(1.0, Color32::BLUE));
         }
         
-        // Определяем цвет текста
-        let text_color_string = self.base.styles.get("color")
-            .map(|s| s.clone())
-            .unwrap_or_else(|| "#FFFFFF".to_string());
-        
-        let text_color = if text_color_string.starts_with('#') && text_color_string.len() >= 7 {
-            let r = u8::from_str_radix(&text_color_string[1..3], 16).unwrap_or(255);
-            let g = u8::from_str_radix(&text_color_string[3..5], 16).unwrap_or(255);
-            let b = u8::from_str_radix(&text_color_string[5..7], 16).unwrap_or(255);
-            Color32::from_rgb(r, g, b)
-        } else if text_color_string == "#FFFFFF" {
-            Color32::WHITE
-        } else {
-            Color32::BLACK
-        };
-        
         // Рисуем текст кнопки
         painter.text(
             element_rect.center(),
             egui::Align2::CENTER_CENTER,
             &self.content,
-            egui::FontId::default(),
+            egui::FontId::proportional(font_size * canvas.scale),
             text_color
         );
     }
@@ -1,2 +1,3 @@
 pub mod button;
+#[allow(non_snake_case)]
 pub mod buttonProperty;
\ No newline at end of file
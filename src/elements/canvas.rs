@@ -0,0 +1,116 @@
+use egui::{Color32, Pos2, Rect, Rounding, Stroke, Vec2};
+use std::path::{Path, PathBuf};
+
+use crate::app::project;
+use crate::elements::{ElementBase, UIElement};
+use crate::elements::css::{self, Border};
+
+// Параметры отрисовки элементов на холсте редактора
+#[derive(Clone, Copy, Debug)]
+pub struct CanvasContext<'a> {
+    // Экранная точка, от которой отсчитываются координаты элементов (начало координат родителя)
+    pub origin: Vec2,
+    // Масштаб холста: сколько экранных точек в одном пикселе страницы.
+    // Все размеры из стилей (шрифты, рамки, скругления, отступы) умножаются на него
+    pub scale: f32,
+    // Выделенные в редакторе элементы
+    pub selected_ids: &'a [String],
+    // Папка проекта, от которой отсчитываются пути к файлам ресурсов (None - проект не сохранен)
+    pub project_dir: Option<&'a Path>,
+}
+
+impl<'a> CanvasContext<'a> {
+    pub fn new(origin: Vec2, scale: f32, selected_ids: &'a [String]) -> Self {
+        Self { origin, scale, selected_ids, project_dir: None }
+    }
+
+    pub fn with_project_dir(self, project_dir: Option<&'a Path>) -> Self {
+        Self { project_dir, ..self }
+    }
+
+    // Путь к файлу ресурса на диске
+    pub fn asset_path(&self, path: &str) -> PathBuf {
+        project::resolve_asset(self.project_dir, path)
+    }
+
+    // Экранный прямоугольник элемента
+    pub fn rect(&self, base: &ElementBase) -> Rect {
+        Rect::from_min_size(
+            Pos2::ZERO + Vec2::new(base.position.0, base.position.1) * self.scale + self.origin,
+            Vec2::new(base.size.0, base.size.1) * self.scale
+        )
+    }
+
+    // Выделен ли элемент
+    pub fn is_selected(&self, element_id: &str) -> bool {
+        self.selected_ids.iter().any(|id| id == element_id)
+    }
+
+    // Рисует список элементов по порядку (последний - сверху), пропуская скрытые
+    // в редакторе и на сайте при выбранной ширине
+    pub fn render_elements(&self, painter: &egui::Painter, elements: &[Box<dyn UIElement>]) {
+        for element in elements.iter().filter(|e| !e.base().hidden && !e.base().site_hidden) {
+            element.render(painter, self);
+        }
+    }
+
+    // Контекст для дочерних элементов: координаты отсчитываются от позиции родителя
+    pub fn child(&self, parent_position: (f32, f32)) -> Self {
+        Self {
+            origin: self.origin + Vec2::new(parent_position.0, parent_position.1) * self.scale,
+            ..*self
+        }
+    }
+
+    // Скругление углов элемента по стилю border-radius в экранных точках
+    pub fn rounding(&self, base: &ElementBase) -> Rounding {
+        let font_size = css::font_size(&base.styles, css::ROOT_FONT_SIZE);
+        self.scale_rounding(css::rounding(&base.styles, font_size, base.size))
+    }
+
+    // Переводит скругление из пикселей страницы в экранные точки
+    pub fn scale_rounding(&self, rounding: Rounding) -> Rounding {
+        Rounding {
+            nw: rounding.nw * self.scale,
+            ne: rounding.ne * self.scale,
+            sw: rounding.sw * self.scale,
+            se: rounding.se * self.scale,
+        }
+    }
+
+    // Фон элемента по стилю background-color; default - фон элемента в браузере по умолчанию
+    pub fn paint_background(&self, painter: &egui::Painter, base: &ElementBase, default: Color32) {
+        let color = css::color(&base.styles, "background-color").unwrap_or(default);
+        if color != Color32::TRANSPARENT {
+            painter.rect_filled(self.rect(base), self.rounding(base), color);
+        }
+    }
+
+    // Рамка элемента по стилям; initial - рамка элемента в браузере по умолчанию.
+    // Как в браузере, рамка лежит внутри границ элемента и повторяет скругление углов
+    pub fn paint_border(&self, painter: &egui::Painter, base: &ElementBase, initial: Border) {
+        let font_size = css::font_size(&base.styles, css::ROOT_FONT_SIZE);
+        let border = Border::from_styles(&base.styles, font_size, initial);
+        let text_color = css::color(&base.styles, "color").unwrap_or(Color32::BLACK);
+        if let Some(stroke) = border.stroke(text_color) {
+            let stroke = Stroke::new(stroke.width * self.scale, stroke.color);
+            paint_stroke(painter, self.rect(base), self.rounding(base), stroke);
+        }
+    }
+}
+
+// Скругление контура, отступающего внутрь на inset: радиусы углов уменьшаются на столько же
+pub fn inset_rounding(rounding: Rounding, inset: f32) -> Rounding {
+    Rounding {
+        nw: (rounding.nw - inset).max(0.0),
+        ne: (rounding.ne - inset).max(0.0),
+        sw: (rounding.sw - inset).max(0.0),
+        se: (rounding.se - inset).max(0.0),
+    }
+}
+
+// Линия по внутреннему краю прямоугольника со скругленными углами
+pub fn paint_stroke(painter: &egui::Painter, rect: Rect, rounding: Rounding, stroke: Stroke) {
+    let inset = stroke.width / 2.0;
+    painter.rect_stroke(rect.shrink(inset), inset_rounding(rounding, inset), stroke);
+}
@@ -0,0 +1,567 @@
+// Общая палитра выбора цвета для редакторов свойств: цвет с прозрачностью, ввод в HEX,
+// RGB и HSL, пипетка для цвета элемента на холсте, недавние цвета и цвета сайта.
+// Недавние цвета, цвета сайта и пипетка общие для всех палитр и хранятся в памяти egui
+
+use egui::color_picker::show_color_at;
+use egui::epaint::Mesh;
+use egui::{Color32, Context, Id, Painter, Rect, Response, Sense, Stroke, Ui, Vec2};
+use uuid::Uuid;
+
+use crate::elements::css;
+
+// Сколько недавних цветов запоминается
+const MAX_RECENT: usize = 12;
+
+// Размер образца цвета
+const SWATCH_SIZE: f32 = 18.0;
+
+// Размер поля насыщенности и яркости
+const FIELD_SIZE: f32 = 200.0;
+
+// Толщина полос оттенка и прозрачности
+const BAR_SIZE: f32 = 16.0;
+
+// Число делений градиентов по каждой оси
+const GRADIENT_STEPS: usize = 24;
+
+// Общие для всех палитр цвета
+#[derive(Clone, Default)]
+struct SharedColors {
+    // Недавно выбранные цвета, последний - первым
+    recent: Vec<Color32>,
+    // Цвета сайта из его настроек
+    palette: Vec<Color32>,
+    // Палитра, которая ждет цвет с холста
+    eyedropper: Option<Id>,
+    // Цвет, взятый пипеткой, и палитра, которой он предназначен
+    picked: Option<(Id, Color32)>,
+}
+
+fn with_shared<R>(ctx: &Context, f: impl FnOnce(&mut SharedColors) -> R) -> R {
+    ctx.data_mut(|data| f(data.get_temp_mut_or_default::<SharedColors>(Id::new("shared_colors"))))
+}
+
+// Цвета сайта для образцов; редактор обновляет их каждый кадр
+pub fn set_site_palette(ctx: &Context, colors: &[String]) {
+    let palette = colors.iter().filter_map(|color| css::parse_color(color)).collect();
+    with_shared(ctx, |shared| shared.palette = palette);
+}
+
+// Ждет ли какая-нибудь палитра цвет с холста
+pub fn eyedropper_active(ctx: &Context) -> bool {
+    with_shared(ctx, |shared| shared.eyedropper.is_some())
+}
+
+// Завершает выбор пипеткой: Some - цвет под курсором, None - отмена.
+// Палитра получит цвет в следующем кадре
+pub fn finish_eyedropper(ctx: &Context, color: Option<Color32>) {
+    with_shared(ctx, |shared| {
+        if let (Some(id), Some(color)) = (shared.eyedropper.take(), color) {
+            shared.picked = Some((id, color));
+        }
+    });
+    ctx.request_repaint();
+}
+
+// Запоминает цвет в недавних
+fn remember(ctx: &Context, color: Color32) {
+    with_shared(ctx, |shared| {
+        shared.recent.retain(|recent| *recent != color);
+        shared.recent.insert(0, color);
+        shared.recent.truncate(MAX_RECENT);
+    });
+}
+
+// Цветовая модель числовых полей
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ColorModel {
+    Rgb,
+    Hsl,
+}
+
+pub struct ColorPicker {
+    id: Id,
+    is_open: bool,
+    // Оттенок, насыщенность и яркость от 0 до 1: оттенок не теряется у серых цветов
+    hsv: [f32; 3],
+    alpha: u8,
+    // Текст поля ввода цвета
+    text: String,
+    // Значение из стилей, по которому выставлен цвет
+    source: Option<String>,
+    model: ColorModel,
+    // Цвет изменен, но еще не попал в недавние: ждет отпускания мыши
+    unsaved: bool,
+}
+
+impl Default for ColorPicker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ColorPicker {
+    pub fn new() -> Self {
+        Self {
+            id: Id::new(Uuid::new_v4()),
+            is_open: false,
+            hsv: [0.0, 0.0, 0.0],
+            alpha: 255,
+            text: String::new(),
+            source: None,
+            model: ColorModel::Rgb,
+            unsaved: false,
+        }
+    }
+
+    // Выбранный цвет
+    pub fn color(&self) -> Color32 {
+        let [r, g, b] = hsv_to_rgb(self.hsv);
+        Color32::from_rgba_unmultiplied(r, g, b, self.alpha)
+    }
+
+    // Выставляет цвет, не трогая поле ввода. У серых цветов оттенок,
+    // а у черного и насыщенность остаются прежними
+    fn apply_color(&mut self, color: Color32) {
+        let [r, g, b, a] = color.to_srgba_unmultiplied();
+        let [hue, saturation, value] = rgb_to_hsv([r, g, b]);
+        if value > 0.0 {
+            if saturation > 0.0 {
+                self.hsv[0] = hue;
+            }
+            self.hsv[1] = saturation;
+        }
+        self.hsv[2] = value;
+        self.alpha = a;
+    }
+
+    fn set_color(&mut self, color: Color32) {
+        self.apply_color(color);
+        self.text = css::format_color(self.color());
+    }
+
+    // Палитра для значения из стилей (None - цвет не задан): образец, поле ввода и пипетка,
+    // по щелчку на образце - окно выбора. Возвращает новый цвет в записи CSS, если он изменен
+    pub fn show(&mut self, ui: &mut Ui, current: Option<&str>) -> Option<String> {
+        // Значение изменено извне (выбран другой элемент, отмена) - показываем его
+        if current != self.source.as_deref() {
+            self.source = current.map(str::to_string);
+            match current.and_then(css::parse_color) {
+                Some(color) => self.set_color(color),
+                None => self.text = current.unwrap_or_default().to_string(),
+            }
+        }
+        let mut changed = false;
+
+        let picked = with_shared(ui.ctx(), |shared| match shared.picked {
+            Some((id, color)) if id == self.id => shared.picked.take().map(|_| color),
+            _ => None,
+        });
+        if let Some(color) = picked {
+            self.set_color(color);
+            self.unsaved = true;
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            let color = if current.is_some() { self.color() } else { Color32::TRANSPARENT };
+            if swatch(ui, color).on_hover_text("Выбор цвета").clicked() {
+                self.is_open = !self.is_open;
+            }
+            changed |= self.show_text_input(ui);
+            self.show_eyedropper_button(ui);
+        });
+
+        if self.is_open {
+            changed |= self.show_window(ui.ctx());
+        }
+
+        // В недавние попадает итоговый цвет, а не каждый шаг перетаскивания
+        if self.unsaved && !ui.input(|i| i.pointer.any_down()) {
+            self.unsaved = false;
+            remember(ui.ctx(), self.color());
+        }
+
+        changed.then(|| {
+            let value = css::format_color(self.color());
+            self.source = Some(value.clone());
+            value
+        })
+    }
+
+    // Поле ввода принимает любую запись цвета CSS: #RRGGBBAA, rgba(), hsl(), имена цветов
+    fn show_text_input(&mut self, ui: &mut Ui) -> bool {
+        let response = ui.add(egui::TextEdit::singleline(&mut self.text).hint_text("не задан").desired_width(110.0));
+        let parsed = css::parse_color(&self.text);
+        let changed = response.changed() && parsed.is_some_and(|color| color != self.color());
+        if let Some(color) = parsed.filter(|_| changed) {
+            self.apply_color(color);
+        }
+
+        // После ввода запись приводится к HEX, а неверная - к выбранному цвету
+        if response.lost_focus() && self.source.is_some() {
+            if parsed.is_some() {
+                self.unsaved = true;
+            }
+            self.text = css::format_color(self.color());
+        }
+        changed
+    }
+
+    fn show_eyedropper_button(&self, ui: &mut Ui) {
+        let active = with_shared(ui.ctx(), |shared| shared.eyedropper == Some(self.id));
+        let response = ui.selectable_label(active, "💧")
+            .on_hover_text("Пипетка: щелкните по элементу на холсте, Esc - отмена");
+        if response.clicked() {
+            with_shared(ui.ctx(), |shared| shared.eyedropper = (!active).then_some(self.id));
+        }
+    }
+
+    // Окно выбора цвета
+    fn show_window(&mut self, ctx: &Context) -> bool {
+        let mut open = true;
+        let mut done = false;
+        let mut changed = false;
+
+        egui::Window::new("Выбор цвета")
+            .id(self.id.with("window"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    changed |= self.show_saturation_value(ui);
+                    changed |= self.show_hue_bar(ui);
+                });
+                changed |= self.show_alpha_bar(ui);
+                changed |= self.show_numeric_inputs(ui);
+
+                ui.separator();
+                changed |= self.show_swatches(ui);
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    changed |= self.show_text_input(ui);
+                    self.show_eyedropper_button(ui);
+                    if ui.button("Готово").clicked() {
+                        done = true;
+                    }
+                });
+            });
+
+        self.is_open = open && !done;
+        changed
+    }
+
+    // Поле насыщенности (по горизонтали) и яркости (по вертикали) для текущего оттенка
+    fn show_saturation_value(&mut self, ui: &mut Ui) -> bool {
+        let (rect, response) = ui.allocate_exact_size(Vec2::splat(FIELD_SIZE), Sense::click_and_drag());
+        let [hue, saturation, value] = self.hsv;
+        paint_gradient(ui.painter(), rect, |x, y| hsv_color([hue, x, 1.0 - y]));
+
+        let marker = rect.lerp_inside(Vec2::new(saturation, 1.0 - value));
+        ui.painter().circle_stroke(marker, 5.0, Stroke::new(2.0, Color32::BLACK));
+        ui.painter().circle_stroke(marker, 5.0, Stroke::new(1.0, Color32::WHITE));
+
+        match pointer_fraction(&response, rect) {
+            Some(t) => {
+                self.hsv[1] = t.x;
+                self.hsv[2] = 1.0 - t.y;
+                self.edited()
+            },
+            None => false,
+        }
+    }
+
+    // Вертикальная полоса оттенка
+    fn show_hue_bar(&mut self, ui: &mut Ui) -> bool {
+        let (rect, response) = ui.allocate_exact_size(Vec2::new(BAR_SIZE, FIELD_SIZE), Sense::click_and_drag());
+        paint_gradient(ui.painter(), rect, |_, y| hsv_color([y, 1.0, 1.0]));
+
+        let y = rect.top() + self.hsv[0] * rect.height();
+        let marker = Rect::from_x_y_ranges(rect.x_range(), y - 2.0..=y + 2.0);
+        ui.painter().rect_stroke(marker, 0.0, Stroke::new(1.0, Color32::WHITE));
+
+        match pointer_fraction(&response, rect) {
+            Some(t) => {
+                self.hsv[0] = t.y;
+                self.edited()
+            },
+            None => false,
+        }
+    }
+
+    // Горизонтальная полоса прозрачности поверх шахматного фона
+    fn show_alpha_bar(&mut self, ui: &mut Ui) -> bool {
+        let (rect, response) = ui.allocate_exact_size(Vec2::new(FIELD_SIZE + BAR_SIZE, BAR_SIZE), Sense::click_and_drag());
+        show_color_at(ui.painter(), Color32::TRANSPARENT, rect);
+        let [r, g, b] = hsv_to_rgb(self.hsv);
+        paint_gradient(ui.painter(), rect, |x, _| Color32::from_rgba_unmultiplied(r, g, b, (x * 255.0).round() as u8));
+
+        let x = rect.left() + self.alpha as f32 / 255.0 * rect.width();
+        let marker = Rect::from_x_y_ranges(x - 2.0..=x + 2.0, rect.y_range());
+        ui.painter().rect_stroke(marker, 0.0, Stroke::new(1.0, Color32::BLACK));
+
+        match pointer_fraction(&response, rect) {
+            Some(t) => {
+                self.alpha = (t.x * 255.0).round() as u8;
+                self.edited()
+            },
+            None => false,
+        }
+    }
+
+    // Числовые поля RGB или HSL и непрозрачность в процентах
+    fn show_numeric_inputs(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.model, ColorModel::Rgb, "RGB");
+            ui.selectable_value(&mut self.model, ColorModel::Hsl, "HSL");
+        });
+
+        ui.horizontal(|ui| match self.model {
+            ColorModel::Rgb => {
+                let mut rgb = hsv_to_rgb(self.hsv);
+                for (channel, label) in rgb.iter_mut().zip(["R", "G", "B"]) {
+                    ui.label(label);
+                    changed |= ui.add(egui::DragValue::new(channel).clamp_range(0..=255)).changed();
+                }
+                if changed {
+                    self.apply_color(Color32::from_rgba_unmultiplied(rgb[0], rgb[1], rgb[2], self.alpha));
+                }
+            },
+            ColorModel::Hsl => {
+                // Значения считаются от HSV без округления до целых каналов, чтобы перетаскивание было плавным
+                let [hue, saturation, lightness] = hsv_to_hsl(self.hsv);
+                let mut hsl = [hue * 360.0, saturation * 100.0, lightness * 100.0];
+                for ((value, label), (max, suffix)) in hsl.iter_mut().zip(["H", "S", "L"]).zip([(360.0, "°"), (100.0, "%"), (100.0, "%")]) {
+                    ui.label(label);
+                    changed |= ui.add(egui::DragValue::new(value).clamp_range(0.0..=max).max_decimals(0).suffix(suffix)).changed();
+                }
+                if changed {
+                    self.hsv = hsl_to_hsv([hsl[0] / 360.0 % 1.0, hsl[1] / 100.0, hsl[2] / 100.0]);
+                }
+            },
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Непрозрачность");
+            let mut opacity = self.alpha as f32 / 255.0 * 100.0;
+            if ui.add(egui::DragValue::new(&mut opacity).clamp_range(0.0..=100.0).max_decimals(0).suffix("%")).changed() {
+                self.alpha = (opacity / 100.0 * 255.0).round() as u8;
+                changed = true;
+            }
+        });
+
+        changed && self.edited()
+    }
+
+    // Образцы цветов сайта и недавних цветов
+    fn show_swatches(&mut self, ui: &mut Ui) -> bool {
+        let (palette, recent) = with_shared(ui.ctx(), |shared| (shared.palette.clone(), shared.recent.clone()));
+        let mut picked = None;
+
+        for (title, colors, empty) in [
+            ("Цвета сайта", palette, "задаются в настройках сайта"),
+            ("Недавние", recent, "пока нет"),
+        ] {
+            ui.label(title);
+            if colors.is_empty() {
+                ui.weak(empty);
+                continue;
+            }
+            ui.horizontal_wrapped(|ui| {
+                for color in colors {
+                    if swatch(ui, color).on_hover_text(css::format_color(color)).clicked() {
+                        picked = Some(color);
+                    }
+                }
+            });
+        }
+
+        match picked {
+            Some(color) => {
+                self.set_color(color);
+                self.unsaved = true;
+                true
+            },
+            None => false,
+        }
+    }
+
+    // Цвет изменен в окне: поле ввода показывает его, а в недавние он попадет по отпусканию мыши
+    fn edited(&mut self) -> bool {
+        self.text = css::format_color(self.color());
+        self.unsaved = true;
+        true
+    }
+}
+
+// Образец цвета, по которому можно щелкнуть; прозрачный показывается шахматным фоном
+fn swatch(ui: &mut Ui, color: Color32) -> Response {
+    let (rect, response) = ui.allocate_exact_size(Vec2::splat(SWATCH_SIZE), Sense::click());
+    show_color_at(ui.painter(), color, rect);
+    let stroke = if response.hovered() {
+        Stroke::new(2.0, Color32::from_rgb(33, 150, 243))
+    } else {
+        Stroke::new(1.0, Color32::from_gray(160))
+    };
+    ui.painter().rect_stroke(rect, 0.0, stroke);
+    response
+}
+
+// Положение курсора внутри rect в долях от 0 до 1, пока по нему щелкают или тянут
+fn pointer_fraction(response: &Response, rect: Rect) -> Option<Vec2> {
+    if !(response.clicked() || response.dragged()) {
+        return None;
+    }
+    let pos = response.interact_pointer_pos()?;
+    let t = (pos - rect.min) / rect.size();
+    Some(Vec2::new(t.x.clamp(0.0, 1.0), t.y.clamp(0.0, 1.0)))
+}
+
+// Заливает rect градиентом; color_at получает доли от 0 до 1 по горизонтали и вертикали
+fn paint_gradient(painter: &Painter, rect: Rect, color_at: impl Fn(f32, f32) -> Color32) {
+    let mut mesh = Mesh::default();
+    for row in 0..=GRADIENT_STEPS {
+        for column in 0..=GRADIENT_STEPS {
+            let t = Vec2::new(column as f32, row as f32) / GRADIENT_STEPS as f32;
+            mesh.colored_vertex(rect.lerp_inside(t), color_at(t.x, t.y));
+        }
+    }
+    let index = |row: usize, column: usize| (row * (GRADIENT_STEPS + 1) + column) as u32;
+    for row in 0..GRADIENT_STEPS {
+        for column in 0..GRADIENT_STEPS {
+            mesh.add_triangle(index(row, column), index(row, column + 1), index(row + 1, column));
+            mesh.add_triangle(index(row, column + 1), index(row + 1, column + 1), index(row + 1, column));
+        }
+    }
+    painter.add(mesh);
+}
+
+fn hsv_color(hsv: [f32; 3]) -> Color32 {
+    let [r, g, b] = hsv_to_rgb(hsv);
+    Color32::from_rgb(r, g, b)
+}
+
+// Перевод HSV (все составляющие от 0 до 1) в RGB
+fn hsv_to_rgb([hue, saturation, value]: [f32; 3]) -> [u8; 3] {
+    let h = hue.rem_euclid(1.0) * 6.0;
+    let f = h - h.floor();
+    let p = value * (1.0 - saturation);
+    let q = value * (1.0 - f * saturation);
+    let t = value * (1.0 - (1.0 - f) * saturation);
+    let (r, g, b) = match h.floor() as u8 {
+        0 => (value, t, p),
+        1 => (q, value, p),
+        2 => (p, value, t),
+        3 => (p, q, value),
+        4 => (t, p, value),
+        _ => (value, p, q),
+    };
+    [r, g, b].map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+fn rgb_to_hsv(rgb: [u8; 3]) -> [f32; 3] {
+    let [r, g, b] = rgb.map(|c| c as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let delta = max - r.min(g).min(b);
+
+    let hue = if delta <= f32::EPSILON {
+        0.0
+    } else if max == r {
+        ((g - b) / delta).rem_euclid(6.0) / 6.0
+    } else if max == g {
+        ((b - r) / delta + 2.0) / 6.0
+    } else {
+        ((r - g) / delta + 4.0) / 6.0
+    };
+    let saturation = if max <= f32::EPSILON { 0.0 } else { delta / max };
+    [hue, saturation, max]
+}
+
+// Оттенок в HSV и HSL один и тот же, пересчитываются насыщенность и яркость/светлота
+fn hsv_to_hsl([hue, saturation, value]: [f32; 3]) -> [f32; 3] {
+    let lightness = value * (1.0 - saturation / 2.0);
+    let limit = lightness.min(1.0 - lightness);
+    let saturation = if limit <= f32::EPSILON { 0.0 } else { (value - lightness) / limit };
+    [hue, saturation, lightness]
+}
+
+fn hsl_to_hsv([hue, saturation, lightness]: [f32; 3]) -> [f32; 3] {
+    let value = lightness + saturation * lightness.min(1.0 - lightness);
+    let saturation = if value <= f32::EPSILON { 0.0 } else { 2.0 * (1.0 - lightness / value) };
+    [hue, saturation, value]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIMARIES: [([u8; 3], f32); 6] = [
+        ([255, 0, 0], 0.0),
+        ([255, 255, 0], 1.0 / 6.0),
+        ([0, 255, 0], 2.0 / 6.0),
+        ([0, 255, 255], 3.0 / 6.0),
+        ([0, 0, 255], 4.0 / 6.0),
+        ([255, 0, 255], 5.0 / 6.0),
+    ];
+
+    fn close(a: [f32; 3], b: [f32; 3]) -> bool {
+        a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-4)
+    }
+
+    #[test]
+    fn primary_colors_round_trip_through_hsv() {
+        for (rgb, hue) in PRIMARIES {
+            let hsv = rgb_to_hsv(rgb);
+            assert!(close(hsv, [hue, 1.0, 1.0]), "{:?} -> {:?}", rgb, hsv);
+            assert_eq!(hsv_to_rgb(hsv), rgb);
+        }
+        // Оттенок 1 - тот же красный, что и 0
+        assert_eq!(hsv_to_rgb([1.0, 1.0, 1.0]), [255, 0, 0]);
+    }
+
+    #[test]
+    fn grays_have_no_saturation() {
+        for level in [0, 1, 51, 128, 254, 255] {
+            let hsv = rgb_to_hsv([level; 3]);
+            assert_eq!(hsv[1], 0.0);
+            assert!((hsv[2] - level as f32 / 255.0).abs() < 1e-6);
+            assert_eq!(hsv_to_rgb(hsv), [level; 3]);
+            // Оттенок серого не важен
+            assert_eq!(hsv_to_rgb([0.7, 0.0, hsv[2]]), [level; 3]);
+        }
+    }
+
+    #[test]
+    fn hsl_round_trips_through_hsv() {
+        assert!(close(hsv_to_hsl([0.0, 1.0, 1.0]), [0.0, 1.0, 0.5]));
+        assert!(close(hsv_to_hsl([0.5, 0.0, 1.0]), [0.5, 0.0, 1.0]));
+        assert!(close(hsv_to_hsl([0.0, 0.0, 0.0]), [0.0, 0.0, 0.0]));
+        assert!(close(hsl_to_hsv([0.0, 1.0, 0.25]), [0.0, 1.0, 0.5]));
+
+        for hsv in [[0.1, 0.5, 0.8], [0.6, 1.0, 0.4], [0.9, 0.2, 0.3], [0.3, 0.0, 0.5]] {
+            assert!(close(hsl_to_hsv(hsv_to_hsl(hsv)), hsv), "{:?}", hsv);
+        }
+    }
+
+    #[test]
+    fn picker_keeps_alpha_and_gray_hue() {
+        let mut picker = ColorPicker::new();
+        let color = css::parse_color("rgba(30, 136, 229, 0.5)").unwrap();
+        picker.set_color(color);
+        assert_eq!(picker.alpha, 128);
+        assert_eq!(picker.color(), color);
+        assert_eq!(picker.text, css::format_color(color));
+        assert_eq!(css::parse_color(&picker.text), Some(color));
+
+        let hue = picker.hsv[0];
+        picker.set_color(Color32::from_gray(128));
+        assert_eq!(picker.hsv[0], hue);
+        assert_eq!(picker.alpha, 255);
+
+        picker.set_color(css::parse_color("#FF000000").unwrap());
+        assert_eq!(picker.alpha, 0);
+    }
+}
@@ -0,0 +1,160 @@
+use serde::{Serialize, Deserialize};
+use egui::{Color32, Stroke};
+use crate::elements::{children_css, ElementBase, ElementType, UIElement};
+use crate::elements::canvas::CanvasContext;
+use crate::elements::css::Border;
+use crate::elements::layout::is_flow;
+use crate::elements::export::{CssRule, ExportContext};
+use crate::elements::properties::{self, PropertySection};
+use std::any::Any;
+
+// Контейнер: группирует дочерние элементы, координаты которых отсчитываются от него
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Container {
+    pub base: ElementBase,
+    #[serde(with = "crate::elements::serde_elements")]
+    pub children: Vec<Box<dyn UIElement>>,
+}
+
+impl Default for Container {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Container {
+    pub fn new() -> Self {
+        let mut base = ElementBase::new(ElementType::Container);
+        base.size = (300.0, 200.0);
+        
+        // Устанавливаем стили по умолчанию
+        base.styles.insert("background-color".to_string(), "#F5F5F5".to_string());
+        base.styles.insert("border-width".to_string(), "1px".to_string());
+        base.styles.insert("border-style".to_string(), "solid".to_string());
+        base.styles.insert("border-color".to_string(), "#CCCCCC".to_string());
+        
+        Self {
+            base,
+            children: Vec::new(),
+        }
+    }
+    }
+
+impl UIElement for Container {
+    fn base(&self) -> &ElementBase {
+        &self.base
+    }
+    
+    fn base_mut(&mut self) -> &mut ElementBase {
+        &mut self.base
+    }
+    
+    fn get_id(&self) -> &str {
+        &self.base.id
+    }
+    
+    fn get_element_type(&self) -> &ElementType {
+        &self.base.element_type
+    }
+    
+    fn get_position(&self) -> (f32, f32) {
+        self.base.position
+    }
+    
+    fn set_position(&mut self, position: (f32, f32)) {
+        self.base.position = position;
+    }
+    
+    fn get_size(&self) -> (f32, f32) {
+        self.base.size
+    }
+    
+    fn set_size(&mut self, size: (f32, f32)) {
+        self.base.size = size;
+    }
+    
+    fn clone_box(&self) -> Box<dyn UIElement> {
+        Box::new(self.clone())
+    }
+    
+    fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(self)
+    }
+    
+    // Вложенный <div> с разметкой дочерних элементов
+    fn to_html(&self, ctx: &ExportContext) -> String {
+        let mut html = format!("<div{}>\n", self.base.attributes_html(ctx));
+        for child in &self.children {
+            html.push_str(&child.to_html(ctx));
+            html.push('\n');
+        }
+        html.push_str("</div>");
+        html
+    }
+    
+    // Правила контейнера и всех вложенных элементов; содержимое обрезается по рамке
+    fn to_css(&self, ctx: &ExportContext) -> Vec<CssRule> {
+        let mut rule = self.base.css_rule(ctx);
+        rule.declare("overflow", "hidden".to_string());
+        rule.declare("box-sizing", "border-box".to_string());
+        
+        let mut rules = vec![rule];
+        rules.extend(children_css(&self.children, is_flow(&self.base.styles), ctx));
+        rules
+    }
+    
+    fn children(&self) -> Option<&[Box<dyn UIElement>]> {
+        Some(&self.children)
+    }
+    
+    fn children_mut(&mut self) -> Option<&mut Vec<Box<dyn UIElement>>> {
+        Some(&mut self.children)
+    }
+    
+    fn assets(&self) -> Vec<String> {
+        let mut assets = self.base.style_assets();
+        assets.extend(self.children.iter().flat_map(|child| child.assets()));
+        assets
+    }
+    
+    fn property_schema(&self) -> Vec<PropertySection> {
+        vec![
+            properties::background_section(),
+            properties::border_section(),
+            properties::spacing_section(),
+            properties::effects_section(),
+            properties::attributes_section(),
+            properties::microdata_section(),
+        ]
+    }
+    
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    
+    fn contains_point(&self, point: (f32, f32)) -> bool {
+        self.base.contains_point(point)
+    }
+    
+    fn render(&self, painter: &egui::Painter, canvas: &CanvasContext) {
+        let element_rect = canvas.rect(&self.base);
+        let selected = canvas.is_selected(&self.base.id);
+        
+        canvas.paint_background(painter, &self.base, Color32::TRANSPARENT);
+        
+        // Дочерние элементы обрезаются по границам контейнера
+        let clipped = painter.with_clip_rect(element_rect.intersect(painter.clip_rect()));
+        let child_canvas = canvas.child(self.base.position);
+        child_canvas.render_elements(&clipped, &self.children);
+        
+        canvas.paint_border(painter, &self.base, Border::none());
+        
+        if selected {
+            painter.rect_stroke(element_rect, 0.0, Stroke::new(1.0, Color32::BLUE));
+        }
+    }
+}
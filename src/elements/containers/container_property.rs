@@ -0,0 +1,36 @@
+use egui::Ui;
+use crate::elements::containers::container::Container;
+use crate::elements::UIElement;
+use crate::elements::containers::layout_property::show_layout;
+
+// Структура для работы со свойствами контейнера.
+// Фон, рамка и отступы редактируются в общем инспекторе свойств
+#[derive(Default)]
+pub struct ContainerProperty;
+
+impl ContainerProperty {
+    pub fn new() -> Self {
+        Self
+    }
+    
+    // Метод для отображения и редактирования свойств контейнера
+    pub fn show(&mut self, ui: &mut Ui, element: &mut Box<dyn UIElement>) -> bool {
+        let Some(container) = element.as_any_mut().downcast_mut::<Container>() else {
+            return false;
+        };
+        
+        let mut changed = false;
+        
+        ui.label(format!("Вложенных элементов: {}", container.children.len()));
+        ui.label("Перетащите элемент на контейнер, чтобы вложить его, или за его пределы, чтобы извлечь.");
+        
+        ui.separator();
+        ui.heading("Раскладка");
+        
+        if show_layout(ui, "container", &mut container.base.styles) {
+            changed = true;
+        }
+        
+        changed
+    }
+}
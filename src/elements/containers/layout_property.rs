@@ -0,0 +1,246 @@
+use egui::Ui;
+use std::collections::HashMap;
+
+// Стили раскладки, которые убираются при переключении режима
+const FLEX_STYLES: [&str; 5] = ["flex-direction", "flex-wrap", "justify-content", "align-items", "align-content"];
+const GRID_STYLES: [&str; 5] = ["grid-template-columns", "grid-template-rows", "grid-template-areas", "justify-items", "align-items"];
+const GAP_STYLES: [&str; 3] = ["gap", "row-gap", "column-gap"];
+
+const DIRECTIONS: [(&str, &str); 4] = [
+    ("row", "Слева направо"),
+    ("row-reverse", "Справа налево"),
+    ("column", "Сверху вниз"),
+    ("column-reverse", "Снизу вверх"),
+];
+
+const DISTRIBUTIONS: [(&str, &str); 6] = [
+    ("flex-start", "В начале"),
+    ("center", "По центру"),
+    ("flex-end", "В конце"),
+    ("space-between", "Между элементами"),
+    ("space-around", "Вокруг элементов"),
+    ("space-evenly", "Равномерно"),
+];
+
+const LINE_DISTRIBUTIONS: [(&str, &str); 7] = [
+    ("normal", "Растянуть строки"),
+    ("flex-start", "В начале"),
+    ("center", "По центру"),
+    ("flex-end", "В конце"),
+    ("space-between", "Между строками"),
+    ("space-around", "Вокруг строк"),
+    ("space-evenly", "Равномерно"),
+];
+
+const ALIGNMENTS: [(&str, &str); 3] = [
+    ("start", "В начале"),
+    ("center", "По центру"),
+    ("end", "В конце"),
+];
+
+// Режим раскладки контейнера
+#[derive(Clone, Copy, PartialEq)]
+enum LayoutMode {
+    Free,
+    Flex,
+    Grid,
+}
+
+impl LayoutMode {
+    fn of(styles: &HashMap<String, String>) -> Self {
+        match styles.get("display").map(|s| s.trim()) {
+            Some("flex" | "inline-flex") => LayoutMode::Flex,
+            Some("grid" | "inline-grid") => LayoutMode::Grid,
+            _ => LayoutMode::Free,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            LayoutMode::Free => "Свободная",
+            LayoutMode::Flex => "В строку или столбец (flex)",
+            LayoutMode::Grid => "Сетка (grid)",
+        }
+    }
+}
+
+// Редактор раскладки вложенных элементов; используется контейнером и формой.
+// Раскладка хранится в стилях элемента, поэтому экспортируется и меняется по ширинам экрана как они
+pub fn show_layout(ui: &mut Ui, id_source: &str, styles: &mut HashMap<String, String>) -> bool {
+    let mut changed = false;
+
+    ui.label("Раскладка:");
+    let current = LayoutMode::of(styles);
+    let mut mode = current;
+    egui::ComboBox::from_id_source((id_source, "layout_mode"))
+        .selected_text(mode.label())
+        .show_ui(ui, |ui| {
+            for option in [LayoutMode::Free, LayoutMode::Flex, LayoutMode::Grid] {
+                ui.selectable_value(&mut mode, option, option.label());
+            }
+        });
+    if mode != current {
+        for key in FLEX_STYLES.iter().chain(&GRID_STYLES).chain(&GAP_STYLES) {
+            styles.remove(*key);
+        }
+        match mode {
+            LayoutMode::Free => {
+                styles.remove("display");
+                styles.remove("padding");
+            },
+            LayoutMode::Flex => {
+                styles.insert("display".to_string(), "flex".to_string());
+                styles.insert("gap".to_string(), "10px".to_string());
+            },
+            LayoutMode::Grid => {
+                styles.insert("display".to_string(), "grid".to_string());
+                styles.insert("grid-template-columns".to_string(), "1fr 1fr".to_string());
+                styles.insert("gap".to_string(), "10px".to_string());
+            },
+        }
+        changed = true;
+    }
+
+    match mode {
+        LayoutMode::Free => {
+            ui.label("Элементы расставляются по своим координатам.");
+            return changed;
+        },
+        LayoutMode::Flex => {
+            ui.label("Направление:");
+            changed |= style_combo(ui, (id_source, "direction"), styles, "flex-direction", &DIRECTIONS);
+
+            let mut wrap = styles.get("flex-wrap").is_some_and(|s| s.trim() == "wrap");
+            if ui.checkbox(&mut wrap, "Переносить на новую строку").changed() {
+                if wrap {
+                    styles.insert("flex-wrap".to_string(), "wrap".to_string());
+                } else {
+                    styles.remove("flex-wrap");
+                    styles.remove("align-content");
+                }
+                changed = true;
+            }
+
+            ui.label("Расстояние между элементами (px):");
+            changed |= style_px(ui, styles, "gap");
+
+            ui.label("Распределение вдоль направления:");
+            changed |= style_combo(ui, (id_source, "justify"), styles, "justify-content", &DISTRIBUTIONS);
+
+            ui.label("Выравнивание поперек направления:");
+            changed |= style_combo(ui, (id_source, "align"), styles, "align-items", &ALIGNMENTS);
+
+            if wrap {
+                ui.label("Распределение строк:");
+                changed |= style_combo(ui, (id_source, "align_content"), styles, "align-content", &LINE_DISTRIBUTIONS);
+            }
+        },
+        LayoutMode::Grid => {
+            ui.label("Столбцы (например, 1fr 200px auto):");
+            changed |= style_text(ui, styles, "grid-template-columns");
+
+            ui.label("Строки:");
+            changed |= style_text(ui, styles, "grid-template-rows");
+
+            ui.label("Области (по строке сетки на строку текста):");
+            // Пока поле в фокусе, текст хранится как введен: пустые строки в стиль не попадают
+            let areas_id = ui.id().with((id_source, "areas"));
+            let mut areas = ui.data_mut(|d| d.get_temp::<String>(areas_id))
+                .filter(|_| ui.memory(|m| m.has_focus(areas_id)))
+                .unwrap_or_else(|| areas_text(styles.get("grid-template-areas").map(String::as_str).unwrap_or("")));
+            let response = ui.add(egui::TextEdit::multiline(&mut areas).id(areas_id));
+            ui.data_mut(|d| d.insert_temp(areas_id, areas.clone()));
+            if response.changed() {
+                let value = areas_value(&areas);
+                if value.is_empty() {
+                    styles.remove("grid-template-areas");
+                } else {
+                    styles.insert("grid-template-areas".to_string(), value);
+                }
+                changed = true;
+            }
+
+            ui.label("Расстояние между столбцами (px):");
+            changed |= style_px(ui, styles, "column-gap");
+            ui.label("Расстояние между строками (px):");
+            changed |= style_px(ui, styles, "row-gap");
+
+            ui.label("Выравнивание в ячейке по горизонтали:");
+            changed |= style_combo(ui, (id_source, "justify_items"), styles, "justify-items", &ALIGNMENTS);
+            ui.label("Выравнивание в ячейке по вертикали:");
+            changed |= style_combo(ui, (id_source, "align_items"), styles, "align-items", &ALIGNMENTS);
+        },
+    }
+
+    ui.label("Внутренний отступ (px):");
+    changed |= style_px(ui, styles, "padding");
+
+    changed
+}
+
+// Выбор значения стиля из списка; первый вариант - значение по умолчанию
+fn style_combo(ui: &mut Ui, id_source: (&str, &str), styles: &mut HashMap<String, String>, key: &str, options: &[(&str, &str)]) -> bool {
+    let current = styles.get(key).map(|s| s.trim().to_string());
+    let mut selected = current.clone().unwrap_or_else(|| options[0].0.to_string());
+    let selected_label = options.iter()
+        .find(|(value, _)| *value == selected)
+        .map(|(_, label)| label.to_string())
+        .unwrap_or_else(|| selected.clone());
+
+    egui::ComboBox::from_id_source(id_source)
+        .selected_text(selected_label)
+        .show_ui(ui, |ui| {
+            for (value, label) in options {
+                ui.selectable_value(&mut selected, value.to_string(), *label);
+            }
+        });
+    if current.as_deref() == Some(selected.as_str()) || (current.is_none() && selected == options[0].0) {
+        return false;
+    }
+    styles.insert(key.to_string(), selected);
+    true
+}
+
+// Размер в пикселях; у перечисления через пробел меняется все значение целиком
+fn style_px(ui: &mut Ui, styles: &mut HashMap<String, String>, key: &str) -> bool {
+    let mut value = styles.get(key)
+        .and_then(|s| s.split_whitespace().next()?.replace("px", "").parse::<f32>().ok())
+        .unwrap_or(0.0);
+    if ui.add(egui::DragValue::new(&mut value).clamp_range(0.0..=500.0).speed(1.0)).changed() {
+        styles.insert(key.to_string(), format!("{}px", value));
+        return true;
+    }
+    false
+}
+
+// Произвольное значение стиля; пустая строка убирает стиль
+fn style_text(ui: &mut Ui, styles: &mut HashMap<String, String>, key: &str) -> bool {
+    let mut value = styles.get(key).cloned().unwrap_or_default();
+    if !ui.text_edit_singleline(&mut value).changed() {
+        return false;
+    }
+    if value.trim().is_empty() {
+        styles.remove(key);
+    } else {
+        styles.insert(key.to_string(), value);
+    }
+    true
+}
+
+// Значение grid-template-areas ("a b" "c d") в виде строк текста
+fn areas_text(value: &str) -> String {
+    value.split('"')
+        .skip(1)
+        .step_by(2)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn areas_value(text: &str) -> String {
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .map(|line| format!("\"{}\"", line))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
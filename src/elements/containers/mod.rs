@@ -0,0 +1,3 @@
+pub mod container;
+pub mod container_property;
+pub mod layout_property;
@@ -0,0 +1,783 @@
+// Разбор значений CSS для отрисовки элементов на холсте так же, как в браузере:
+// цвета, длины с единицами и сокращенные записи рамок, отступов и скруглений
+
+use egui::{Color32, Rounding, Stroke};
+use std::collections::HashMap;
+
+// Размер шрифта корневого элемента страницы (для rem и em)
+pub const ROOT_FONT_SIZE: f32 = 16.0;
+
+// Стороны в порядке сокращенных записей CSS
+pub const SIDES: [&str; 4] = ["top", "right", "bottom", "left"];
+
+// Углы в порядке сокращенной записи border-radius
+pub const CORNERS: [&str; 4] = ["top-left", "top-right", "bottom-right", "bottom-left"];
+
+// Длина CSS с единицами измерения
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Length {
+    Px(f32),
+    Em(f32),
+    Rem(f32),
+    Percent(f32),
+}
+
+impl Length {
+    // Число без единиц считается пикселями
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_ascii_lowercase();
+        let number = |text: &str| text.trim().parse::<f32>().ok().filter(|n| n.is_finite());
+
+        if let Some(text) = value.strip_suffix("px") {
+            number(text).map(Length::Px)
+        } else if let Some(text) = value.strip_suffix("rem") {
+            number(text).map(Length::Rem)
+        } else if let Some(text) = value.strip_suffix("em") {
+            number(text).map(Length::Em)
+        } else if let Some(text) = value.strip_suffix('%') {
+            number(text).map(Length::Percent)
+        } else if let Some(text) = value.strip_suffix("pt") {
+            number(text).map(|n| Length::Px(n * 4.0 / 3.0))
+        } else {
+            number(&value).map(Length::Px)
+        }
+    }
+
+    // Длина в пикселях; em - от размера шрифта элемента, проценты - от reference
+    pub fn to_px(self, font_size: f32, reference: f32) -> f32 {
+        match self {
+            Length::Px(n) => n,
+            Length::Em(n) => n * font_size,
+            Length::Rem(n) => n * ROOT_FONT_SIZE,
+            Length::Percent(n) => n / 100.0 * reference,
+        }
+    }
+}
+
+// Длина из стилей в пикселях
+pub fn length(styles: &HashMap<String, String>, key: &str, font_size: f32, reference: f32) -> Option<f32> {
+    styles.get(key)
+        .and_then(|value| Length::parse(value))
+        .map(|length| length.to_px(font_size, reference))
+}
+
+// Цвет из стилей
+pub fn color(styles: &HashMap<String, String>, key: &str) -> Option<Color32> {
+    styles.get(key).and_then(|value| parse_color(value))
+}
+
+// Цвет CSS: #RGB, #RGBA, #RRGGBB, #RRGGBBAA, rgb(), rgba(), hsl(), hsla() и имена цветов
+pub fn parse_color(value: &str) -> Option<Color32> {
+    let value = value.trim().to_ascii_lowercase();
+
+    if let Some(hex) = value.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+    if let Some((name, args)) = value.strip_suffix(')').and_then(|v| v.split_once('(')) {
+        let args = color_args(args);
+        return match name.trim() {
+            "rgb" | "rgba" => parse_rgb(&args),
+            "hsl" | "hsla" => parse_hsl(&args),
+            _ => None,
+        };
+    }
+    if value == "transparent" {
+        return Some(Color32::TRANSPARENT);
+    }
+    named_color(&value).map(|[r, g, b]| Color32::from_rgb(r, g, b))
+}
+
+fn parse_hex(hex: &str) -> Option<Color32> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|d| d * 17);
+    let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+
+    let [r, g, b, a] = match hex.len() {
+        3 => [digit(0)?, digit(1)?, digit(2)?, 255],
+        4 => [digit(0)?, digit(1)?, digit(2)?, digit(3)?],
+        6 => [pair(0)?, pair(2)?, pair(4)?, 255],
+        8 => [pair(0)?, pair(2)?, pair(4)?, pair(6)?],
+        _ => return None,
+    };
+    Some(Color32::from_rgba_unmultiplied(r, g, b, a))
+}
+
+// Аргументы цветовой функции в старой (через запятую) и новой (через пробел и /) записи
+fn color_args(args: &str) -> Vec<String> {
+    args.replace(['/', ','], " ")
+        .split_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+// Число или процент от max
+fn parse_component(value: &str, max: f32) -> Option<f32> {
+    match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f32>().ok().map(|p| p / 100.0 * max),
+        None => value.parse::<f32>().ok(),
+    }
+    .filter(|n| n.is_finite())
+}
+
+// Прозрачность: число от 0 до 1 или процент
+fn parse_alpha(value: Option<&String>) -> Option<u8> {
+    let alpha = match value {
+        Some(value) => parse_component(value, 1.0)?,
+        None => 1.0,
+    };
+    Some((alpha.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+fn parse_rgb(args: &[String]) -> Option<Color32> {
+    if !(3..=4).contains(&args.len()) {
+        return None;
+    }
+    let channel = |value: &String| parse_component(value, 255.0).map(|c| c.clamp(0.0, 255.0).round() as u8);
+    Some(Color32::from_rgba_unmultiplied(
+        channel(&args[0])?,
+        channel(&args[1])?,
+        channel(&args[2])?,
+        parse_alpha(args.get(3))?,
+    ))
+}
+
+fn parse_hsl(args: &[String]) -> Option<Color32> {
+    if !(3..=4).contains(&args.len()) {
+        return None;
+    }
+    let hue = parse_hue(&args[0])?;
+    let saturation = parse_component(args[1].trim_end_matches('%'), 100.0)?.clamp(0.0, 100.0) / 100.0;
+    let lightness = parse_component(args[2].trim_end_matches('%'), 100.0)?.clamp(0.0, 100.0) / 100.0;
+    let [r, g, b] = hsl_to_rgb(hue, saturation, lightness);
+    Some(Color32::from_rgba_unmultiplied(r, g, b, parse_alpha(args.get(3))?))
+}
+
+// Оттенок в градусах: число, deg, rad, grad или turn
+fn parse_hue(value: &str) -> Option<f32> {
+    let (number, scale) = if let Some(n) = value.strip_suffix("deg") {
+        (n, 1.0)
+    } else if let Some(n) = value.strip_suffix("grad") {
+        (n, 0.9)
+    } else if let Some(n) = value.strip_suffix("rad") {
+        (n, 180.0 / std::f32::consts::PI)
+    } else if let Some(n) = value.strip_suffix("turn") {
+        (n, 360.0)
+    } else {
+        (value, 1.0)
+    };
+    number.parse::<f32>().ok().filter(|n| n.is_finite()).map(|n| n * scale)
+}
+
+// Перевод HSL (оттенок в градусах, насыщенность и светлота от 0 до 1) в RGB
+pub fn hsl_to_rgb(hue: f32, saturation: f32, lightness: f32) -> [u8; 3] {
+    let hue = hue.rem_euclid(360.0);
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let m = lightness - chroma / 2.0;
+    let (r, g, b) = match hue {
+        h if h < 60.0 => (chroma, x, 0.0),
+        h if h < 120.0 => (x, chroma, 0.0),
+        h if h < 180.0 => (0.0, chroma, x),
+        h if h < 240.0 => (0.0, x, chroma),
+        h if h < 300.0 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let to_u8 = |c: f32| ((c + m).clamp(0.0, 1.0) * 255.0).round() as u8;
+    [to_u8(r), to_u8(g), to_u8(b)]
+}
+
+// Цвет в записи CSS: #RRGGBB, с прозрачностью - #RRGGBBAA
+pub fn format_color(color: Color32) -> String {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    if a == 255 {
+        format!("#{:02X}{:02X}{:02X}", r, g, b)
+    } else {
+        format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+    }
+}
+
+// Путь из значения url("...")
+pub fn url_path(value: &str) -> Option<String> {
+    let inner = value.trim().strip_prefix("url(")?.strip_suffix(')')?.trim();
+    let path = inner.trim_matches(|c| c == '"' || c == '\'');
+    (!path.is_empty()).then(|| path.to_string())
+}
+
+// Значение url("...") для пути
+pub fn url(path: &str) -> String {
+    format!("url(\"{}\")", path.replace('"', "%22"))
+}
+
+// Разбивает значение на части по пробелам вне скобок: "1px solid rgb(0, 0, 0)"
+pub fn split_values(value: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    for c in value.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if c.is_whitespace() && depth == 0 => {
+                if !current.is_empty() {
+                    parts.push(std::mem::take(&mut current));
+                }
+                continue;
+            },
+            _ => {},
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+// Значения сторон из сокращенной записи из 1-4 частей: (верх, право, низ, лево)
+pub fn expand_sides<T: Clone>(values: &[T]) -> Option<[T; 4]> {
+    let (top, right, bottom, left) = match values {
+        [all] => (all, all, all, all),
+        [vertical, horizontal] => (vertical, horizontal, vertical, horizontal),
+        [top, horizontal, bottom] => (top, horizontal, bottom, horizontal),
+        [top, right, bottom, left] => (top, right, bottom, left),
+        _ => return None,
+    };
+    Some([top.clone(), right.clone(), bottom.clone(), left.clone()])
+}
+
+// Отступы padding или margin по сторонам: сокращенная запись, затем отдельные свойства сторон.
+// Проценты считаются от ширины reference
+pub fn edges(styles: &HashMap<String, String>, property: &str, font_size: f32, reference: f32) -> [f32; 4] {
+    let to_px = |value: &str| Length::parse(value).map(|l| l.to_px(font_size, reference));
+
+    let mut edges = styles.get(property)
+        .and_then(|value| {
+            let values: Option<Vec<f32>> = split_values(value).iter().map(|v| to_px(v)).collect();
+            expand_sides(&values?)
+        })
+        .unwrap_or([0.0; 4]);
+    for (edge, side) in edges.iter_mut().zip(SIDES) {
+        if let Some(value) = styles.get(&format!("{}-{}", property, side)).and_then(|v| to_px(v)) {
+            *edge = value;
+        }
+    }
+    edges
+}
+
+// Рамка элемента по сторонам (верх, право, низ, лево)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Border {
+    widths: [f32; 4],
+    // Стиль рамки не none и не hidden
+    visible: [bool; 4],
+    // None - цвет текста элемента (currentColor)
+    colors: [Option<Color32>; 4],
+}
+
+impl Border {
+    // Рамка по умолчанию: border-style none
+    pub fn none() -> Self {
+        Self {
+            widths: [3.0; 4],
+            visible: [false; 4],
+            colors: [None; 4],
+        }
+    }
+
+    // Одинаковая рамка со всех сторон
+    pub fn uniform(width: f32, color: Color32) -> Self {
+        Self {
+            widths: [width; 4],
+            visible: [true; 4],
+            colors: [Some(color); 4],
+        }
+    }
+
+    // Рамка из стилей поверх начальной initial (стили браузера для элемента):
+    // border, border-top..., border-width/style/color и свойства отдельных сторон
+    pub fn from_styles(styles: &HashMap<String, String>, font_size: f32, initial: Border) -> Self {
+        let mut border = initial;
+
+        if let Some(value) = styles.get("border") {
+            border.apply_shorthand(value, font_size, &[0, 1, 2, 3]);
+        }
+        for (side, name) in SIDES.iter().enumerate() {
+            if let Some(value) = styles.get(&format!("border-{}", name)) {
+                border.apply_shorthand(value, font_size, &[side]);
+            }
+        }
+
+        let sides = |key: &str| styles.get(key).map(|value| split_values(value));
+        if let Some(widths) = sides("border-width").and_then(|v| expand_sides(&v)) {
+            for (side, value) in widths.iter().enumerate() {
+                border.set_width(side, value, font_size);
+            }
+        }
+        if let Some(values) = sides("border-style").and_then(|v| expand_sides(&v)) {
+            for (side, value) in values.iter().enumerate() {
+                border.set_style(side, value);
+            }
+        }
+        if let Some(values) = sides("border-color").and_then(|v| expand_sides(&v)) {
+            for (side, value) in values.iter().enumerate() {
+                border.set_color(side, value);
+            }
+        }
+
+        for (side, name) in SIDES.iter().enumerate() {
+            if let Some(value) = styles.get(&format!("border-{}-width", name)) {
+                border.set_width(side, value, font_size);
+            }
+            if let Some(value) = styles.get(&format!("border-{}-style", name)) {
+                border.set_style(side, value);
+            }
+            if let Some(value) = styles.get(&format!("border-{}-color", name)) {
+                border.set_color(side, value);
+            }
+        }
+
+        border
+    }
+
+    // Сокращенная запись "ширина стиль цвет" в любом порядке; пропущенные части сбрасываются
+    fn apply_shorthand(&mut self, value: &str, font_size: f32, sides: &[usize]) {
+        for &side in sides {
+            self.widths[side] = 3.0;
+            self.visible[side] = false;
+            self.colors[side] = None;
+        }
+        for part in split_values(value) {
+            for &side in sides {
+                if is_border_style(&part) {
+                    self.set_style(side, &part);
+                } else if border_width(&part, font_size).is_some() {
+                    self.set_width(side, &part, font_size);
+                } else {
+                    self.set_color(side, &part);
+                }
+            }
+        }
+    }
+
+    fn set_width(&mut self, side: usize, value: &str, font_size: f32) {
+        if let Some(width) = border_width(value, font_size) {
+            self.widths[side] = width;
+        }
+    }
+
+    fn set_style(&mut self, side: usize, value: &str) {
+        if is_border_style(value) {
+            self.visible[side] = !matches!(value.trim(), "none" | "hidden");
+        }
+    }
+
+    fn set_color(&mut self, side: usize, value: &str) {
+        if value.trim().eq_ignore_ascii_case("currentcolor") {
+            self.colors[side] = None;
+        } else if let Some(color) = parse_color(value) {
+            self.colors[side] = Some(color);
+        }
+    }
+
+    // Видимые толщины сторон: (верх, право, низ, лево)
+    pub fn widths(&self) -> [f32; 4] {
+        let mut widths = self.widths;
+        for (width, visible) in widths.iter_mut().zip(self.visible) {
+            if !visible {
+                *width = 0.0;
+            }
+        }
+        widths
+    }
+
+    // Линия для отрисовки на холсте: egui рисует рамку одной толщины и одного цвета,
+    // поэтому берутся самая толстая сторона и цвет первой видимой стороны
+    pub fn stroke(&self, text_color: Color32) -> Option<Stroke> {
+        let widths = self.widths();
+        let width = widths.iter().copied().fold(0.0, f32::max);
+        if width <= 0.0 {
+            return None;
+        }
+        let side = widths.iter().position(|w| *w > 0.0).unwrap_or(0);
+        Some(Stroke::new(width, self.colors[side].unwrap_or(text_color)))
+    }
+}
+
+fn is_border_style(value: &str) -> bool {
+    matches!(
+        value.trim(),
+        "none" | "hidden" | "dotted" | "dashed" | "solid" | "double" | "groove" | "ridge" | "inset" | "outset"
+    )
+}
+
+fn border_width(value: &str, font_size: f32) -> Option<f32> {
+    match value.trim() {
+        "thin" => Some(1.0),
+        "medium" => Some(3.0),
+        "thick" => Some(5.0),
+        value => Length::parse(value)
+            .filter(|length| !matches!(length, Length::Percent(_)))
+            .map(|length| length.to_px(font_size, 0.0)),
+    }
+}
+
+// Радиусы скругления углов (левый верхний, правый верхний, правый нижний, левый нижний).
+// Как в браузере, слишком большие радиусы уменьшаются, чтобы скругления соседних углов не перекрывались
+pub fn border_radius(styles: &HashMap<String, String>, font_size: f32, size: (f32, f32)) -> [f32; 4] {
+    // Проценты берутся от меньшей стороны: egui рисует только круговые скругления
+    let reference = size.0.min(size.1).max(0.0);
+    let to_px = |value: &str| Length::parse(value).map(|l| l.to_px(font_size, reference).max(0.0));
+
+    // Эллиптические радиусы "a / b" заменяются горизонтальными
+    let mut radii = styles.get("border-radius")
+        .and_then(|value| {
+            let horizontal = value.split('/').next().unwrap_or("");
+            let values: Option<Vec<f32>> = split_values(horizontal).iter().map(|v| to_px(v)).collect();
+            expand_sides(&values?)
+        })
+        .unwrap_or([0.0; 4]);
+    for (radius, corner) in radii.iter_mut().zip(CORNERS) {
+        if let Some(value) = styles.get(&format!("border-{}-radius", corner))
+            .and_then(|v| split_values(v).first().and_then(|v| to_px(v))) {
+            *radius = value;
+        }
+    }
+
+    let [top_left, top_right, bottom_right, bottom_left] = radii;
+    let scale = [
+        size.0 / (top_left + top_right),
+        size.0 / (bottom_left + bottom_right),
+        size.1 / (top_left + bottom_left),
+        size.1 / (top_right + bottom_right),
+    ]
+    .into_iter()
+    .filter(|k| k.is_finite())
+    .fold(1.0, f32::min)
+    .max(0.0);
+    radii.map(|r| r * scale)
+}
+
+// Скругление углов для отрисовки в egui
+pub fn rounding(styles: &HashMap<String, String>, font_size: f32, size: (f32, f32)) -> Rounding {
+    let [top_left, top_right, bottom_right, bottom_left] = border_radius(styles, font_size, size);
+    Rounding {
+        nw: top_left,
+        ne: top_right,
+        se: bottom_right,
+        sw: bottom_left,
+    }
+}
+
+// Размер шрифта в пикселях: длины и ключевые слова; em и % - от шрифта страницы
+pub fn font_size(styles: &HashMap<String, String>, default: f32) -> f32 {
+    let Some(value) = styles.get("font-size") else {
+        return default;
+    };
+    match value.trim() {
+        "xx-small" => 9.0,
+        "x-small" => 10.0,
+        "small" => 13.0,
+        "medium" => 16.0,
+        "large" => 18.0,
+        "x-large" => 24.0,
+        "xx-large" => 32.0,
+        "xxx-large" => 48.0,
+        value => Length::parse(value)
+            .map(|length| length.to_px(ROOT_FONT_SIZE, ROOT_FONT_SIZE))
+            .filter(|size| *size > 0.0)
+            .unwrap_or(default),
+    }
+}
+
+// Межстрочный интервал в пикселях: normal, множитель или длина
+pub fn line_height(styles: &HashMap<String, String>, font_size: f32) -> f32 {
+    let normal = font_size * 1.2;
+    let Some(value) = styles.get("line-height").map(|s| s.trim()) else {
+        return normal;
+    };
+    if let Ok(factor) = value.parse::<f32>() {
+        return factor * font_size;
+    }
+    Length::parse(value)
+        .map(|length| length.to_px(font_size, font_size))
+        .unwrap_or(normal)
+}
+
+// Именованные цвета CSS
+fn named_color(name: &str) -> Option<[u8; 3]> {
+    let rgb = match name {
+        "aliceblue" => [240, 248, 255],
+        "antiquewhite" => [250, 235, 215],
+        "aqua" | "cyan" => [0, 255, 255],
+        "aquamarine" => [127, 255, 212],
+        "azure" => [240, 255, 255],
+        "beige" => [245, 245, 220],
+        "bisque" => [255, 228, 196],
+        "black" => [0, 0, 0],
+        "blanchedalmond" => [255, 235, 205],
+        "blue" => [0, 0, 255],
+        "blueviolet" => [138, 43, 226],
+        "brown" => [165, 42, 42],
+        "burlywood" => [222, 184, 135],
+        "cadetblue" => [95, 158, 160],
+        "chartreuse" => [127, 255, 0],
+        "chocolate" => [210, 105, 30],
+        "coral" => [255, 127, 80],
+        "cornflowerblue" => [100, 149, 237],
+        "cornsilk" => [255, 248, 220],
+        "crimson" => [220, 20, 60],
+        "darkblue" => [0, 0, 139],
+        "darkcyan" => [0, 139, 139],
+        "darkgoldenrod" => [184, 134, 11],
+        "darkgray" | "darkgrey" => [169, 169, 169],
+        "darkgreen" => [0, 100, 0],
+        "darkkhaki" => [189, 183, 107],
+        "darkmagenta" => [139, 0, 139],
+        "darkolivegreen" => [85, 107, 47],
+        "darkorange" => [255, 140, 0],
+        "darkorchid" => [153, 50, 204],
+        "darkred" => [139, 0, 0],
+        "darksalmon" => [233, 150, 122],
+        "darkseagreen" => [143, 188, 143],
+        "darkslateblue" => [72, 61, 139],
+        "darkslategray" | "darkslategrey" => [47, 79, 79],
+        "darkturquoise" => [0, 206, 209],
+        "darkviolet" => [148, 0, 211],
+        "deeppink" => [255, 20, 147],
+        "deepskyblue" => [0, 191, 255],
+        "dimgray" | "dimgrey" => [105, 105, 105],
+        "dodgerblue" => [30, 144, 255],
+        "firebrick" => [178, 34, 34],
+        "floralwhite" => [255, 250, 240],
+        "forestgreen" => [34, 139, 34],
+        "fuchsia" | "magenta" => [255, 0, 255],
+        "gainsboro" => [220, 220, 220],
+        "ghostwhite" => [248, 248, 255],
+        "gold" => [255, 215, 0],
+        "goldenrod" => [218, 165, 32],
+        "gray" | "grey" => [128, 128, 128],
+        "green" => [0, 128, 0],
+        "greenyellow" => [173, 255, 47],
+        "honeydew" => [240, 255, 240],
+        "hotpink" => [255, 105, 180],
+        "indianred" => [205, 92, 92],
+        "indigo" => [75, 0, 130],
+        "ivory" => [255, 255, 240],
+        "khaki" => [240, 230, 140],
+        "lavender" => [230, 230, 250],
+        "lavenderblush" => [255, 240, 245],
+        "lawngreen" => [124, 252, 0],
+        "lemonchiffon" => [255, 250, 205],
+        "lightblue" => [173, 216, 230],
+        "lightcoral" => [240, 128, 128],
+        "lightcyan" => [224, 255, 255],
+        "lightgoldenrodyellow" => [250, 250, 210],
+        "lightgray" | "lightgrey" => [211, 211, 211],
+        "lightgreen" => [144, 238, 144],
+        "lightpink" => [255, 182, 193],
+        "lightsalmon" => [255, 160, 122],
+        "lightseagreen" => [32, 178, 170],
+        "lightskyblue" => [135, 206, 250],
+        "lightslategray" | "lightslategrey" => [119, 136, 153],
+        "lightsteelblue" => [176, 196, 222],
+        "lightyellow" => [255, 255, 224],
+        "lime" => [0, 255, 0],
+        "limegreen" => [50, 205, 50],
+        "linen" => [250, 240, 230],
+        "maroon" => [128, 0, 0],
+        "mediumaquamarine" => [102, 205, 170],
+        "mediumblue" => [0, 0, 205],
+        "mediumorchid" => [186, 85, 211],
+        "mediumpurple" => [147, 112, 219],
+        "mediumseagreen" => [60, 179, 113],
+        "mediumslateblue" => [123, 104, 238],
+        "mediumspringgreen" => [0, 250, 154],
+        "mediumturquoise" => [72, 209, 204],
+        "mediumvioletred" => [199, 21, 133],
+        "midnightblue" => [25, 25, 112],
+        "mintcream" => [245, 255, 250],
+        "mistyrose" => [255, 228, 225],
+        "moccasin" => [255, 228, 181],
+        "navajowhite" => [255, 222, 173],
+        "navy" => [0, 0, 128],
+        "oldlace" => [253, 245, 230],
+        "olive" => [128, 128, 0],
+        "olivedrab" => [107, 142, 35],
+        "orange" => [255, 165, 0],
+        "orangered" => [255, 69, 0],
+        "orchid" => [218, 112, 214],
+        "palegoldenrod" => [238, 232, 170],
+        "palegreen" => [152, 251, 152],
+        "paleturquoise" => [175, 238, 238],
+        "palevioletred" => [219, 112, 147],
+        "papayawhip" => [255, 239, 213],
+        "peachpuff" => [255, 218, 185],
+        "peru" => [205, 133, 63],
+        "pink" => [255, 192, 203],
+        "plum" => [221, 160, 221],
+        "powderblue" => [176, 224, 230],
+        "purple" => [128, 0, 128],
+        "rebeccapurple" => [102, 51, 153],
+        "red" => [255, 0, 0],
+        "rosybrown" => [188, 143, 143],
+        "royalblue" => [65, 105, 225],
+        "saddlebrown" => [139, 69, 19],
+        "salmon" => [250, 128, 114],
+        "sandybrown" => [244, 164, 96],
+        "seagreen" => [46, 139, 87],
+        "seashell" => [255, 245, 238],
+        "sienna" => [160, 82, 45],
+        "silver" => [192, 192, 192],
+        "skyblue" => [135, 206, 235],
+        "slateblue" => [106, 90, 205],
+        "slategray" | "slategrey" => [112, 128, 144],
+        "snow" => [255, 250, 250],
+        "springgreen" => [0, 255, 127],
+        "steelblue" => [70, 130, 180],
+        "tan" => [210, 180, 140],
+        "teal" => [0, 128, 128],
+        "thistle" => [216, 191, 216],
+        "tomato" => [255, 99, 71],
+        "turquoise" => [64, 224, 208],
+        "violet" => [238, 130, 238],
+        "wheat" => [245, 222, 179],
+        "white" => [255, 255, 255],
+        "whitesmoke" => [245, 245, 245],
+        "yellow" => [255, 255, 0],
+        "yellowgreen" => [154, 205, 50],
+        _ => return None,
+    };
+    Some(rgb)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn styles(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn lengths_parse_units() {
+        assert_eq!(Length::parse("10px"), Some(Length::Px(10.0)));
+        assert_eq!(Length::parse(" 7 "), Some(Length::Px(7.0)));
+        assert_eq!(Length::parse("1.5EM"), Some(Length::Em(1.5)));
+        assert_eq!(Length::parse("2rem"), Some(Length::Rem(2.0)));
+        assert_eq!(Length::parse("50%"), Some(Length::Percent(50.0)));
+        assert_eq!(Length::parse("12pt"), Some(Length::Px(16.0)));
+        assert_eq!(Length::parse("px"), None);
+        assert_eq!(Length::parse("abc"), None);
+        assert_eq!(Length::parse("NaNpx"), None);
+        assert_eq!(Length::parse("infpx"), None);
+
+        assert_eq!(Length::Em(2.0).to_px(10.0, 0.0), 20.0);
+        assert_eq!(Length::Rem(2.0).to_px(10.0, 0.0), 32.0);
+        assert_eq!(Length::Percent(50.0).to_px(10.0, 300.0), 150.0);
+    }
+
+    #[test]
+    fn hex_colors() {
+        assert_eq!(parse_color("#fff"), Some(Color32::WHITE));
+        assert_eq!(parse_color("#0000"), Some(Color32::TRANSPARENT));
+        assert_eq!(parse_color("#1E88E5"), Some(Color32::from_rgb(0x1E, 0x88, 0xE5)));
+        assert_eq!(parse_color("#FF000080"), Some(Color32::from_rgba_unmultiplied(255, 0, 0, 128)));
+        assert_eq!(parse_color("#12345"), None);
+        assert_eq!(parse_color("#ggg"), None);
+    }
+
+    #[test]
+    fn functional_and_named_colors() {
+        assert_eq!(parse_color("rgb(255, 0, 0)"), Some(Color32::RED));
+        assert_eq!(parse_color("rgb(100%, 0%, 0%)"), Some(Color32::RED));
+        assert_eq!(parse_color("rgba(0, 0, 255, 0.5)"), Some(Color32::from_rgba_unmultiplied(0, 0, 255, 128)));
+        assert_eq!(parse_color("rgb(0 128 0 / 50%)"), Some(Color32::from_rgba_unmultiplied(0, 128, 0, 128)));
+        assert_eq!(parse_color("hsl(120, 100%, 50%)"), Some(Color32::from_rgb(0, 255, 0)));
+        assert_eq!(parse_color("hsla(240deg 100% 50% / 0.25)"), Some(Color32::from_rgba_unmultiplied(0, 0, 255, 64)));
+        assert_eq!(parse_color("hsl(0.5turn, 100%, 50%)"), Some(Color32::from_rgb(0, 255, 255)));
+        assert_eq!(parse_color(" Red "), Some(Color32::from_rgb(255, 0, 0)));
+        assert_eq!(parse_color("transparent"), Some(Color32::TRANSPARENT));
+        assert_eq!(parse_color("rgb(1, 2)"), None);
+        assert_eq!(parse_color("rgb(a, b, c)"), None);
+        assert_eq!(parse_color("foo(1, 2, 3)"), None);
+        assert_eq!(parse_color("notacolor"), None);
+    }
+
+    #[test]
+    fn colors_format_back_to_hex() {
+        assert_eq!(format_color(Color32::from_rgb(0x1E, 0x88, 0xE5)), "#1E88E5");
+        assert_eq!(format_color(parse_color("#FF000080").unwrap()), "#FF000080");
+    }
+
+    #[test]
+    fn values_split_outside_parentheses() {
+        assert_eq!(split_values("1px solid rgb(0, 0, 0)"), vec!["1px", "solid", "rgb(0, 0, 0)"]);
+        assert_eq!(split_values("  a   b "), vec!["a", "b"]);
+        assert!(split_values("").is_empty());
+    }
+
+    #[test]
+    fn sides_expand_from_shorthand() {
+        assert_eq!(expand_sides(&[1]), Some([1, 1, 1, 1]));
+        assert_eq!(expand_sides(&[1, 2]), Some([1, 2, 1, 2]));
+        assert_eq!(expand_sides(&[1, 2, 3]), Some([1, 2, 3, 2]));
+        assert_eq!(expand_sides(&[1, 2, 3, 4]), Some([1, 2, 3, 4]));
+        assert_eq!(expand_sides::<i32>(&[]), None);
+        assert_eq!(expand_sides(&[1, 2, 3, 4, 5]), None);
+    }
+
+    #[test]
+    fn edges_combine_shorthand_and_sides() {
+        let padding = styles(&[("padding", "10px 20px"), ("padding-left", "5px")]);
+        assert_eq!(edges(&padding, "padding", 16.0, 0.0), [10.0, 20.0, 10.0, 5.0]);
+        assert_eq!(edges(&styles(&[("margin", "10%")]), "margin", 16.0, 200.0), [20.0; 4]);
+        assert_eq!(edges(&styles(&[("padding", "10px abc")]), "padding", 16.0, 0.0), [0.0; 4]);
+    }
+
+    #[test]
+    fn radii_expand_and_shrink_to_fit() {
+        let radius = |value: &str, size| border_radius(&styles(&[("border-radius", value)]), 16.0, size);
+        assert_eq!(radius("10px", (100.0, 100.0)), [10.0; 4]);
+        assert_eq!(radius("50%", (100.0, 40.0)), [20.0; 4]);
+        assert_eq!(radius("80px", (100.0, 100.0)), [50.0; 4]);
+        assert_eq!(radius("10px 20px / 5px", (100.0, 100.0)), [10.0, 20.0, 10.0, 20.0]);
+        assert_eq!(radius("abc", (100.0, 100.0)), [0.0; 4]);
+
+        let corner = styles(&[("border-radius", "10px"), ("border-top-left-radius", "30px 5px")]);
+        assert_eq!(border_radius(&corner, 16.0, (100.0, 100.0)), [30.0, 10.0, 10.0, 10.0]);
+    }
+
+    #[test]
+    fn borders_apply_shorthands_in_order() {
+        let border = Border::from_styles(&styles(&[("border", "2px solid red")]), 16.0, Border::none());
+        assert_eq!(border.widths(), [2.0; 4]);
+        assert_eq!(border.stroke(Color32::BLACK), Some(Stroke::new(2.0, Color32::from_rgb(255, 0, 0))));
+
+        let left_none = styles(&[("border", "1px solid #000"), ("border-left", "none")]);
+        assert_eq!(Border::from_styles(&left_none, 16.0, Border::none()).widths(), [1.0, 1.0, 1.0, 0.0]);
+
+        let keywords = styles(&[("border-style", "solid"), ("border-width", "thin thick")]);
+        assert_eq!(Border::from_styles(&keywords, 16.0, Border::none()).widths(), [1.0, 5.0, 1.0, 5.0]);
+
+        let medium = Border::from_styles(&styles(&[("border-style", "solid")]), 16.0, Border::none());
+        assert_eq!(medium.widths(), [3.0; 4]);
+        assert_eq!(medium.stroke(Color32::BLUE), Some(Stroke::new(3.0, Color32::BLUE)));
+
+        let malformed = Border::from_styles(&styles(&[("border", "foo")]), 16.0, Border::uniform(1.0, Color32::RED));
+        assert_eq!(malformed.widths(), [0.0; 4]);
+        assert_eq!(malformed.stroke(Color32::BLACK), None);
+    }
+
+    #[test]
+    fn font_sizes_and_line_heights() {
+        assert_eq!(font_size(&styles(&[("font-size", "2em")]), 10.0), 32.0);
+        assert_eq!(font_size(&styles(&[("font-size", "large")]), 10.0), 18.0);
+        assert_eq!(font_size(&styles(&[("font-size", "-5px")]), 10.0), 10.0);
+        assert_eq!(font_size(&styles(&[]), 10.0), 10.0);
+        assert_eq!(line_height(&styles(&[("line-height", "1.5")]), 20.0), 30.0);
+        assert_eq!(line_height(&styles(&[("line-height", "24px")]), 20.0), 24.0);
+        assert_eq!(line_height(&styles(&[("line-height", "normal")]), 20.0), 24.0);
+    }
+}
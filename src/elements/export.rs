@@ -0,0 +1,158 @@
+// Контекст экспорта: общие для всех элементов параметры генерации HTML и CSS
+
+use std::collections::HashMap;
+use std::path::Path;
+
+// Параметры, которые элементы используют при формировании разметки и стилей
+#[derive(Clone, Debug)]
+pub struct ExportContext {
+    // Префикс CSS-классов элементов
+    pub class_prefix: String,
+    // Папка с изображениями относительно страницы
+    pub assets_dir: String,
+    // Имена копий файлов ресурсов в папке изображений по пути файла в проекте
+    pub assets: HashMap<String, String>,
+    // Файл иконки сайта в корне экспорта (None - иконка не задана или не найдена)
+    pub favicon: Option<String>,
+    // Файлы страниц сайта по идентификатору страницы
+    pub page_files: HashMap<String, String>,
+    // Страница, которая экспортируется сейчас
+    pub current_page_id: Option<String>,
+    // Файл, который записывается сейчас (страница или ее таблица стилей), от корня экспорта.
+    // Пути к ресурсам строятся относительно него
+    pub document: String,
+}
+
+impl Default for ExportContext {
+    fn default() -> Self {
+        Self {
+            class_prefix: "el-".to_string(),
+            assets_dir: "img".to_string(),
+            assets: HashMap::new(),
+            favicon: None,
+            page_files: HashMap::new(),
+            current_page_id: None,
+            document: String::new(),
+        }
+    }
+}
+
+impl ExportContext {
+    // Имя CSS-класса элемента
+    pub fn class_name(&self, element_id: &str) -> String {
+        format!("{}{}", self.class_prefix, element_id)
+    }
+
+    // Путь к копии файла ресурса (по пути в проекте) относительно записываемого файла
+    pub fn asset_url(&self, source: &str) -> String {
+        let file_name = self.assets.get(source).cloned().unwrap_or_else(|| {
+            Path::new(source).file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| source.to_string())
+        });
+        self.file_url(&format!("{}/{}", self.assets_dir, file_name))
+    }
+
+    // Путь к файлу экспорта (от корня экспорта) относительно записываемого файла
+    pub fn file_url(&self, path: &str) -> String {
+        relative_path(&self.document, path)
+    }
+
+    // Путь к странице сайта относительно текущей страницы
+    pub fn page_url(&self, page_id: &str) -> Option<String> {
+        let target = self.page_files.get(page_id)?;
+        let current = self.current_page_id.as_ref()
+            .and_then(|id| self.page_files.get(id))
+            .map(String::as_str)
+            .unwrap_or("");
+        Some(relative_path(current, target))
+    }
+}
+
+// Правило CSS: селектор и список объявлений
+#[derive(Clone, Debug, PartialEq)]
+pub struct CssRule {
+    pub selector: String,
+    pub declarations: Vec<(String, String)>,
+}
+
+impl CssRule {
+    pub fn new(selector: String) -> Self {
+        Self {
+            selector,
+            declarations: Vec::new(),
+        }
+    }
+
+    pub fn declare(&mut self, property: &str, value: String) {
+        self.declarations.push((property.to_string(), value));
+    }
+
+    // Убирает объявления свойства
+    pub fn remove(&mut self, property: &str) {
+        self.declarations.retain(|(p, _)| p != property);
+    }
+
+    // Текст правила для таблицы стилей
+    pub fn to_css(&self) -> String {
+        let mut css = format!("{} {{\n", self.selector);
+        for (property, value) in &self.declarations {
+            css.push_str(&format!("    {}: {};\n", property, value));
+        }
+        css.push_str("}\n");
+        css
+    }
+}
+
+// Относительный путь от файла `from` к файлу `to` (пути заданы от корня экспорта)
+fn relative_path(from: &str, to: &str) -> String {
+    let from_dirs: Vec<&str> = from.split('/').filter(|s| !s.is_empty()).collect();
+    let from_dirs = &from_dirs[..from_dirs.len().saturating_sub(1)];
+    let to_parts: Vec<&str> = to.split('/').filter(|s| !s.is_empty()).collect();
+
+    let common = from_dirs.iter()
+        .zip(to_parts.iter())
+        .take_while(|(a, b)| a == b)
+        .count()
+        .min(to_parts.len().saturating_sub(1));
+
+    let mut parts: Vec<&str> = vec![".."; from_dirs.len() - common];
+    parts.extend(&to_parts[common..]);
+    parts.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_paths_between_export_files() {
+        assert_eq!(relative_path("index.html", "about.html"), "about.html");
+        assert_eq!(relative_path("blog/post.html", "index.html"), "../index.html");
+        assert_eq!(relative_path("blog/post.html", "blog/other.html"), "other.html");
+        assert_eq!(relative_path("blog/post.html", "news/today.html"), "../news/today.html");
+        assert_eq!(relative_path("index.html", "blog/post.html"), "blog/post.html");
+        assert_eq!(relative_path("a/b/c.html", "a/d.html"), "../d.html");
+    }
+
+    #[test]
+    fn page_urls_are_relative_to_current_page() {
+        let mut ctx = ExportContext {
+            page_files: HashMap::from([
+                ("home".to_string(), "index.html".to_string()),
+                ("post".to_string(), "blog/post.html".to_string()),
+                ("other".to_string(), "blog/other.html".to_string()),
+                ("today".to_string(), "news/today.html".to_string()),
+            ]),
+            ..ExportContext::default()
+        };
+        assert_eq!(ctx.page_url("post").as_deref(), Some("blog/post.html"));
+        assert_eq!(ctx.page_url("missing"), None);
+
+        ctx.current_page_id = Some("post".to_string());
+        assert_eq!(ctx.page_url("home").as_deref(), Some("../index.html"));
+        assert_eq!(ctx.page_url("other").as_deref(), Some("other.html"));
+        assert_eq!(ctx.page_url("today").as_deref(), Some("../news/today.html"));
+        assert_eq!(ctx.page_url("post").as_deref(), Some("post.html"));
+    }
+}
@@ -0,0 +1,206 @@
+use crate::elements::canvas::CanvasContext;
+use crate::elements::css::Border;
+use crate::elements::export::{CssRule, ExportContext};
+use crate::elements::layout::is_flow;
+use crate::elements::properties::{self, PropertySection};
+use crate::elements::{ElementBase, ElementType, UIElement, children_css};
+use crate::utils::html_generator::escape_html;
+use egui::{Color32, Stroke};
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+
+// Метод отправки формы
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum FormMethod {
+    Get,
+    Post,
+}
+
+impl FormMethod {
+    pub fn html_value(&self) -> &'static str {
+        match self {
+            FormMethod::Get => "get",
+            FormMethod::Post => "post",
+        }
+    }
+}
+
+// Форма: контейнер для полей ввода с адресом и методом отправки
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Form {
+    pub base: ElementBase,
+    #[serde(with = "crate::elements::serde_elements")]
+    pub children: Vec<Box<dyn UIElement>>,
+    // Адрес обработчика формы
+    pub action: String,
+    pub method: FormMethod,
+}
+
+impl Default for Form {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Form {
+    pub fn new() -> Self {
+        let mut base = ElementBase::new(ElementType::Form);
+        base.size = (320.0, 260.0);
+
+        // Устанавливаем стили по умолчанию
+        base.styles
+            .insert("background-color".to_string(), "#FFFFFF".to_string());
+        base.styles
+            .insert("border-width".to_string(), "1px".to_string());
+        base.styles
+            .insert("border-style".to_string(), "solid".to_string());
+        base.styles
+            .insert("border-color".to_string(), "#DDDDDD".to_string());
+
+        Self {
+            base,
+            children: Vec::new(),
+            action: String::new(),
+            method: FormMethod::Post,
+        }
+    }
+}
+
+impl UIElement for Form {
+    fn base(&self) -> &ElementBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut ElementBase {
+        &mut self.base
+    }
+
+    fn get_id(&self) -> &str {
+        &self.base.id
+    }
+
+    fn get_element_type(&self) -> &ElementType {
+        &self.base.element_type
+    }
+
+    fn get_position(&self) -> (f32, f32) {
+        self.base.position
+    }
+
+    fn set_position(&mut self, position: (f32, f32)) {
+        self.base.position = position;
+    }
+
+    fn get_size(&self) -> (f32, f32) {
+        self.base.size
+    }
+
+    fn set_size(&mut self, size: (f32, f32)) {
+        self.base.size = size;
+    }
+
+    fn clone_box(&self) -> Box<dyn UIElement> {
+        Box::new(self.clone())
+    }
+
+    fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(self)
+    }
+
+    fn to_html(&self, ctx: &ExportContext) -> String {
+        let action = if self.action.trim().is_empty() {
+            String::new()
+        } else {
+            format!(" action=\"{}\"", escape_html(self.action.trim()))
+        };
+
+        let mut html = format!(
+            "<form{}{} method=\"{}\">\n",
+            self.base.attributes_html(ctx),
+            action,
+            self.method.html_value()
+        );
+        for child in &self.children {
+            html.push_str(&child.to_html(ctx));
+            html.push('\n');
+        }
+        html.push_str("</form>");
+        html
+    }
+
+    fn to_css(&self, ctx: &ExportContext) -> Vec<CssRule> {
+        let mut rule = self.base.css_rule(ctx);
+        rule.declare("box-sizing", "border-box".to_string());
+
+        let mut rules = vec![rule];
+        rules.extend(children_css(
+            &self.children,
+            is_flow(&self.base.styles),
+            ctx,
+        ));
+        rules
+    }
+
+    fn children(&self) -> Option<&[Box<dyn UIElement>]> {
+        Some(&self.children)
+    }
+
+    fn children_mut(&mut self) -> Option<&mut Vec<Box<dyn UIElement>>> {
+        Some(&mut self.children)
+    }
+
+    fn assets(&self) -> Vec<String> {
+        let mut assets = self.base.style_assets();
+        assets.extend(self.children.iter().flat_map(|child| child.assets()));
+        assets
+    }
+
+    fn property_schema(&self) -> Vec<PropertySection> {
+        vec![
+            properties::background_section(),
+            properties::border_section(),
+            properties::spacing_section(),
+            properties::effects_section(),
+            properties::attributes_section(),
+        ]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn contains_point(&self, point: (f32, f32)) -> bool {
+        self.base.contains_point(point)
+    }
+
+    fn render(&self, painter: &egui::Painter, canvas: &CanvasContext) {
+        let element_rect = canvas.rect(&self.base);
+        let selected = canvas.is_selected(&self.base.id);
+
+        canvas.paint_background(painter, &self.base, Color32::TRANSPARENT);
+
+        // Поля формы обрезаются по ее границам
+        let clipped = painter.with_clip_rect(element_rect.intersect(painter.clip_rect()));
+        let child_canvas = canvas.child(self.base.position);
+        child_canvas.render_elements(&clipped, &self.children);
+
+        canvas.paint_border(painter, &self.base, Border::none());
+
+        // Подпись с методом отправки в углу формы
+        painter.text(
+            element_rect.right_top() + egui::vec2(-4.0, 2.0),
+            egui::Align2::RIGHT_TOP,
+            format!("form {}", self.method.html_value().to_uppercase()),
+            egui::FontId::proportional(10.0),
+            Color32::GRAY,
+        );
+
+        if selected {
+            painter.rect_stroke(element_rect, 0.0, Stroke::new(1.0, Color32::BLUE));
+        }
+    }
+}
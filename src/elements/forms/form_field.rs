@@ -0,0 +1,411 @@
+use serde::{Serialize, Deserialize};
+use egui::{Pos2, Rect, Vec2, Color32, Stroke};
+use crate::elements::{ElementBase, ElementType, UIElement};
+use crate::elements::canvas::CanvasContext;
+use crate::elements::export::{CssRule, ExportContext};
+use crate::elements::properties::{self, PropertySection};
+use crate::utils::html_generator::escape_html;
+use std::any::Any;
+
+// Вид поля формы
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum FieldKind {
+    Text,
+    Email,
+    Number,
+    Password,
+    Textarea,
+    Select,
+    Checkbox,
+    Radio,
+    Submit,
+}
+
+impl FieldKind {
+    pub const ALL: [FieldKind; 9] = [
+        FieldKind::Text, FieldKind::Email, FieldKind::Number, FieldKind::Password, FieldKind::Textarea,
+        FieldKind::Select, FieldKind::Checkbox, FieldKind::Radio, FieldKind::Submit,
+    ];
+    
+    // Название для интерфейса
+    pub fn label(&self) -> &'static str {
+        match self {
+            FieldKind::Text => "Текст",
+            FieldKind::Email => "Email",
+            FieldKind::Number => "Число",
+            FieldKind::Password => "Пароль",
+            FieldKind::Textarea => "Многострочный текст",
+            FieldKind::Select => "Выпадающий список",
+            FieldKind::Checkbox => "Флажок",
+            FieldKind::Radio => "Переключатели",
+            FieldKind::Submit => "Кнопка отправки",
+        }
+    }
+    
+    // Значение атрибута type для <input>
+    fn input_type(&self) -> &'static str {
+        match self {
+            FieldKind::Email => "email",
+            FieldKind::Number => "number",
+            FieldKind::Password => "password",
+            _ => "text",
+        }
+    }
+    
+    // Используются ли у поля варианты выбора
+    pub fn has_options(&self) -> bool {
+        matches!(self, FieldKind::Select | FieldKind::Radio)
+    }
+    
+    // Поддерживает ли поле подсказку и шаблон ввода
+    pub fn is_text_input(&self) -> bool {
+        matches!(self, FieldKind::Text | FieldKind::Email | FieldKind::Number | FieldKind::Password | FieldKind::Textarea)
+    }
+    
+    // Размер элемента по умолчанию
+    fn default_size(&self) -> (f32, f32) {
+        match self {
+            FieldKind::Textarea => (260.0, 110.0),
+            FieldKind::Checkbox => (260.0, 24.0),
+            FieldKind::Radio => (260.0, 90.0),
+            FieldKind::Submit => (140.0, 40.0),
+            _ => (260.0, 56.0),
+        }
+    }
+}
+
+// Поле формы: ввод текста, список, флажок, переключатели или кнопка отправки
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FormField {
+    pub base: ElementBase,
+    pub kind: FieldKind,
+    // Подпись поля (для кнопки отправки - ее текст)
+    pub label: String,
+    // Имя поля при отправке формы
+    pub name: String,
+    pub placeholder: String,
+    pub required: bool,
+    // Регулярное выражение для проверки ввода
+    pub pattern: String,
+    // Варианты для списка и переключателей
+    pub options: Vec<String>,
+    // Состояние флажка по умолчанию
+    pub checked: bool,
+}
+
+impl Default for FormField {
+    fn default() -> Self {
+        Self::new(FieldKind::Text)
+    }
+}
+
+impl FormField {
+    pub fn new(kind: FieldKind) -> Self {
+        let mut base = ElementBase::new(ElementType::Input);
+        base.size = kind.default_size();
+        
+        let mut field = Self {
+            base,
+            kind,
+            label: String::new(),
+            name: String::new(),
+            placeholder: String::new(),
+            required: false,
+            pattern: String::new(),
+            options: Vec::new(),
+            checked: false,
+        };
+        field.set_kind(kind);
+        field
+    }
+    
+    // Меняет вид поля, подставляя подходящие значения по умолчанию.
+    // Размер, заданный пользователем, сохраняется: размер вида задается только при создании
+    pub fn set_kind(&mut self, kind: FieldKind) {
+        self.kind = kind;
+        
+        if self.label.is_empty() || FieldKind::ALL.iter().any(|k| k.label() == self.label) || self.label == "Отправить" {
+            self.label = match kind {
+                FieldKind::Submit => "Отправить".to_string(),
+                _ => kind.label().to_string(),
+            };
+        }
+        if self.name.is_empty() {
+            self.name = format!("field_{}", self.base.id.chars().take(8).collect::<String>());
+        }
+        if kind.has_options() && self.options.is_empty() {
+            self.options = vec!["Вариант 1".to_string(), "Вариант 2".to_string()];
+        }
+    }
+    
+    // Общие атрибуты name/required/placeholder/pattern
+    fn input_attributes(&self) -> String {
+        let mut attributes = format!(" name=\"{}\"", escape_html(&self.name));
+        if self.required {
+            attributes.push_str(" required");
+        }
+        if self.kind.is_text_input() {
+            if !self.placeholder.is_empty() {
+                attributes.push_str(&format!(" placeholder=\"{}\"", escape_html(&self.placeholder)));
+            }
+            if !self.pattern.is_empty() && self.kind != FieldKind::Textarea {
+                attributes.push_str(&format!(" pattern=\"{}\"", escape_html(&self.pattern)));
+            }
+        }
+        attributes
+    }
+    
+    // Рамка поля ввода на холсте
+    // scale - масштаб холста
+    fn render_input_box(&self, painter: &egui::Painter, rect: Rect, scale: f32, text: &str, is_placeholder: bool) {
+        painter.rect_filled(rect, 2.0 * scale, Color32::WHITE);
+        painter.rect_stroke(rect, 2.0 * scale, Stroke::new(scale, Color32::from_gray(170)));
+        let color = if is_placeholder { Color32::from_gray(150) } else { Color32::from_gray(40) };
+        painter.text(
+            rect.left_top() + Vec2::splat(6.0 * scale),
+            egui::Align2::LEFT_TOP,
+            text,
+            egui::FontId::proportional(13.0 * scale),
+            color
+        );
+    }
+}
+
+impl UIElement for FormField {
+    fn base(&self) -> &ElementBase {
+        &self.base
+    }
+    
+    fn base_mut(&mut self) -> &mut ElementBase {
+        &mut self.base
+    }
+    
+    fn get_id(&self) -> &str {
+        &self.base.id
+    }
+    
+    fn get_element_type(&self) -> &ElementType {
+        &self.base.element_type
+    }
+    
+    fn get_position(&self) -> (f32, f32) {
+        self.base.position
+    }
+    
+    fn set_position(&mut self, position: (f32, f32)) {
+        self.base.position = position;
+    }
+    
+    fn get_size(&self) -> (f32, f32) {
+        self.base.size
+    }
+    
+    fn set_size(&mut self, size: (f32, f32)) {
+        self.base.size = size;
+    }
+    
+    fn clone_box(&self) -> Box<dyn UIElement> {
+        Box::new(self.clone())
+    }
+    
+    fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(self)
+    }
+    
+    // Поле экспортируется вместе с подписью: <label> оборачивает элемент управления
+    fn to_html(&self, ctx: &ExportContext) -> String {
+        let attributes = self.base.attributes_html(ctx);
+        let label = escape_html(&self.label);
+        
+        match self.kind {
+            FieldKind::Textarea => format!(
+                "<label{}><span>{}</span><textarea{}></textarea></label>",
+                attributes, label, self.input_attributes()
+            ),
+            FieldKind::Select => {
+                let options: String = self.options.iter()
+                    .map(|option| format!("<option value=\"{0}\">{0}</option>", escape_html(option)))
+                    .collect();
+                format!(
+                    "<label{}><span>{}</span><select{}>{}</select></label>",
+                    attributes, label, self.input_attributes(), options
+                )
+            },
+            FieldKind::Checkbox => format!(
+                "<label{}><input type=\"checkbox\" value=\"1\"{}{}> <span>{}</span></label>",
+                attributes,
+                self.input_attributes(),
+                if self.checked { " checked" } else { "" },
+                label
+            ),
+            FieldKind::Radio => {
+                let required = if self.required { " required" } else { "" };
+                let options: String = self.options.iter()
+                    .map(|option| format!(
+                        "<label><input type=\"radio\" name=\"{}\" value=\"{}\"{}> {}</label>",
+                        escape_html(&self.name), escape_html(option), required, escape_html(option)
+                    ))
+                    .collect();
+                format!("<fieldset{}><legend>{}</legend>{}</fieldset>", attributes, label, options)
+            },
+            FieldKind::Submit => format!("<button type=\"submit\"{}>{}</button>", attributes, label),
+            _ => format!(
+                "<label{}><span>{}</span><input type=\"{}\"{}></label>",
+                attributes, label, self.kind.input_type(), self.input_attributes()
+            ),
+        }
+    }
+    
+    fn to_css(&self, ctx: &ExportContext) -> Vec<CssRule> {
+        let class = ctx.class_name(&self.base.id);
+        let mut rule = self.base.css_rule(ctx);
+        rule.declare("box-sizing", "border-box".to_string());
+        rule.declare("margin", "0".to_string());
+        
+        match self.kind {
+            FieldKind::Checkbox => {
+                rule.declare("display", "flex".to_string());
+                rule.declare("align-items", "center".to_string());
+                rule.declare("gap", "6px".to_string());
+                vec![rule]
+            },
+            FieldKind::Radio => {
+                rule.declare("display", "flex".to_string());
+                rule.declare("flex-direction", "column".to_string());
+                rule.declare("gap", "4px".to_string());
+                rule.declare("border", "none".to_string());
+                rule.declare("padding", "0".to_string());
+                vec![rule]
+            },
+            FieldKind::Submit => vec![rule],
+            _ => {
+                rule.declare("display", "flex".to_string());
+                rule.declare("flex-direction", "column".to_string());
+                rule.declare("gap", "4px".to_string());
+                
+                // Элемент управления занимает все место под подписью
+                let mut control = CssRule::new(format!(".{0} input, .{0} select, .{0} textarea", class));
+                control.declare("flex", "1".to_string());
+                control.declare("width", "100%".to_string());
+                control.declare("box-sizing", "border-box".to_string());
+                vec![rule, control]
+            }
+        }
+    }
+    
+    fn property_schema(&self) -> Vec<PropertySection> {
+        // Поле рисуется браузером; атрибуты относятся к подписи, в которую оно вложено
+        vec![properties::attributes_section()]
+    }
+    
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    
+    fn contains_point(&self, point: (f32, f32)) -> bool {
+        self.base.contains_point(point)
+    }
+    
+    fn render(&self, painter: &egui::Painter, canvas: &CanvasContext) {
+        let element_rect = canvas.rect(&self.base);
+        let selected = canvas.is_selected(&self.base.id);
+        let scale = canvas.scale;
+        let font = egui::FontId::proportional(13.0 * scale);
+        let text_color = Color32::from_gray(40);
+        let label_height = 18.0 * scale;
+        
+        match self.kind {
+            FieldKind::Checkbox => {
+                let box_rect = Rect::from_min_size(
+                    Pos2::new(element_rect.left(), element_rect.center().y - 7.0 * scale),
+                    Vec2::splat(14.0 * scale)
+                );
+                painter.rect_filled(box_rect, 2.0 * scale, Color32::WHITE);
+                painter.rect_stroke(box_rect, 2.0 * scale, Stroke::new(scale, Color32::from_gray(120)));
+                if self.checked {
+                    painter.line_segment(
+                        [box_rect.left_center() + Vec2::new(3.0, 0.0) * scale, box_rect.center_bottom() + Vec2::new(0.0, -3.0) * scale],
+                        Stroke::new(2.0 * scale, text_color)
+                    );
+                    painter.line_segment(
+                        [box_rect.center_bottom() + Vec2::new(0.0, -3.0) * scale, box_rect.right_top() + Vec2::new(-2.0, 3.0) * scale],
+                        Stroke::new(2.0 * scale, text_color)
+                    );
+                }
+                painter.text(
+                    Pos2::new(box_rect.right() + 6.0 * scale, element_rect.center().y),
+                    egui::Align2::LEFT_CENTER,
+                    &self.label,
+                    font,
+                    text_color
+                );
+            },
+            FieldKind::Radio => {
+                painter.text(element_rect.left_top(), egui::Align2::LEFT_TOP, &self.label, font.clone(), text_color);
+                for (index, option) in self.options.iter().enumerate() {
+                    let center = Pos2::new(
+                        element_rect.left() + 7.0 * scale,
+                        element_rect.top() + label_height + (10.0 + index as f32 * 22.0) * scale
+                    );
+                    painter.circle_filled(center, 6.0 * scale, Color32::WHITE);
+                    painter.circle_stroke(center, 6.0 * scale, Stroke::new(scale, Color32::from_gray(120)));
+                    painter.text(
+                        center + Vec2::new(12.0 * scale, 0.0),
+                        egui::Align2::LEFT_CENTER,
+                        option,
+                        font.clone(),
+                        text_color
+                    );
+                }
+            },
+            FieldKind::Submit => {
+                painter.rect_filled(element_rect, 4.0 * scale, Color32::from_rgb(33, 150, 243));
+                painter.text(element_rect.center(), egui::Align2::CENTER_CENTER, &self.label, font, Color32::WHITE);
+            },
+            _ => {
+                painter.text(element_rect.left_top(), egui::Align2::LEFT_TOP, &self.label, font, text_color);
+                let input_rect = Rect::from_min_max(
+                    Pos2::new(element_rect.left(), element_rect.top() + label_height),
+                    element_rect.max
+                );
+                
+                if self.kind == FieldKind::Select {
+                    let text = self.options.first().cloned().unwrap_or_default();
+                    self.render_input_box(painter, input_rect, scale, &text, false);
+                    painter.text(
+                        Pos2::new(input_rect.right() - 8.0 * scale, input_rect.center().y),
+                        egui::Align2::RIGHT_CENTER,
+                        "▼",
+                        egui::FontId::proportional(10.0 * scale),
+                        text_color
+                    );
+                } else {
+                    let text = if self.kind == FieldKind::Password && !self.placeholder.is_empty() {
+                        "••••••".to_string()
+                    } else {
+                        self.placeholder.clone()
+                    };
+                    self.render_input_box(painter, input_rect, scale, &text, true);
+                }
+            }
+        }
+        
+        if self.required && self.kind != FieldKind::Submit {
+            painter.text(
+                element_rect.right_top(),
+                egui::Align2::RIGHT_TOP,
+                "*",
+                egui::FontId::proportional(13.0 * scale),
+                Color32::RED
+            );
+        }
+        
+        if selected {
+            painter.rect_stroke(element_rect, 0.0, Stroke::new(1.0, Color32::BLUE));
+        }
+    }
+}
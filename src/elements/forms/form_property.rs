@@ -0,0 +1,162 @@
+use egui::Ui;
+use crate::elements::forms::form::{Form, FormMethod};
+use crate::elements::forms::form_field::{FieldKind, FormField};
+use crate::elements::UIElement;
+use crate::elements::containers::layout_property::show_layout;
+
+// Структура для работы со свойствами формы.
+// Фон, рамка и отступы редактируются в общем инспекторе свойств
+#[derive(Default)]
+pub struct FormProperty;
+
+impl FormProperty {
+    pub fn new() -> Self {
+        Self
+    }
+    
+    // Метод для отображения и редактирования свойств формы
+    pub fn show(&mut self, ui: &mut Ui, element: &mut Box<dyn UIElement>) -> bool {
+        let Some(form) = element.as_any_mut().downcast_mut::<Form>() else {
+            return false;
+        };
+        
+        let mut changed = false;
+        
+        ui.label(format!("Полей в форме: {}", form.children.len()));
+        ui.label("Перетащите поля формы внутрь, чтобы они отправлялись вместе с ней.");
+        
+        ui.separator();
+        ui.heading("Отправка");
+        
+        // Адрес обработчика формы
+        ui.label("Адрес (action):");
+        if ui.text_edit_singleline(&mut form.action).changed() {
+            changed = true;
+        }
+        
+        // Метод отправки
+        ui.label("Метод:");
+        egui::ComboBox::from_id_source("form_method")
+            .selected_text(form.method.html_value())
+            .show_ui(ui, |ui| {
+                for method in [FormMethod::Get, FormMethod::Post] {
+                    if ui.selectable_value(&mut form.method, method, method.html_value()).changed() {
+                        changed = true;
+                    }
+                }
+            });
+        
+        ui.separator();
+        ui.heading("Раскладка");
+        
+        if show_layout(ui, "form", &mut form.base.styles) {
+            changed = true;
+        }
+        
+        changed
+    }
+}
+
+// Структура для работы со свойствами поля формы
+#[derive(Default)]
+pub struct FormFieldProperty {
+    // Текст нового варианта выбора
+    new_option: String,
+}
+
+impl FormFieldProperty {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    // Метод для отображения и редактирования свойств поля формы
+    pub fn show(&mut self, ui: &mut Ui, element: &mut Box<dyn UIElement>) -> bool {
+        let Some(field) = element.as_any_mut().downcast_mut::<FormField>() else {
+            return false;
+        };
+        
+        let mut changed = false;
+        
+        // Вид поля
+        ui.label("Вид поля:");
+        let mut kind = field.kind;
+        egui::ComboBox::from_id_source("form_field_kind")
+            .selected_text(kind.label())
+            .show_ui(ui, |ui| {
+                for option in FieldKind::ALL {
+                    ui.selectable_value(&mut kind, option, option.label());
+                }
+            });
+        if kind != field.kind {
+            field.set_kind(kind);
+            changed = true;
+        }
+        
+        ui.label(if field.kind == FieldKind::Submit { "Текст кнопки:" } else { "Подпись:" });
+        if ui.text_edit_singleline(&mut field.label).changed() {
+            changed = true;
+        }
+        
+        if field.kind != FieldKind::Submit {
+            ui.label("Имя поля (name):");
+            if ui.text_edit_singleline(&mut field.name).changed() {
+                changed = true;
+            }
+            
+            if ui.checkbox(&mut field.required, "Обязательное поле").changed() {
+                changed = true;
+            }
+        }
+        
+        if field.kind.is_text_input() {
+            ui.label("Подсказка (placeholder):");
+            if ui.text_edit_singleline(&mut field.placeholder).changed() {
+                changed = true;
+            }
+            
+            if field.kind != FieldKind::Textarea {
+                ui.label("Шаблон ввода (pattern):");
+                if ui.text_edit_singleline(&mut field.pattern).changed() {
+                    changed = true;
+                }
+            }
+        }
+        
+        if field.kind == FieldKind::Checkbox
+            && ui.checkbox(&mut field.checked, "Отмечен по умолчанию").changed() {
+            changed = true;
+        }
+        
+        if field.kind.has_options() {
+            ui.separator();
+            ui.heading("Варианты");
+            
+            let mut remove_index = None;
+            for (index, option) in field.options.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.text_edit_singleline(option).changed() {
+                        changed = true;
+                    }
+                    if ui.small_button("✕").clicked() {
+                        remove_index = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = remove_index {
+                field.options.remove(index);
+                changed = true;
+            }
+            
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut self.new_option);
+                if ui.button("Добавить").clicked() && !self.new_option.trim().is_empty() {
+                    field.options.push(self.new_option.trim().to_string());
+                    self.new_option.clear();
+                    changed = true;
+                }
+            });
+        }
+        
+        changed
+    }
+}
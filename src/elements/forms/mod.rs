@@ -0,0 +1,3 @@
+pub mod form;
+pub mod form_field;
+pub mod form_property;
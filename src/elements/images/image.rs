@@ -0,0 +1,281 @@
+use serde::{Serialize, Deserialize};
+use egui::{Rect, Rounding, Vec2, Color32, Stroke};
+use egui::epaint::RectShape;
+use crate::elements::{ElementBase, ElementType, UIElement};
+use crate::elements::canvas::CanvasContext;
+use crate::elements::css::Border;
+use crate::elements::export::{CssRule, ExportContext};
+use crate::elements::properties::{self, PropertySection};
+use crate::utils::html_generator::escape_html;
+use std::any::Any;
+use std::path::Path;
+
+// Поддерживаемые форматы изображений
+pub const IMAGE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "svg", "webp"];
+
+// Способ вписывания изображения в рамку элемента (CSS object-fit)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ObjectFit {
+    Fill,
+    Contain,
+    Cover,
+    None,
+    ScaleDown,
+}
+
+impl ObjectFit {
+    pub const ALL: [ObjectFit; 5] = [
+        ObjectFit::Fill, ObjectFit::Contain, ObjectFit::Cover, ObjectFit::None, ObjectFit::ScaleDown,
+    ];
+    
+    // Значение свойства CSS
+    pub fn css_value(&self) -> &'static str {
+        match self {
+            ObjectFit::Fill => "fill",
+            ObjectFit::Contain => "contain",
+            ObjectFit::Cover => "cover",
+            ObjectFit::None => "none",
+            ObjectFit::ScaleDown => "scale-down",
+        }
+    }
+    
+    // Название для интерфейса
+    pub fn label(&self) -> &'static str {
+        match self {
+            ObjectFit::Fill => "Растянуть",
+            ObjectFit::Contain => "Вписать",
+            ObjectFit::Cover => "Заполнить",
+            ObjectFit::None => "Исходный размер",
+            ObjectFit::ScaleDown => "Уменьшить при необходимости",
+        }
+    }
+    
+    // Масштаб изображения размера `image` в рамке размера `frame`
+    fn scale(&self, image: Vec2, frame: Vec2) -> Vec2 {
+        let contain = (frame.x / image.x).min(frame.y / image.y);
+        match self {
+            ObjectFit::Fill => Vec2::new(frame.x / image.x, frame.y / image.y),
+            ObjectFit::Contain => Vec2::splat(contain),
+            ObjectFit::Cover => Vec2::splat((frame.x / image.x).max(frame.y / image.y)),
+            ObjectFit::None => Vec2::splat(1.0),
+            ObjectFit::ScaleDown => Vec2::splat(contain.min(1.0)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Image {
+    pub base: ElementBase,
+    // Путь к файлу изображения от папки проекта (абсолютный, пока проект не сохранен)
+    pub source: String,
+    pub alt: String,
+    pub object_fit: ObjectFit,
+    // Положение изображения внутри рамки в процентах (CSS object-position)
+    pub object_position: (f32, f32),
+}
+
+impl Default for Image {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Image {
+    pub fn new() -> Self {
+        let mut base = ElementBase::new(ElementType::Image);
+        base.size = (200.0, 150.0);
+        
+        Self {
+            base,
+            source: String::new(),
+            alt: String::new(),
+            object_fit: ObjectFit::Cover,
+            object_position: (50.0, 50.0),
+        }
+    }
+    
+    // Имя файла изображения без пути
+    pub fn file_name(&self) -> Option<String> {
+        Path::new(&self.source).file_name().map(|name| name.to_string_lossy().to_string())
+    }
+    
+    // Прямоугольник изображения с учетом object-fit и object-position
+    fn image_rect(&self, frame: Rect, image_size: Vec2) -> Rect {
+        if image_size.x <= 0.0 || image_size.y <= 0.0 {
+            return frame;
+        }
+        
+        let size = image_size * self.object_fit.scale(image_size, frame.size());
+        let free = frame.size() - size;
+        let min = frame.min + Vec2::new(
+            free.x * self.object_position.0 / 100.0,
+            free.y * self.object_position.1 / 100.0,
+        );
+        Rect::from_min_size(min, size)
+    }
+    
+    // Заглушка, пока изображение не выбрано или не загружено
+    fn render_placeholder(&self, painter: &egui::Painter, canvas: &CanvasContext, message: &str) {
+        let rect = canvas.rect(&self.base);
+        painter.rect_filled(rect, canvas.rounding(&self.base), Color32::from_gray(235));
+        painter.line_segment([rect.left_top(), rect.right_bottom()], Stroke::new(1.0, Color32::from_gray(200)));
+        painter.line_segment([rect.right_top(), rect.left_bottom()], Stroke::new(1.0, Color32::from_gray(200)));
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            message,
+            egui::FontId::default(),
+            Color32::DARK_GRAY
+        );
+    }
+}
+
+impl UIElement for Image {
+    fn base(&self) -> &ElementBase {
+        &self.base
+    }
+    
+    fn base_mut(&mut self) -> &mut ElementBase {
+        &mut self.base
+    }
+    
+    fn get_id(&self) -> &str {
+        &self.base.id
+    }
+    
+    fn get_element_type(&self) -> &ElementType {
+        &self.base.element_type
+    }
+    
+    fn get_position(&self) -> (f32, f32) {
+        self.base.position
+    }
+    
+    fn set_position(&mut self, position: (f32, f32)) {
+        self.base.position = position;
+    }
+    
+    fn get_size(&self) -> (f32, f32) {
+        self.base.size
+    }
+    
+    fn set_size(&mut self, size: (f32, f32)) {
+        self.base.size = size;
+    }
+    
+    fn clone_box(&self) -> Box<dyn UIElement> {
+        Box::new(self.clone())
+    }
+    
+    fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(self)
+    }
+    
+    // Путь src указывает на копию файла в папке изображений экспорта
+    fn to_html(&self, ctx: &ExportContext) -> String {
+        let src = if self.source.is_empty() { String::new() } else { ctx.asset_url(&self.source) };
+        format!(
+            "<img{} src=\"{}\" alt=\"{}\">",
+            self.base.attributes_html(ctx),
+            escape_html(&src),
+            escape_html(&self.alt)
+        )
+    }
+    
+    fn to_css(&self, ctx: &ExportContext) -> Vec<CssRule> {
+        let mut rule = self.base.css_rule(ctx);
+        rule.declare("object-fit", self.object_fit.css_value().to_string());
+        rule.declare("object-position", format!("{}% {}%", self.object_position.0, self.object_position.1));
+        vec![rule]
+    }
+    
+    fn assets(&self) -> Vec<String> {
+        let mut assets = self.base.style_assets();
+        if !self.source.is_empty() {
+            assets.push(self.source.clone());
+        }
+        assets
+    }
+    
+    fn map_assets(&mut self, map: &mut dyn FnMut(&str) -> String) {
+        if !self.source.is_empty() {
+            self.source = map(&self.source);
+        }
+        self.base.map_style_assets(map);
+    }
+    
+    fn property_schema(&self) -> Vec<PropertySection> {
+        vec![
+            properties::border_section(),
+            properties::effects_section(),
+            properties::image_attributes_section(),
+        ]
+    }
+    
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    
+    fn contains_point(&self, point: (f32, f32)) -> bool {
+        self.base.contains_point(point)
+    }
+    
+    fn render(&self, painter: &egui::Painter, canvas: &CanvasContext) {
+        let element_rect = canvas.rect(&self.base);
+        let selected = canvas.is_selected(&self.base.id);
+        
+        canvas.paint_background(painter, &self.base, Color32::TRANSPARENT);
+        
+        if self.source.is_empty() {
+            self.render_placeholder(painter, canvas, "Изображение");
+        } else {
+            // Текстура загружается загрузчиками egui_extras и кэшируется контекстом
+            let uri = format!("file://{}", canvas.asset_path(&self.source).display());
+            let poll = painter.ctx().try_load_texture(
+                &uri,
+                egui::TextureOptions::LINEAR,
+                egui::SizeHint::default()
+            );
+            
+            match poll {
+                Ok(egui::load::TexturePoll::Ready { texture }) => {
+                    // Видимая часть изображения рисуется текстурой со скругленными углами элемента
+                    let image_rect = self.image_rect(element_rect, texture.size);
+                    let visible = image_rect.intersect(element_rect);
+                    if visible.is_positive() {
+                        let uv = Rect::from_min_max(
+                            ((visible.min - image_rect.min) / image_rect.size()).to_pos2(),
+                            ((visible.max - image_rect.min) / image_rect.size()).to_pos2(),
+                        );
+                        // Скругления обрезают углы элемента; изображение, не доходящее до них, не скругляется
+                        let rounding = if visible == element_rect {
+                            canvas.rounding(&self.base)
+                        } else {
+                            Rounding::ZERO
+                        };
+                        let mut shape = RectShape::filled(visible, rounding, Color32::WHITE);
+                        shape.fill_texture_id = texture.id;
+                        shape.uv = uv;
+                        painter.add(shape);
+                    }
+                },
+                Ok(egui::load::TexturePoll::Pending { .. }) => {
+                    self.render_placeholder(painter, canvas, "Загрузка...");
+                },
+                Err(_) => {
+                    self.render_placeholder(painter, canvas, "Файл не найден");
+                }
+            }
+        }
+        
+        canvas.paint_border(painter, &self.base, Border::none());
+        
+        if selected {
+            painter.rect_stroke(element_rect, 0.0, Stroke::new(1.0, Color32::BLUE));
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use egui::Ui;
+use std::path::Path;
+use crate::app::project;
+use crate::elements::images::image::{Image, ObjectFit};
+use crate::elements::UIElement;
+
+// Структура для работы со свойствами изображения
+pub struct ImageProperty {
+    // Путь к файлу для импорта
+    pub import_path: String,
+    // Ошибка последнего импорта
+    pub import_error: Option<String>,
+}
+
+impl Default for ImageProperty {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImageProperty {
+    pub fn new() -> Self {
+        Self {
+            import_path: String::new(),
+            import_error: None,
+        }
+    }
+    
+    // Метод для отображения и редактирования свойств изображения
+    pub fn show(&mut self, ui: &mut Ui, element: &mut Box<dyn UIElement>, project_path: Option<&Path>) -> bool {
+        let Some(image) = element.as_any_mut().downcast_mut::<Image>() else {
+            return false;
+        };
+        
+        let mut changed = false;
+        
+        // Файл изображения
+        ui.label("Файл:");
+        ui.label(image.file_name().unwrap_or_else(|| "не выбран".to_string()));
+        
+        ui.label("Импортировать файл (PNG, JPEG, SVG, WebP):");
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut self.import_path);
+            if ui.button("Импорт").clicked() {
+                match project::import_asset(project_path, Path::new(self.import_path.trim())) {
+                    Ok(path) => {
+                        image.source = path;
+                        self.import_error = None;
+                        changed = true;
+                    }
+                    Err(e) => {
+                        self.import_error = Some(e.to_string());
+                    }
+                }
+            }
+        });
+        
+        if let Some(error) = &self.import_error {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+        
+        // Альтернативный текст
+        ui.label("Альтернативный текст:");
+        changed |= ui.text_edit_singleline(&mut image.alt).changed();
+        
+        ui.separator();
+        ui.heading("Вписывание");
+        
+        // object-fit
+        ui.label("Режим:");
+        let mut object_fit = image.object_fit;
+        egui::ComboBox::from_id_source("image_object_fit")
+            .selected_text(object_fit.label())
+            .show_ui(ui, |ui| {
+                for option in ObjectFit::ALL {
+                    ui.selectable_value(&mut object_fit, option, option.label());
+                }
+            });
+        if object_fit != image.object_fit {
+            image.object_fit = object_fit;
+            changed = true;
+        }
+        
+        // object-position
+        ui.label("Положение по горизонтали (%):");
+        changed |= ui.add(egui::Slider::new(&mut image.object_position.0, 0.0..=100.0).step_by(1.0)).changed();
+        ui.label("Положение по вертикали (%):");
+        changed |= ui.add(egui::Slider::new(&mut image.object_position.1, 0.0..=100.0).step_by(1.0)).changed();
+        
+        changed
+    }
+}
@@ -0,0 +1,752 @@
+// Раскладка дочерних элементов по правилам CSS flexbox и grid.
+// Свойства раскладки хранятся в стилях контейнера (display, flex-direction, gap,
+// grid-template-columns и т.д.), поэтому экспортируются как есть и могут отличаться
+// на разных ширинах экрана. Редактор вычисляет положения так же, как браузер:
+// размеры элементов заданы явно, элементы не растягиваются и не сжимаются.
+
+use std::collections::HashMap;
+
+use egui::{Pos2, Rect, Vec2};
+
+use crate::elements::UIElement;
+use crate::elements::css::{self, Border, Length};
+use crate::elements::export::CssRule;
+
+// Выравнивание элемента внутри строки flex или ячейки сетки
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Align {
+    Start,
+    Center,
+    End,
+}
+
+impl Align {
+    fn parse(value: Option<&str>) -> Self {
+        match value.map(str::trim) {
+            Some("center") => Align::Center,
+            Some("flex-end" | "end" | "self-end" | "right") => Align::End,
+            // stretch и baseline при явном размере элемента дают начало
+            _ => Align::Start,
+        }
+    }
+
+    // Смещение элемента размером size в области размером space
+    fn offset(self, space: f32, size: f32) -> f32 {
+        match self {
+            Align::Start => 0.0,
+            Align::Center => (space - size) / 2.0,
+            Align::End => space - size,
+        }
+    }
+}
+
+// Распределение свободного места между элементами или строками
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Distribute {
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+    // Строки делят свободное место поровну (align-content: normal)
+    Stretch,
+}
+
+impl Distribute {
+    fn parse(value: Option<&str>, default: Self) -> Self {
+        match value.map(str::trim) {
+            Some("flex-start" | "start" | "left") => Distribute::Start,
+            Some("center") => Distribute::Center,
+            Some("flex-end" | "end" | "right") => Distribute::End,
+            Some("space-between") => Distribute::SpaceBetween,
+            Some("space-around") => Distribute::SpaceAround,
+            Some("space-evenly") => Distribute::SpaceEvenly,
+            Some("stretch") => Distribute::Stretch,
+            _ => default,
+        }
+    }
+
+    // Отступ перед первым элементом и добавка к промежуткам между элементами.
+    // При нехватке места space-* ведут себя как в браузере: start или center
+    fn offsets(self, free: f32, count: usize) -> (f32, f32) {
+        if count == 0 {
+            return (0.0, 0.0);
+        }
+        let n = count as f32;
+        match self {
+            Distribute::Start | Distribute::Stretch => (0.0, 0.0),
+            Distribute::End => (free, 0.0),
+            Distribute::Center => (free / 2.0, 0.0),
+            Distribute::SpaceBetween if free > 0.0 && count > 1 => (0.0, free / (n - 1.0)),
+            Distribute::SpaceBetween => (0.0, 0.0),
+            Distribute::SpaceAround if free > 0.0 => (free / n / 2.0, free / n),
+            Distribute::SpaceEvenly if free > 0.0 => (free / (n + 1.0), free / (n + 1.0)),
+            Distribute::SpaceAround | Distribute::SpaceEvenly => (free / 2.0, 0.0),
+        }
+    }
+}
+
+// Направление главной оси flex-контейнера
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlexDirection {
+    Row,
+    RowReverse,
+    Column,
+    ColumnReverse,
+}
+
+impl FlexDirection {
+    fn parse(value: Option<&str>) -> Self {
+        match value.map(str::trim) {
+            Some("row-reverse") => FlexDirection::RowReverse,
+            Some("column") => FlexDirection::Column,
+            Some("column-reverse") => FlexDirection::ColumnReverse,
+            _ => FlexDirection::Row,
+        }
+    }
+
+    fn is_horizontal(self) -> bool {
+        matches!(self, FlexDirection::Row | FlexDirection::RowReverse)
+    }
+
+    fn is_reversed(self) -> bool {
+        matches!(self, FlexDirection::RowReverse | FlexDirection::ColumnReverse)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlexLayout {
+    pub direction: FlexDirection,
+    pub wrap: bool,
+    // Промежутки вдоль главной оси и между строками
+    pub main_gap: f32,
+    pub cross_gap: f32,
+    pub justify: Distribute,
+    pub align_items: Align,
+    pub align_content: Distribute,
+}
+
+// Размер дорожки сетки
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Track {
+    Fixed(f32),
+    Percent(f32),
+    Fraction(f32),
+    Auto,
+}
+
+// Именованная область сетки: строки и столбцы [начало, конец)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Area {
+    rows: (usize, usize),
+    columns: (usize, usize),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridLayout {
+    pub columns: Vec<Track>,
+    pub rows: Vec<Track>,
+    areas: HashMap<String, Area>,
+    area_rows: usize,
+    area_columns: usize,
+    pub column_gap: f32,
+    pub row_gap: f32,
+    pub justify_items: Align,
+    pub align_items: Align,
+}
+
+// Элемент, который раскладывает контейнер
+pub struct FlowItem {
+    pub size: Vec2,
+    // Значение grid-area элемента
+    pub area: Option<String>,
+}
+
+// Раскладка контейнера в потоке
+#[derive(Clone, Debug, PartialEq)]
+pub enum FlowLayout {
+    Flex(FlexLayout),
+    Grid(GridLayout),
+}
+
+impl FlowLayout {
+    // Раскладка по стилям контейнера; None - элементы расставляются по координатам
+    pub fn from_styles(styles: &HashMap<String, String>) -> Option<Self> {
+        let style = |key: &str| styles.get(key).map(String::as_str);
+        let (row_gap, column_gap) = gaps(styles);
+
+        match style("display").map(str::trim) {
+            Some("flex" | "inline-flex") => {
+                let direction = FlexDirection::parse(style("flex-direction"));
+                let (main_gap, cross_gap) = if direction.is_horizontal() {
+                    (column_gap, row_gap)
+                } else {
+                    (row_gap, column_gap)
+                };
+                Some(FlowLayout::Flex(FlexLayout {
+                    direction,
+                    wrap: matches!(style("flex-wrap").map(str::trim), Some("wrap" | "wrap-reverse")),
+                    main_gap,
+                    cross_gap,
+                    justify: Distribute::parse(style("justify-content"), Distribute::Start),
+                    align_items: Align::parse(style("align-items")),
+                    align_content: Distribute::parse(style("align-content"), Distribute::Stretch),
+                }))
+            },
+            Some("grid" | "inline-grid") => {
+                let (areas, area_rows, area_columns) = parse_areas(style("grid-template-areas").unwrap_or(""));
+                Some(FlowLayout::Grid(GridLayout {
+                    columns: parse_tracks(style("grid-template-columns").unwrap_or("")),
+                    rows: parse_tracks(style("grid-template-rows").unwrap_or("")),
+                    areas,
+                    area_rows,
+                    area_columns,
+                    column_gap,
+                    row_gap,
+                    justify_items: Align::parse(style("justify-items")),
+                    align_items: Align::parse(style("align-items")),
+                }))
+            },
+            _ => None,
+        }
+    }
+
+    // Положения элементов (левый верхний угол) внутри области содержимого content
+    pub fn arrange(&self, content: Rect, items: &[FlowItem]) -> Vec<Pos2> {
+        match self {
+            FlowLayout::Flex(flex) => flex.arrange(content, items),
+            FlowLayout::Grid(grid) => grid.arrange(content, items),
+        }
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        match self {
+            FlowLayout::Flex(flex) => flex.direction.is_horizontal(),
+            FlowLayout::Grid(_) => true,
+        }
+    }
+}
+
+impl FlexLayout {
+    fn arrange(&self, content: Rect, items: &[FlowItem]) -> Vec<Pos2> {
+        let horizontal = self.direction.is_horizontal();
+        let main = |size: Vec2| if horizontal { size.x } else { size.y };
+        let cross = |size: Vec2| if horizontal { size.y } else { size.x };
+        let main_size = main(content.size());
+        let cross_size = cross(content.size());
+
+        // Разбиение на строки: без переноса все элементы в одной строке
+        let mut lines: Vec<Vec<usize>> = Vec::new();
+        let mut line = Vec::new();
+        let mut line_main = 0.0;
+        for (index, item) in items.iter().enumerate() {
+            let size = main(item.size);
+            if self.wrap && !line.is_empty() && line_main + self.main_gap + size > main_size {
+                lines.push(std::mem::take(&mut line));
+                line_main = 0.0;
+            }
+            if !line.is_empty() {
+                line_main += self.main_gap;
+            }
+            line_main += size;
+            line.push(index);
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+
+        // Однострочный контейнер занимает всю поперечную ось,
+        // строки многострочного распределяются по align-content
+        let mut line_cross: Vec<f32> = lines.iter()
+            .map(|line| line.iter().map(|&i| cross(items[i].size)).fold(0.0, f32::max))
+            .collect();
+        let mut line_starts = Vec::with_capacity(lines.len());
+        if self.wrap {
+            let count = lines.len();
+            let used: f32 = line_cross.iter().sum::<f32>() + self.cross_gap * count.saturating_sub(1) as f32;
+            let free = cross_size - used;
+            let (leading, between) = if self.align_content == Distribute::Stretch && free > 0.0 {
+                for size in &mut line_cross {
+                    *size += free / count as f32;
+                }
+                (0.0, 0.0)
+            } else {
+                self.align_content.offsets(free, count)
+            };
+            let mut position = leading;
+            for size in &line_cross {
+                line_starts.push(position);
+                position += size + self.cross_gap + between;
+            }
+        } else {
+            line_cross = vec![cross_size; lines.len()];
+            line_starts = vec![0.0; lines.len()];
+        }
+
+        let mut positions = vec![content.min; items.len()];
+        for ((line, line_start), line_cross) in lines.iter().zip(line_starts).zip(line_cross) {
+            let used: f32 = line.iter().map(|&i| main(items[i].size)).sum::<f32>()
+                + self.main_gap * line.len().saturating_sub(1) as f32;
+            let (leading, between) = self.justify.offsets(main_size - used, line.len());
+
+            let mut position = leading;
+            for &index in line {
+                let size = items[index].size;
+                // Обратное направление - зеркальное отражение вдоль главной оси
+                let main_position = if self.direction.is_reversed() {
+                    main_size - position - main(size)
+                } else {
+                    position
+                };
+                let cross_position = line_start + self.align_items.offset(line_cross, cross(size));
+                positions[index] = if horizontal {
+                    content.min + Vec2::new(main_position, cross_position)
+                } else {
+                    content.min + Vec2::new(cross_position, main_position)
+                };
+                position += main(size) + self.main_gap + between;
+            }
+        }
+        positions
+    }
+}
+
+impl GridLayout {
+    fn arrange(&self, content: Rect, items: &[FlowItem]) -> Vec<Pos2> {
+        let column_count = self.columns.len().max(self.area_columns).max(1);
+
+        // Размещение: элементы с именованной областью - в ней, остальные - в первые свободные ячейки по строкам
+        let mut placements: Vec<Option<Area>> = items.iter()
+            .map(|item| item.area.as_ref().and_then(|name| self.areas.get(name.trim())).copied())
+            .collect();
+        let mut occupied: Vec<Vec<bool>> = Vec::new();
+        let mark = |occupied: &mut Vec<Vec<bool>>, area: Area| {
+            for row in area.rows.0..area.rows.1 {
+                if occupied.len() <= row {
+                    occupied.resize(row + 1, vec![false; column_count]);
+                }
+                let end = area.columns.1.min(column_count);
+                for cell in occupied[row].iter_mut().take(end).skip(area.columns.0) {
+                    *cell = true;
+                }
+            }
+        };
+        for area in placements.iter().flatten() {
+            mark(&mut occupied, *area);
+        }
+        let (mut row, mut column) = (0, 0);
+        for placement in placements.iter_mut().filter(|p| p.is_none()) {
+            while occupied.get(row).is_some_and(|cells| cells[column]) {
+                column += 1;
+                if column == column_count {
+                    column = 0;
+                    row += 1;
+                }
+            }
+            let area = Area { rows: (row, row + 1), columns: (column, column + 1) };
+            mark(&mut occupied, area);
+            *placement = Some(area);
+        }
+        let placements: Vec<Area> = placements.into_iter().flatten().collect();
+
+        let row_count = placements.iter().map(|a| a.rows.1)
+            .chain([self.rows.len(), self.area_rows, 1])
+            .max()
+            .unwrap_or(1);
+
+        let column_sizes = track_sizes(&self.columns, column_count, content.width(), self.column_gap,
+            placements.iter().zip(items).map(|(a, item)| (a.columns, item.size.x)));
+        let row_sizes = track_sizes(&self.rows, row_count, content.height(), self.row_gap,
+            placements.iter().zip(items).map(|(a, item)| (a.rows, item.size.y)));
+        let column_starts = track_starts(&column_sizes, self.column_gap);
+        let row_starts = track_starts(&row_sizes, self.row_gap);
+
+        placements.iter().zip(items)
+            .map(|(area, item)| {
+                let span = |starts: &[f32], sizes: &[f32], (start, end): (usize, usize)| {
+                    let last = end.saturating_sub(1).max(start);
+                    (starts[start], starts[last] + sizes[last] - starts[start])
+                };
+                let (x, width) = span(&column_starts, &column_sizes, area.columns);
+                let (y, height) = span(&row_starts, &row_sizes, area.rows);
+                content.min + Vec2::new(
+                    x + self.justify_items.offset(width, item.size.x),
+                    y + self.align_items.offset(height, item.size.y),
+                )
+            })
+            .collect()
+    }
+}
+
+// Размеры дорожек одной оси. Дорожки auto подстраиваются под элементы из одной ячейки;
+// остаток места делят дорожки fr, а если их нет - дорожки auto
+fn track_sizes(
+    template: &[Track],
+    count: usize,
+    available: f32,
+    gap: f32,
+    spans: impl Iterator<Item = ((usize, usize), f32)>,
+) -> Vec<f32> {
+    let tracks: Vec<Track> = (0..count).map(|i| template.get(i).copied().unwrap_or(Track::Auto)).collect();
+    let mut sizes: Vec<f32> = tracks.iter()
+        .map(|track| match *track {
+            Track::Fixed(size) => size,
+            Track::Percent(percent) => available * percent / 100.0,
+            _ => 0.0,
+        })
+        .collect();
+
+    for ((start, end), size) in spans {
+        if end == start + 1 && start < count && tracks[start] == Track::Auto {
+            sizes[start] = sizes[start].max(size);
+        }
+    }
+
+    let free = available - sizes.iter().sum::<f32>() - gap * count.saturating_sub(1) as f32;
+    let fractions: f32 = tracks.iter().map(|t| if let Track::Fraction(f) = t { *f } else { 0.0 }).sum();
+    if fractions > 0.0 {
+        let unit = free.max(0.0) / fractions.max(1.0);
+        for (size, track) in sizes.iter_mut().zip(&tracks) {
+            if let Track::Fraction(fraction) = track {
+                *size = unit * fraction;
+            }
+        }
+    } else if free > 0.0 {
+        let auto_count = tracks.iter().filter(|t| **t == Track::Auto).count();
+        for (size, track) in sizes.iter_mut().zip(&tracks) {
+            if *track == Track::Auto {
+                *size += free / auto_count as f32;
+            }
+        }
+    }
+    sizes
+}
+
+fn track_starts(sizes: &[f32], gap: f32) -> Vec<f32> {
+    let mut position = 0.0;
+    sizes.iter()
+        .map(|size| {
+            let start = position;
+            position += size + gap;
+            start
+        })
+        .collect()
+}
+
+// Наибольшее число дорожек сетки: repeat() с огромным числом повторов не должен занять всю память
+const MAX_TRACKS: usize = 1000;
+
+// Список дорожек grid-template-columns/rows: px, em, rem, %, fr, auto, repeat() и minmax()
+fn parse_tracks(value: &str) -> Vec<Track> {
+    let mut tracks = Vec::new();
+    for token in split_top_level(value, |c| c.is_whitespace()) {
+        if let Some(inner) = function_args(&token, "repeat") {
+            let args = split_top_level(&inner, |c| c == ',');
+            if let [count, list] = args.as_slice() {
+                let repeated = parse_tracks(list);
+                let count = count.trim().parse::<usize>().unwrap_or(1).min(MAX_TRACKS);
+                for _ in 0..count {
+                    if tracks.len() + repeated.len() > MAX_TRACKS {
+                        break;
+                    }
+                    tracks.extend(repeated.iter().copied());
+                }
+            }
+        } else if let Some(inner) = function_args(&token, "minmax") {
+            // Из minmax берется верхняя граница, если она задана размером
+            let args = split_top_level(&inner, |c| c == ',');
+            let track = args.iter().rev().map(|arg| parse_track(arg)).find(|t| *t != Track::Auto);
+            tracks.push(track.unwrap_or(Track::Auto));
+        } else {
+            tracks.push(parse_track(&token));
+        }
+    }
+    tracks
+}
+
+fn parse_track(token: &str) -> Track {
+    let token = token.trim();
+    if let Some(value) = token.strip_suffix("fr") {
+        return value.trim().parse().map(Track::Fraction).unwrap_or(Track::Auto);
+    }
+    if let Some(value) = token.strip_suffix('%') {
+        return value.trim().parse().map(Track::Percent).unwrap_or(Track::Auto);
+    }
+    parse_length(token, css::ROOT_FONT_SIZE).map(Track::Fixed).unwrap_or(Track::Auto)
+}
+
+// Аргументы функции вида name(...)
+fn function_args(token: &str, name: &str) -> Option<String> {
+    let inner = token.trim().strip_prefix(name)?.trim_start().strip_prefix('(')?.strip_suffix(')')?;
+    Some(inner.to_string())
+}
+
+// Делит строку по разделителю вне скобок
+fn split_top_level(value: &str, is_separator: impl Fn(char) -> bool) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    for c in value.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        if depth == 0 && is_separator(c) {
+            if !current.trim().is_empty() {
+                parts.push(current.trim().to_string());
+            }
+            current.clear();
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+    parts
+}
+
+// grid-template-areas: строки в кавычках, "." - пустая ячейка.
+// Возвращает области, число строк и столбцов
+fn parse_areas(value: &str) -> (HashMap<String, Area>, usize, usize) {
+    let rows: Vec<Vec<&str>> = value.split(['"', '\''])
+        .skip(1)
+        .step_by(2)
+        .map(|row| row.split_whitespace().collect())
+        .collect();
+
+    let mut areas: HashMap<String, Area> = HashMap::new();
+    for (row, names) in rows.iter().enumerate() {
+        for (column, name) in names.iter().enumerate() {
+            if name.chars().all(|c| c == '.') {
+                continue;
+            }
+            let area = areas.entry(name.to_string()).or_insert(Area {
+                rows: (row, row + 1),
+                columns: (column, column + 1),
+            });
+            area.rows = (area.rows.0.min(row), area.rows.1.max(row + 1));
+            area.columns = (area.columns.0.min(column), area.columns.1.max(column + 1));
+        }
+    }
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    (areas, rows.len(), columns)
+}
+
+// Длина в пикселях; em - от шрифта элемента, проценты здесь не поддерживаются
+fn parse_length(value: &str, font_size: f32) -> Option<f32> {
+    Length::parse(value)
+        .filter(|length| !matches!(length, Length::Percent(_)))
+        .map(|length| length.to_px(font_size, 0.0))
+}
+
+// Промежутки между строками и столбцами: gap, row-gap, column-gap
+fn gaps(styles: &HashMap<String, String>) -> (f32, f32) {
+    let font_size = css::font_size(styles, css::ROOT_FONT_SIZE);
+    let gap: Vec<f32> = styles.get("gap")
+        .map(|value| css::split_values(value).iter().filter_map(|v| parse_length(v, font_size)).collect())
+        .unwrap_or_default();
+    let row_gap = styles.get("row-gap").and_then(|v| parse_length(v, font_size))
+        .or(gap.first().copied())
+        .unwrap_or(0.0);
+    let column_gap = styles.get("column-gap").and_then(|v| parse_length(v, font_size))
+        .or(gap.get(1).or(gap.first()).copied())
+        .unwrap_or(0.0);
+    (row_gap, column_gap)
+}
+
+// Область содержимого контейнера (внутри рамки и внутренних отступов) от его левого верхнего угла
+pub fn content_box(styles: &HashMap<String, String>, size: (f32, f32)) -> Rect {
+    let font_size = css::font_size(styles, css::ROOT_FONT_SIZE);
+    let border = Border::from_styles(styles, font_size, Border::none()).widths();
+    // Проценты внутренних отступов считаются от ширины самого контейнера
+    let padding = css::edges(styles, "padding", font_size, size.0);
+    let min = Pos2::new(border[3] + padding[3], border[0] + padding[0]);
+    let max = Pos2::new(size.0 - border[1] - padding[1], size.1 - border[2] - padding[2]);
+    Rect::from_min_max(min, max.max(min))
+}
+
+// Элемент flex- или grid-контейнера: вместо координат - место в потоке, без растяжения и сжатия.
+// position: relative сохраняет его точкой отсчета для собственных дочерних элементов
+pub fn declare_flow_item(rule: &mut CssRule) {
+    rule.remove("position");
+    rule.remove("left");
+    rule.remove("top");
+    rule.declare("position", "relative".to_string());
+    rule.declare("flex", "0 0 auto".to_string());
+}
+
+// Раскладывается ли содержимое элемента с такими стилями в потоке
+pub fn is_flow(styles: &HashMap<String, String>) -> bool {
+    is_flow_display(styles.get("display").map(String::as_str))
+}
+
+pub fn is_flow_display(display: Option<&str>) -> bool {
+    matches!(display.map(str::trim), Some("flex" | "inline-flex" | "grid" | "inline-grid"))
+}
+
+// Пересчитывает положения элементов во flex- и grid-контейнерах (на всех уровнях вложенности).
+// Скрытые на сайте элементы места не занимают
+pub fn arrange_elements(elements: &mut [Box<dyn UIElement>]) {
+    for element in elements {
+        let layout = FlowLayout::from_styles(&element.base().styles);
+        let content = content_box(&element.base().styles, element.get_size());
+        let Some(children) = element.children_mut() else {
+            continue;
+        };
+
+        if let Some(layout) = layout {
+            let visible: Vec<usize> = (0..children.len())
+                .filter(|&i| !children[i].base().site_hidden)
+                .collect();
+            let items: Vec<FlowItem> = visible.iter()
+                .map(|&i| {
+                    let size = children[i].get_size();
+                    FlowItem {
+                        size: Vec2::new(size.0, size.1),
+                        area: children[i].base().styles.get("grid-area").cloned(),
+                    }
+                })
+                .collect();
+            for (index, position) in visible.into_iter().zip(layout.arrange(content, &items)) {
+                children[index].set_position((position.x, position.y));
+            }
+        }
+        arrange_elements(children);
+    }
+}
+
+// Позиция в списке соседей, куда попадает элемент, брошенный в точку pointer.
+// siblings - прямоугольники остальных элементов контейнера в порядке списка
+pub fn insert_index(layout: &FlowLayout, siblings: &[Rect], pointer: Pos2) -> usize {
+    let reversed = matches!(layout, FlowLayout::Flex(flex) if flex.direction.is_reversed());
+    siblings.iter()
+        .filter(|rect| {
+            let before = if layout.is_horizontal() {
+                // По строкам: элемент выше курсора или левее в той же строке
+                rect.bottom() < pointer.y || (rect.y_range().contains(pointer.y) && rect.center().x < pointer.x)
+            } else {
+                rect.center().y < pointer.y
+            };
+            before != reversed
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn styles(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn items(sizes: &[(f32, f32)]) -> Vec<FlowItem> {
+        sizes.iter().map(|&(w, h)| FlowItem { size: Vec2::new(w, h), area: None }).collect()
+    }
+
+    fn arrange(pairs: &[(&str, &str)], content: (f32, f32), items: &[FlowItem]) -> Vec<(f32, f32)> {
+        let layout = FlowLayout::from_styles(&styles(pairs)).unwrap();
+        let content = Rect::from_min_size(Pos2::ZERO, Vec2::new(content.0, content.1));
+        layout.arrange(content, items).into_iter().map(|p| (p.x, p.y)).collect()
+    }
+
+    #[test]
+    fn tracks_parse_sizes_and_functions() {
+        assert_eq!(parse_tracks("100px 1fr auto 25% 2em"), vec![
+            Track::Fixed(100.0), Track::Fraction(1.0), Track::Auto, Track::Percent(25.0), Track::Fixed(32.0),
+        ]);
+        assert_eq!(parse_tracks("repeat(2, 10px 2fr)"), vec![
+            Track::Fixed(10.0), Track::Fraction(2.0), Track::Fixed(10.0), Track::Fraction(2.0),
+        ]);
+        assert_eq!(parse_tracks("minmax(100px, 1fr) minmax(100px, auto)"), vec![Track::Fraction(1.0), Track::Fixed(100.0)]);
+        assert_eq!(parse_tracks(""), vec![]);
+        assert_eq!(parse_tracks("abc 1xfr"), vec![Track::Auto, Track::Auto]);
+        assert_eq!(parse_tracks("repeat(x, 1fr)"), vec![Track::Fraction(1.0)]);
+    }
+
+    #[test]
+    fn huge_repeat_is_clamped() {
+        assert_eq!(parse_tracks("repeat(1000000000, 1fr)").len(), MAX_TRACKS);
+        assert_eq!(parse_tracks("repeat(1000, repeat(1000, 1px))").len(), MAX_TRACKS);
+        assert_eq!(parse_tracks("repeat(1000000000, )"), vec![]);
+    }
+
+    #[test]
+    fn areas_span_rows_and_columns() {
+        let (areas, rows, columns) = parse_areas("\"a a .\" 'b c c'");
+        assert_eq!((rows, columns), (2, 3));
+        assert_eq!(areas["a"], Area { rows: (0, 1), columns: (0, 2) });
+        assert_eq!(areas["c"], Area { rows: (1, 2), columns: (1, 3) });
+        assert!(!areas.contains_key("."));
+        assert_eq!(parse_areas("none"), (HashMap::new(), 0, 0));
+    }
+
+    #[test]
+    fn flex_row_distributes_and_aligns() {
+        let positions = arrange(
+            &[("display", "flex"), ("gap", "10px"), ("justify-content", "space-between"), ("align-items", "center")],
+            (200.0, 100.0),
+            &items(&[(50.0, 20.0), (30.0, 40.0)]),
+        );
+        assert_eq!(positions, vec![(0.0, 40.0), (170.0, 30.0)]);
+    }
+
+    #[test]
+    fn flex_wrap_stretches_lines() {
+        let positions = arrange(
+            &[("display", "flex"), ("flex-wrap", "wrap"), ("gap", "10px")],
+            (100.0, 100.0),
+            &items(&[(40.0, 20.0), (40.0, 20.0), (40.0, 20.0)]),
+        );
+        assert_eq!(positions, vec![(0.0, 0.0), (50.0, 0.0), (0.0, 55.0)]);
+    }
+
+    #[test]
+    fn flex_column_reverse_starts_at_bottom() {
+        let positions = arrange(
+            &[("display", "flex"), ("flex-direction", "column-reverse")],
+            (100.0, 100.0),
+            &items(&[(10.0, 30.0), (10.0, 20.0)]),
+        );
+        assert_eq!(positions, vec![(0.0, 70.0), (0.0, 50.0)]);
+    }
+
+    #[test]
+    fn grid_places_items_in_free_cells() {
+        let positions = arrange(
+            &[("display", "grid"), ("grid-template-columns", "100px 1fr"), ("gap", "10px")],
+            (300.0, 200.0),
+            &items(&[(50.0, 40.0), (50.0, 40.0), (50.0, 40.0)]),
+        );
+        assert_eq!(positions, vec![(0.0, 0.0), (110.0, 0.0), (0.0, 105.0)]);
+    }
+
+    #[test]
+    fn grid_places_items_in_named_areas() {
+        let mut items = items(&[(10.0, 10.0), (10.0, 10.0), (10.0, 10.0)]);
+        for (item, area) in items.iter_mut().zip(["main", "head", "side"]) {
+            item.area = Some(area.to_string());
+        }
+        let positions = arrange(
+            &[
+                ("display", "grid"),
+                ("grid-template-areas", "\"head head\" \"side main\""),
+                ("grid-template-columns", "1fr 3fr"),
+                ("grid-template-rows", "50px 1fr"),
+            ],
+            (400.0, 250.0),
+            &items,
+        );
+        assert_eq!(positions, vec![(100.0, 50.0), (0.0, 0.0), (0.0, 50.0)]);
+    }
+
+    #[test]
+    fn gaps_accept_shorthand_and_longhands() {
+        assert_eq!(gaps(&styles(&[("gap", "10px 20px")])), (10.0, 20.0));
+        assert_eq!(gaps(&styles(&[("gap", "8px"), ("column-gap", "1em")])), (8.0, 16.0));
+        assert_eq!(gaps(&styles(&[("gap", "abc")])), (0.0, 0.0));
+    }
+}
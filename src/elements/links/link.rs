@@ -0,0 +1,154 @@
+use serde::{Serialize, Deserialize};
+use egui::{Pos2, Color32, Stroke};
+use crate::elements::{ElementBase, ElementType, UIElement};
+use crate::elements::canvas::CanvasContext;
+use crate::elements::css::{self, Border};
+use crate::elements::export::ExportContext;
+use crate::elements::links::link_target::LinkTarget;
+use crate::utils::html_generator::escape_html;
+use std::any::Any;
+
+// Текстовая ссылка
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Link {
+    pub base: ElementBase,
+    pub text: String,
+    pub target: LinkTarget,
+    // Открывать ли ссылку в новой вкладке
+    #[serde(default)]
+    pub new_tab: bool,
+}
+
+impl Default for Link {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Link {
+    pub fn new() -> Self {
+        let mut base = ElementBase::new(ElementType::Link);
+        base.size = (160.0, 24.0);
+        
+        // Устанавливаем стили по умолчанию
+        base.styles.insert("color".to_string(), "#1E88E5".to_string());
+        base.styles.insert("font-size".to_string(), "16px".to_string());
+        base.styles.insert("text-decoration".to_string(), "underline".to_string());
+        
+        Self {
+            base,
+            text: "Ссылка".to_string(),
+            target: LinkTarget::None,
+            new_tab: false,
+        }
+    }
+    }
+
+// Атрибуты href и target для тега <a>
+pub fn anchor_attributes(target: &LinkTarget, new_tab: bool, ctx: &ExportContext) -> String {
+    let href = target.href(ctx).unwrap_or_else(|| "#".to_string());
+    let mut attributes = format!(" href=\"{}\"", escape_html(&href));
+    if new_tab {
+        attributes.push_str(" target=\"_blank\" rel=\"noopener\"");
+    }
+    attributes
+}
+
+impl UIElement for Link {
+    fn base(&self) -> &ElementBase {
+        &self.base
+    }
+    
+    fn base_mut(&mut self) -> &mut ElementBase {
+        &mut self.base
+    }
+    
+    fn get_id(&self) -> &str {
+        &self.base.id
+    }
+    
+    fn get_element_type(&self) -> &ElementType {
+        &self.base.element_type
+    }
+    
+    fn get_position(&self) -> (f32, f32) {
+        self.base.position
+    }
+    
+    fn set_position(&mut self, position: (f32, f32)) {
+        self.base.position = position;
+    }
+    
+    fn get_size(&self) -> (f32, f32) {
+        self.base.size
+    }
+    
+    fn set_size(&mut self, size: (f32, f32)) {
+        self.base.size = size;
+    }
+    
+    fn clone_box(&self) -> Box<dyn UIElement> {
+        Box::new(self.clone())
+    }
+    
+    fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(self)
+    }
+    
+    // Ссылки на страницы сайта превращаются в относительные пути при экспорте
+    fn to_html(&self, ctx: &ExportContext) -> String {
+        format!(
+            "<a{}{}>{}</a>",
+            self.base.attributes_html(ctx),
+            anchor_attributes(&self.target, self.new_tab, ctx),
+            escape_html(&self.text)
+        )
+    }
+    
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    
+    fn contains_point(&self, point: (f32, f32)) -> bool {
+        self.base.contains_point(point)
+    }
+    
+    fn render(&self, painter: &egui::Painter, canvas: &CanvasContext) {
+        let element_rect = canvas.rect(&self.base);
+        let color = css::color(&self.base.styles, "color").unwrap_or(Color32::from_rgb(30, 136, 229));
+        let font_size = css::font_size(&self.base.styles, css::ROOT_FONT_SIZE);
+        
+        canvas.paint_background(painter, &self.base, Color32::TRANSPARENT);
+        
+        let text_rect = painter.text(
+            Pos2::new(element_rect.left(), element_rect.center().y),
+            egui::Align2::LEFT_CENTER,
+            &self.text,
+            egui::FontId::proportional(font_size * canvas.scale),
+            color
+        );
+        
+        // Подчеркивание, если оно не отключено в стилях
+        if self.base.styles.get("text-decoration").map(String::as_str) != Some("none") {
+            painter.line_segment(
+                [text_rect.left_bottom(), text_rect.right_bottom()],
+                Stroke::new(canvas.scale, color)
+            );
+        }
+        
+        canvas.paint_border(painter, &self.base, Border::none());
+        
+        // Ссылка без цели отмечается бледно-красной рамкой
+        if self.target.is_none() {
+            painter.rect_stroke(element_rect, 0.0, Stroke::new(1.0, Color32::from_rgba_unmultiplied(255, 0, 0, 80)));
+        }
+        
+        if canvas.is_selected(&self.base.id) {
+            painter.rect_stroke(element_rect, 0.0, Stroke::new(1.0, Color32::BLUE));
+        }
+    }
+}
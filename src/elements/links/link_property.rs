@@ -0,0 +1,159 @@
+use egui::Ui;
+use crate::elements::links::link::Link;
+use crate::elements::links::link_target::{LinkTarget, PageInfo};
+use crate::elements::UIElement;
+
+// Виды целей ссылки в порядке показа в списке
+const TARGET_KINDS: [&str; 6] = ["none", "url", "page", "anchor", "email", "phone"];
+
+fn target_kind(target: &LinkTarget) -> &'static str {
+    match target {
+        LinkTarget::None => "none",
+        LinkTarget::Url(_) => "url",
+        LinkTarget::Page(_) => "page",
+        LinkTarget::Anchor { .. } => "anchor",
+        LinkTarget::Email(_) => "email",
+        LinkTarget::Phone(_) => "phone",
+    }
+}
+
+// Пустая цель выбранного вида
+fn empty_target(kind: &str, pages: &[PageInfo]) -> LinkTarget {
+    match kind {
+        "url" => LinkTarget::Url("https://".to_string()),
+        "page" => LinkTarget::Page(pages.first().map(|p| p.id.clone()).unwrap_or_default()),
+        "anchor" => LinkTarget::Anchor { page_id: None, element_id: String::new() },
+        "email" => LinkTarget::Email(String::new()),
+        "phone" => LinkTarget::Phone(String::new()),
+        _ => LinkTarget::None,
+    }
+}
+
+// Редактор цели ссылки; используется ссылкой и кнопкой
+pub fn show_link_target(ui: &mut Ui, id_source: &str, target: &mut LinkTarget, pages: &[PageInfo]) -> bool {
+    let mut changed = false;
+    
+    let mut kind = target_kind(target);
+    egui::ComboBox::from_id_source((id_source, "kind"))
+        .selected_text(target.kind_label())
+        .show_ui(ui, |ui| {
+            for option in TARGET_KINDS {
+                ui.selectable_value(&mut kind, option, empty_target(option, pages).kind_label());
+            }
+        });
+    if kind != target_kind(target) {
+        *target = empty_target(kind, pages);
+        changed = true;
+    }
+    
+    let page_title = |id: &str| pages.iter()
+        .find(|p| p.id == id)
+        .map(|p| p.title.clone())
+        .unwrap_or_else(|| "(страница удалена)".to_string());
+    
+    match target {
+        LinkTarget::None => {},
+        LinkTarget::Url(url) => {
+            ui.label("Адрес:");
+            changed |= ui.text_edit_singleline(url).changed();
+        },
+        LinkTarget::Email(address) => {
+            ui.label("Адрес почты:");
+            changed |= ui.text_edit_singleline(address).changed();
+        },
+        LinkTarget::Phone(number) => {
+            ui.label("Номер телефона:");
+            changed |= ui.text_edit_singleline(number).changed();
+        },
+        LinkTarget::Page(page_id) => {
+            ui.label("Страница:");
+            egui::ComboBox::from_id_source((id_source, "page"))
+                .selected_text(page_title(page_id))
+                .show_ui(ui, |ui| {
+                    for page in pages {
+                        if ui.selectable_label(*page_id == page.id, &page.title).clicked() {
+                            *page_id = page.id.clone();
+                            changed = true;
+                        }
+                    }
+                });
+        },
+        LinkTarget::Anchor { page_id, element_id } => {
+            ui.label("Страница:");
+            let selected = page_id.as_deref().map(page_title).unwrap_or_else(|| "Текущая".to_string());
+            egui::ComboBox::from_id_source((id_source, "anchor_page"))
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    if ui.selectable_label(page_id.is_none(), "Текущая").clicked() {
+                        *page_id = None;
+                        changed = true;
+                    }
+                    for page in pages {
+                        if ui.selectable_label(page_id.as_deref() == Some(page.id.as_str()), &page.title).clicked() {
+                            *page_id = Some(page.id.clone());
+                            changed = true;
+                        }
+                    }
+                });
+            
+            ui.label("Элемент:");
+            changed |= ui.text_edit_singleline(element_id).changed();
+            
+            // Элементы выбранной страницы для быстрого выбора
+            let anchors = pages.iter()
+                .find(|p| match page_id {
+                    Some(id) => p.id == *id,
+                    None => p.current,
+                })
+                .map(|p| p.anchors.as_slice());
+            if let Some(anchors) = anchors {
+                let selected = anchors.iter()
+                    .find(|(id, _)| id == element_id)
+                    .map(|(_, label)| label.clone())
+                    .unwrap_or_else(|| "Выберите элемент".to_string());
+                egui::ComboBox::from_id_source((id_source, "anchor_element"))
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        for (id, label) in anchors {
+                            if ui.selectable_label(id == element_id, label).clicked() {
+                                *element_id = id.clone();
+                                changed = true;
+                            }
+                        }
+                    });
+            }
+        },
+    }
+    
+    changed
+}
+
+// Структура для работы со свойствами ссылки.
+// Цвет и подчеркивание редактируются в общем инспекторе свойств
+#[derive(Default)]
+pub struct LinkProperty;
+
+impl LinkProperty {
+    pub fn new() -> Self {
+        Self
+    }
+    
+    // Метод для отображения и редактирования свойств ссылки
+    pub fn show(&mut self, ui: &mut Ui, element: &mut Box<dyn UIElement>, pages: &[PageInfo]) -> bool {
+        let Some(link) = element.as_any_mut().downcast_mut::<Link>() else {
+            return false;
+        };
+        
+        let mut changed = false;
+        
+        ui.label("Текст ссылки:");
+        changed |= ui.text_edit_singleline(&mut link.text).changed();
+        
+        ui.separator();
+        ui.heading("Цель ссылки");
+        changed |= show_link_target(ui, "link_target", &mut link.target, pages);
+        changed |= ui.checkbox(&mut link.new_tab, "Открывать в новой вкладке").changed();
+        
+        changed
+    }
+}
@@ -0,0 +1,105 @@
+// Цель ссылки: внешний адрес, страница сайта, якорь на элемент, почта или телефон.
+// Страницы задаются идентификатором, поэтому ссылки не ломаются при
+// переименовании страниц и файлов - путь вычисляется только при экспорте.
+
+use serde::{Serialize, Deserialize};
+use crate::elements::export::ExportContext;
+use crate::elements::UIElement;
+use crate::models::site::Site;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub enum LinkTarget {
+    #[default]
+    None,
+    // Внешний адрес как есть
+    Url(String),
+    // Страница сайта по идентификатору
+    Page(String),
+    // Элемент на странице; без страницы - на текущей
+    Anchor {
+        page_id: Option<String>,
+        element_id: String,
+    },
+    Email(String),
+    Phone(String),
+}
+
+impl LinkTarget {
+    // Название вида ссылки для интерфейса
+    pub fn kind_label(&self) -> &'static str {
+        match self {
+            LinkTarget::None => "Нет",
+            LinkTarget::Url(_) => "Внешний адрес",
+            LinkTarget::Page(_) => "Страница сайта",
+            LinkTarget::Anchor { .. } => "Элемент на странице",
+            LinkTarget::Email(_) => "Электронная почта",
+            LinkTarget::Phone(_) => "Телефон",
+        }
+    }
+    
+    pub fn is_none(&self) -> bool {
+        *self == LinkTarget::None
+    }
+    
+    // Значение атрибута href при экспорте; None, если цель не задана или не найдена
+    pub fn href(&self, ctx: &ExportContext) -> Option<String> {
+        match self {
+            LinkTarget::None => None,
+            LinkTarget::Url(url) => Some(url.trim().to_string()).filter(|url| !url.is_empty()),
+            LinkTarget::Page(page_id) => ctx.page_url(page_id),
+            LinkTarget::Anchor { page_id, element_id } => {
+                let anchor = format!("#{}", element_id);
+                match page_id {
+                    Some(page_id) if Some(page_id) != ctx.current_page_id.as_ref() => {
+                        ctx.page_url(page_id).map(|url| format!("{}{}", url, anchor))
+                    },
+                    _ => Some(anchor),
+                }
+            },
+            LinkTarget::Email(address) => Some(format!("mailto:{}", address.trim())),
+            LinkTarget::Phone(number) => {
+                let number: String = number.chars().filter(|c| !c.is_whitespace() && *c != '(' && *c != ')' && *c != '-').collect();
+                Some(format!("tel:{}", number))
+            },
+        }
+    }
+}
+
+// Сведения о странице для выбора цели ссылки в редакторе
+#[derive(Clone, Debug)]
+pub struct PageInfo {
+    pub id: String,
+    pub title: String,
+    // Открыта ли страница в редакторе
+    pub current: bool,
+    // Элементы страницы, на которые можно сослаться: (идентификатор, подпись)
+    pub anchors: Vec<(String, String)>,
+}
+
+impl PageInfo {
+    // Список страниц сайта с элементами для якорей
+    pub fn collect(site: &Site) -> Vec<PageInfo> {
+        site.pages.iter()
+            .enumerate()
+            .map(|(index, page)| {
+                let mut anchors = Vec::new();
+                collect_anchors(&page.elements, &mut anchors);
+                PageInfo {
+                    id: page.id.clone(),
+                    title: page.title.clone(),
+                    current: index == site.current_page,
+                    anchors,
+                }
+            })
+            .collect()
+    }
+}
+
+fn collect_anchors(elements: &[Box<dyn UIElement>], anchors: &mut Vec<(String, String)>) {
+    for element in elements {
+        anchors.push((element.get_id().to_string(), element.base().display_name()));
+        if let Some(children) = element.children() {
+            collect_anchors(children, anchors);
+        }
+    }
+}
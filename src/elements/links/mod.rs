@@ -0,0 +1,3 @@
+pub mod link;
+pub mod link_property;
+pub mod link_target;
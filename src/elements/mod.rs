@@ -1,12 +1,38 @@
+pub mod breakpoints;
 pub mod buttons;
+pub mod canvas;
+pub mod color_picker;
+pub mod containers;
+pub mod css;
+pub mod export;
+pub mod forms;
+pub mod images;
+pub mod layout;
+pub mod links;
+pub mod opaque;
+pub mod properties;
+pub mod property_inspector;
+pub mod registry;
+pub mod serde_elements;
+pub mod texts;
 
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 use std::any::Any;
 
+use crate::utils::html_generator::escape_html;
+use breakpoints::{BaseLayout, BreakpointOverride};
+use canvas::CanvasContext;
+use export::{CssRule, ExportContext};
+use properties::PropertySection;
+
 // Базовый трейт для всех элементов
 pub trait UIElement {
+    // Общие данные элемента
+    fn base(&self) -> &ElementBase;
+    fn base_mut(&mut self) -> &mut ElementBase;
+    
     fn get_id(&self) -> &str;
     fn get_element_type(&self) -> &ElementType;
     fn get_position(&self) -> (f32, f32);
@@ -14,7 +40,60 @@ pub trait UIElement {
     fn get_size(&self) -> (f32, f32);
     fn set_size(&mut self, size: (f32, f32));
     fn contains_point(&self, point: (f32, f32)) -> bool;
-    fn render(&self, painter: &egui::Painter, selected: bool);
+    fn render(&self, painter: &egui::Painter, canvas: &CanvasContext);
+    
+    // Создает копию элемента за указателем на трейт
+    fn clone_box(&self) -> Box<dyn UIElement>;
+    
+    // Сериализует данные элемента для сохранения проекта
+    fn to_json(&self) -> serde_json::Result<serde_json::Value>;
+    
+    // Возвращает HTML-разметку элемента для экспорта
+    fn to_html(&self, ctx: &ExportContext) -> String;
+    
+    // Возвращает правила CSS элемента; по умолчанию - стили и позиция из ElementBase
+    fn to_css(&self, ctx: &ExportContext) -> Vec<CssRule> {
+        vec![self.base().css_rule(ctx)]
+    }
+    
+    // Дочерние элементы (только у контейнеров); их координаты отсчитываются от родителя
+    fn children(&self) -> Option<&[Box<dyn UIElement>]> {
+        None
+    }
+    
+    fn children_mut(&mut self) -> Option<&mut Vec<Box<dyn UIElement>>> {
+        None
+    }
+    
+    // Выдает элементу и всем его потомкам новые идентификаторы (для копий)
+    fn regenerate_ids(&mut self) {
+        self.base_mut().id = Uuid::new_v4().to_string();
+        if let Some(children) = self.children_mut() {
+            for child in children.iter_mut() {
+                child.regenerate_ids();
+            }
+        }
+    }
+    
+    // Файлы ресурсов, которые нужно скопировать при экспорте
+    fn assets(&self) -> Vec<String> {
+        self.base().style_assets()
+    }
+    
+    // Заменяет пути к файлам ресурсов элемента и его потомков (при переносе ресурсов в проект)
+    fn map_assets(&mut self, map: &mut dyn FnMut(&str) -> String) {
+        self.base_mut().map_style_assets(map);
+        if let Some(children) = self.children_mut() {
+            for child in children.iter_mut() {
+                child.map_assets(map);
+            }
+        }
+    }
+    
+    // Редактируемые свойства по разделам для общего инспектора свойств
+    fn property_schema(&self) -> Vec<PropertySection> {
+        properties::default_schema()
+    }
     
     // Методы для приведения типов
     fn as_any(&self) -> &dyn Any;
@@ -32,23 +111,65 @@ impl<T: UIElement + Any> UIElementExt for T {
     }
 }
 
+// Позволяет клонировать страницы вместе с их элементами
+impl Clone for Box<dyn UIElement> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+// Правила CSS для списка соседних элементов: порядок в списке задает z-index.
+// В flex- и grid-контейнере (in_flow) элементы идут в потоке, без координат
+pub fn children_css(children: &[Box<dyn UIElement>], in_flow: bool, ctx: &ExportContext) -> Vec<CssRule> {
+    let mut rules = Vec::new();
+    for (index, child) in children.iter().enumerate() {
+        let mut child_rules = child.to_css(ctx);
+        // Первое правило элемента - его собственный класс
+        if let Some(rule) = child_rules.first_mut() {
+            if in_flow {
+                layout::declare_flow_item(rule);
+            }
+            rule.declare("z-index", (index + 1).to_string());
+        }
+        rules.extend(child_rules);
+    }
+    rules
+}
+
 // Трейт для приведения типов в editor.rs
 pub trait UIElementExt: UIElement {
     fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ElementType {
     Text,
     Button,
     Image,
     Container,
     Form,
+    Input,
     Link,
     Custom(String),
 }
 
+impl ElementType {
+    // Название типа для интерфейса
+    pub fn label(&self) -> String {
+        match self {
+            ElementType::Text => "Текст".to_string(),
+            ElementType::Button => "Кнопка".to_string(),
+            ElementType::Image => "Изображение".to_string(),
+            ElementType::Container => "Контейнер".to_string(),
+            ElementType::Form => "Форма".to_string(),
+            ElementType::Input => "Поле формы".to_string(),
+            ElementType::Link => "Ссылка".to_string(),
+            ElementType::Custom(name) => name.clone(),
+        }
+    }
+}
+
 // Базовая структура для элемента
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ElementBase {
@@ -58,6 +179,24 @@ pub struct ElementBase {
     pub size: (f32, f32),
     pub styles: HashMap<String, String>,
     pub attributes: HashMap<String, String>,
+    // Имя в панели слоев; пустое - показывается тип элемента
+    #[serde(default)]
+    pub name: String,
+    // Скрыт на холсте редактора (на экспорт не влияет)
+    #[serde(default)]
+    pub hidden: bool,
+    // Заблокирован: не выделяется и не перемещается на холсте
+    #[serde(default)]
+    pub locked: bool,
+    // Не показывается на сайте при выбранной ширине экрана (display: none)
+    #[serde(default)]
+    pub site_hidden: bool,
+    // Отличия для других ширин экрана по идентификатору ширины
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub overrides: HashMap<String, BreakpointOverride>,
+    // Основные значения, пока в редакторе выбрана другая ширина
+    #[serde(skip)]
+    pub base_layout: Option<BaseLayout>,
 }
 
 impl ElementBase {
@@ -69,9 +208,89 @@ impl ElementBase {
             size: (100.0, 50.0),
             styles: HashMap::new(),
             attributes: HashMap::new(),
+            name: String::new(),
+            hidden: false,
+            locked: false,
+            site_hidden: false,
+            overrides: HashMap::new(),
+            base_layout: None,
+        }
+    }
+    
+    // Имя для панели слоев и списков выбора
+    pub fn display_name(&self) -> String {
+        if self.name.trim().is_empty() {
+            format!("{} {}", self.element_type.label(), &self.id[..self.id.len().min(4)])
+        } else {
+            self.name.clone()
         }
     }
     
+    // Можно ли выделить элемент мышью на холсте
+    pub fn is_interactive(&self) -> bool {
+        !self.hidden && !self.site_hidden && !self.locked
+    }
+    
+    // Правило CSS элемента: его стили и абсолютное позиционирование
+    pub fn css_rule(&self, ctx: &ExportContext) -> CssRule {
+        let mut rule = CssRule::new(format!(".{}", ctx.class_name(&self.id)));
+        
+        let mut keys: Vec<&String> = self.styles.keys().collect();
+        keys.sort();
+        for key in keys {
+            rule.declare(key, export_style_value(&self.styles[key], ctx));
+        }
+        
+        rule.declare("position", "absolute".to_string());
+        rule.declare("left", format!("{}px", self.position.0));
+        rule.declare("top", format!("{}px", self.position.1));
+        rule.declare("width", format!("{}px", self.size.0));
+        rule.declare("height", format!("{}px", self.size.1));
+        rule
+    }
+    
+    // Файлы проекта, на которые ссылаются стили (например, фоновое изображение),
+    // в том числе стили других ширин экрана
+    pub fn style_assets(&self) -> Vec<String> {
+        let overrides = self.overrides.values().flat_map(|o| o.styles.values());
+        let mut assets: Vec<String> = self.styles.values()
+            .chain(overrides)
+            .filter_map(|value| css::url_path(value))
+            .filter(|path| is_local_file(path))
+            .collect();
+        assets.sort();
+        assets.dedup();
+        assets
+    }
+    
+    // Заменяет пути к файлам в url() всех стилей элемента: текущих, основных
+    // (пока выбрана другая ширина) и отличий ширин
+    pub fn map_style_assets(&mut self, map: &mut dyn FnMut(&str) -> String) {
+        let base_layout = self.base_layout.as_mut().map(BaseLayout::styles_mut);
+        let overrides = self.overrides.values_mut().map(|o| &mut o.styles);
+        for styles in std::iter::once(&mut self.styles).chain(base_layout).chain(overrides) {
+            for value in styles.values_mut() {
+                if let Some(path) = css::url_path(value).filter(|path| is_local_file(path)) {
+                    *value = css::url(&map(&path));
+                }
+            }
+        }
+    }
+    
+    // Общие HTML-атрибуты элемента: id, класс и дополнительные атрибуты (кроме style)
+    pub fn attributes_html(&self, ctx: &ExportContext) -> String {
+        let mut keys: Vec<&String> = self.attributes.keys()
+            .filter(|k| *k != "style" && *k != "id" && *k != "class")
+            .collect();
+        keys.sort();
+        
+        let mut attributes = format!(" id=\"{}\" class=\"{}\"", escape_html(&self.id), ctx.class_name(&self.id));
+        for key in keys {
+            attributes.push_str(&format!(" {}=\"{}\"", key, escape_html(&self.attributes[key])));
+        }
+        attributes
+    }
+    
     // Метод для проверки, находится ли точка внутри элемента
     pub fn contains_point(&self, point: (f32, f32)) -> bool {
         point.0 >= self.position.0 
@@ -79,4 +298,17 @@ impl ElementBase {
             && point.1 >= self.position.1 
             && point.1 <= self.position.1 + self.size.1
     }
-}
\ No newline at end of file
+}
+
+// Значение стиля для экспорта: файлы проекта в url() указывают на их копии в папке экспорта
+pub fn export_style_value(value: &str, ctx: &ExportContext) -> String {
+    match css::url_path(value).filter(|path| is_local_file(path)) {
+        Some(path) => css::url(&ctx.asset_url(&path)),
+        None => value.to_string(),
+    }
+}
+
+// Путь к файлу на диске, а не адрес в сети или встроенные данные
+fn is_local_file(path: &str) -> bool {
+    !path.contains("://") && !path.starts_with("//") && !path.starts_with("data:")
+}
@@ -0,0 +1,129 @@
+use egui::{Color32, Stroke};
+use std::any::Any;
+
+use crate::elements::{ElementBase, ElementType, UIElement};
+use crate::elements::canvas::CanvasContext;
+use crate::elements::export::ExportContext;
+use crate::utils::html_generator::escape_html;
+
+// Элемент неизвестного этой сборке типа.
+// Хранит исходные данные как есть, чтобы не потерять их при повторном сохранении.
+#[derive(Clone, Debug)]
+pub struct OpaqueElement {
+    pub base: ElementBase,
+    pub data: serde_json::Value,
+}
+
+impl OpaqueElement {
+    pub fn new(element_type: ElementType, data: serde_json::Value) -> Self {
+        // Пытаемся прочитать общие поля, чтобы элемент можно было показать и переместить
+        let mut base = data.get("base")
+            .and_then(|b| serde_json::from_value::<ElementBase>(b.clone()).ok())
+            .unwrap_or_else(|| {
+                let mut base = ElementBase::new(element_type.clone());
+                // Идентификатор из данных сохраняется, иначе ссылки на элемент сломаются после загрузки
+                let id = data.pointer("/base/id").or_else(|| data.get("id")).and_then(|id| id.as_str());
+                if let Some(id) = id.filter(|id| !id.is_empty()) {
+                    base.id = id.to_string();
+                }
+                base
+            });
+        base.element_type = element_type;
+
+        Self { base, data }
+    }
+
+    // Отображаемое имя типа
+    fn type_name(&self) -> String {
+        match &self.base.element_type {
+            ElementType::Custom(name) => name.clone(),
+            other => format!("{:?}", other),
+        }
+    }
+}
+
+impl UIElement for OpaqueElement {
+    fn base(&self) -> &ElementBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut ElementBase {
+        &mut self.base
+    }
+
+    fn get_id(&self) -> &str {
+        &self.base.id
+    }
+
+    fn get_element_type(&self) -> &ElementType {
+        &self.base.element_type
+    }
+
+    fn get_position(&self) -> (f32, f32) {
+        self.base.position
+    }
+
+    fn set_position(&mut self, position: (f32, f32)) {
+        self.base.position = position;
+    }
+
+    fn get_size(&self) -> (f32, f32) {
+        self.base.size
+    }
+
+    fn set_size(&mut self, size: (f32, f32)) {
+        self.base.size = size;
+    }
+
+    fn contains_point(&self, point: (f32, f32)) -> bool {
+        self.base.contains_point(point)
+    }
+
+    fn render(&self, painter: &egui::Painter, canvas: &CanvasContext) {
+        let rect = canvas.rect(&self.base);
+        let selected = canvas.is_selected(&self.base.id);
+
+        painter.rect_filled(rect, 0.0, Color32::from_gray(230));
+        let stroke_color = if selected { Color32::BLUE } else { Color32::GRAY };
+        painter.rect_stroke(rect, 0.0, Stroke::new(1.0, stroke_color));
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            format!("? {}", self.type_name()),
+            egui::FontId::default(),
+            Color32::DARK_GRAY
+        );
+    }
+
+    fn clone_box(&self) -> Box<dyn UIElement> {
+        Box::new(self.clone())
+    }
+
+    // Возвращаем исходные данные, обновив в них только общие поля. Поле base записывается
+    // всегда, чтобы перемещение и изменение размера сохранились и у данных без него.
+    // Данные не в виде объекта остаются как есть: общие поля в них некуда поместить
+    fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+        let mut data = self.data.clone();
+        if let Some(object) = data.as_object_mut() {
+            object.insert("base".to_string(), serde_json::to_value(&self.base)?);
+        }
+        Ok(data)
+    }
+
+    // Содержимое неизвестно, поэтому экспортируем только контейнер с типом
+    fn to_html(&self, ctx: &ExportContext) -> String {
+        format!(
+            "<div{} data-type=\"{}\"></div>",
+            self.base.attributes_html(ctx),
+            escape_html(&self.type_name())
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,232 @@
+// Схемы редактируемых свойств элементов. Элемент перечисляет свойства по разделам
+// (`UIElement::property_schema`), а общий инспектор свойств показывает для каждого
+// подходящий виджет и записывает значение в стили или атрибуты `ElementBase`
+
+use crate::elements::ElementBase;
+
+// Где хранится значение свойства
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PropertyTarget {
+    Style(&'static str),
+    Attribute(&'static str),
+}
+
+impl PropertyTarget {
+    // Ключ в стилях или атрибутах
+    pub fn key(&self) -> &'static str {
+        match self {
+            PropertyTarget::Style(key) | PropertyTarget::Attribute(key) => key,
+        }
+    }
+
+    pub fn get<'a>(&self, base: &'a ElementBase) -> Option<&'a String> {
+        match self {
+            PropertyTarget::Style(key) => base.styles.get(*key),
+            PropertyTarget::Attribute(key) => base.attributes.get(*key),
+        }
+    }
+
+    // None удаляет свойство: элемент возвращается к значению по умолчанию
+    pub fn set(&self, base: &mut ElementBase, value: Option<String>) {
+        let map = match self {
+            PropertyTarget::Style(_) => &mut base.styles,
+            PropertyTarget::Attribute(_) => &mut base.attributes,
+        };
+        match value {
+            Some(value) => {
+                map.insert(self.key().to_string(), value);
+            },
+            None => {
+                map.remove(self.key());
+            },
+        }
+    }
+}
+
+// Вид значения, от которого зависит виджет инспектора
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PropertyKind {
+    Text,
+    Color,
+    // Длина в px, em, rem или %; max - верхний предел перетаскивания для пикселей
+    Length { max: f32 },
+    // Выбор из списка: (значение, название)
+    Enum(&'static [(&'static str, &'static str)]),
+    // Произвольный текст с готовыми вариантами
+    Suggest(&'static [&'static str]),
+    // Флаг: включенный записывает значение, выключенный удаляет свойство
+    Boolean(&'static str),
+    Url,
+    // Файл изображения из проекта
+    Asset,
+}
+
+// Одно редактируемое свойство
+#[derive(Clone, Copy, Debug)]
+pub struct PropertySpec {
+    pub label: &'static str,
+    pub target: PropertyTarget,
+    pub kind: PropertyKind,
+    // Подсказка в пустом поле
+    pub hint: &'static str,
+}
+
+impl PropertySpec {
+    pub fn style(label: &'static str, key: &'static str, kind: PropertyKind) -> Self {
+        Self { label, target: PropertyTarget::Style(key), kind, hint: "" }
+    }
+
+    pub fn attribute(label: &'static str, key: &'static str, kind: PropertyKind) -> Self {
+        Self { label, target: PropertyTarget::Attribute(key), kind, hint: "" }
+    }
+
+    pub fn hint(mut self, hint: &'static str) -> Self {
+        self.hint = hint;
+        self
+    }
+}
+
+// Раздел инспектора с заголовком
+#[derive(Clone, Debug)]
+pub struct PropertySection {
+    pub title: &'static str,
+    pub properties: Vec<PropertySpec>,
+}
+
+impl PropertySection {
+    pub fn new(title: &'static str, properties: Vec<PropertySpec>) -> Self {
+        Self { title, properties }
+    }
+}
+
+const BORDER_STYLES: &[(&str, &str)] = &[
+    ("solid", "Сплошная"),
+    ("dashed", "Штрихи"),
+    ("dotted", "Точки"),
+    ("double", "Двойная"),
+    ("none", "Нет"),
+];
+
+// Распространенные семейства шрифтов
+const FONT_FAMILIES: &[&str] = &[
+    "Arial, sans-serif",
+    "Helvetica, sans-serif",
+    "Verdana, sans-serif",
+    "Georgia, serif",
+    "\"Times New Roman\", serif",
+    "\"Courier New\", monospace",
+];
+
+const FONT_WEIGHTS: &[(&str, &str)] = &[
+    ("300", "Тонкий"),
+    ("400", "Обычный"),
+    ("500", "Средний"),
+    ("600", "Полужирный"),
+    ("700", "Жирный"),
+];
+
+const TEXT_DECORATIONS: &[(&str, &str)] = &[
+    ("none", "Нет"),
+    ("underline", "Подчеркивание"),
+    ("line-through", "Зачеркивание"),
+];
+
+const TEXT_ALIGNS: &[(&str, &str)] = &[
+    ("left", "По левому краю"),
+    ("center", "По центру"),
+    ("right", "По правому краю"),
+    ("justify", "По ширине"),
+];
+
+const CURSORS: &[(&str, &str)] = &[
+    ("default", "Стрелка"),
+    ("pointer", "Рука"),
+    ("text", "Текст"),
+    ("not-allowed", "Запрещено"),
+];
+
+const LOADING: &[(&str, &str)] = &[
+    ("lazy", "При прокрутке"),
+    ("eager", "Сразу"),
+];
+
+pub fn background_section() -> PropertySection {
+    PropertySection::new("Фон", vec![
+        PropertySpec::style("Цвет", "background-color", PropertyKind::Color),
+        PropertySpec::style("Изображение", "background-image", PropertyKind::Asset),
+        PropertySpec::style("Размер изображения", "background-size", PropertyKind::Enum(&[
+            ("cover", "Заполнить"),
+            ("contain", "Вписать"),
+            ("auto", "Исходный"),
+        ])),
+    ])
+}
+
+pub fn border_section() -> PropertySection {
+    PropertySection::new("Рамка", vec![
+        PropertySpec::style("Толщина", "border-width", PropertyKind::Length { max: 50.0 }),
+        PropertySpec::style("Стиль", "border-style", PropertyKind::Enum(BORDER_STYLES)),
+        PropertySpec::style("Цвет", "border-color", PropertyKind::Color),
+        PropertySpec::style("Скругление", "border-radius", PropertyKind::Length { max: 200.0 }),
+    ])
+}
+
+pub fn spacing_section() -> PropertySection {
+    PropertySection::new("Отступы", vec![
+        PropertySpec::style("Внутренние", "padding", PropertyKind::Length { max: 200.0 }).hint("10px 20px"),
+    ])
+}
+
+pub fn typography_section() -> PropertySection {
+    PropertySection::new("Текст", vec![
+        PropertySpec::style("Цвет", "color", PropertyKind::Color),
+        PropertySpec::style("Шрифт", "font-family", PropertyKind::Suggest(FONT_FAMILIES)),
+        PropertySpec::style("Размер", "font-size", PropertyKind::Length { max: 200.0 }),
+        PropertySpec::style("Жирность", "font-weight", PropertyKind::Enum(FONT_WEIGHTS)),
+        PropertySpec::style("Курсив", "font-style", PropertyKind::Boolean("italic")),
+        PropertySpec::style("Межстрочный интервал", "line-height", PropertyKind::Text).hint("1.4"),
+        PropertySpec::style("Выравнивание", "text-align", PropertyKind::Enum(TEXT_ALIGNS)),
+        PropertySpec::style("Линия", "text-decoration", PropertyKind::Enum(TEXT_DECORATIONS)),
+    ])
+}
+
+pub fn effects_section() -> PropertySection {
+    PropertySection::new("Эффекты", vec![
+        PropertySpec::style("Тень", "box-shadow", PropertyKind::Text).hint("0 2px 6px rgba(0, 0, 0, 0.2)"),
+        PropertySpec::style("Курсор", "cursor", PropertyKind::Enum(CURSORS)),
+    ])
+}
+
+pub fn attributes_section() -> PropertySection {
+    PropertySection::new("Атрибуты", vec![
+        PropertySpec::attribute("Подсказка (title)", "title", PropertyKind::Text),
+        PropertySpec::attribute("Описание (aria-label)", "aria-label", PropertyKind::Text),
+    ])
+}
+
+// Микроразметка schema.org для блоков с содержимым
+pub fn microdata_section() -> PropertySection {
+    PropertySection::new("Микроразметка", vec![
+        PropertySpec::attribute("Отдельная сущность", "itemscope", PropertyKind::Boolean("itemscope")),
+        PropertySpec::attribute("Тип (itemtype)", "itemtype", PropertyKind::Url).hint("https://schema.org/Product"),
+    ])
+}
+
+// Загрузка изображения (атрибут loading у <img>)
+pub fn image_attributes_section() -> PropertySection {
+    let mut section = attributes_section();
+    section.properties.push(PropertySpec::attribute("Загрузка", "loading", PropertyKind::Enum(LOADING)));
+    section
+}
+
+// Все общие разделы: для элементов, у которых нет особых ограничений по стилям
+pub fn default_schema() -> Vec<PropertySection> {
+    vec![
+        background_section(),
+        border_section(),
+        spacing_section(),
+        typography_section(),
+        effects_section(),
+        attributes_section(),
+    ]
+}
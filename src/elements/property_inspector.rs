@@ -0,0 +1,227 @@
+// Общий инспектор свойств: по схеме элемента показывает разделы со свойствами
+// и подходящими виджетами, значения записываются в стили или атрибуты элемента
+
+use egui::{Color32, Ui};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::app::project;
+use crate::elements::{ElementBase, UIElement};
+use crate::elements::color_picker::ColorPicker;
+use crate::elements::css::{self, Length, ROOT_FONT_SIZE};
+use crate::elements::properties::{PropertyKind, PropertySpec, PropertyTarget};
+
+// Единицы длины в порядке списка выбора
+const LENGTH_UNITS: [&str; 4] = ["px", "em", "rem", "%"];
+
+// Состояние инспектора между кадрами
+#[derive(Default)]
+pub struct PropertyInspector {
+    // Пути к файлам для импорта по ключу свойства
+    import_paths: HashMap<&'static str, String>,
+    // Ошибка последнего импорта
+    import_error: Option<String>,
+    // Палитры цветовых свойств по ключу свойства
+    color_pickers: HashMap<&'static str, ColorPicker>,
+}
+
+impl PropertyInspector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Разделы схемы элемента; возвращает, изменилось ли что-нибудь
+    pub fn show(&mut self, ui: &mut Ui, element: &mut Box<dyn UIElement>, project_path: Option<&Path>) -> bool {
+        let sections = element.property_schema();
+        let base = element.base_mut();
+        let mut changed = false;
+
+        for section in &sections {
+            egui::CollapsingHeader::new(section.title)
+                .id_source(("property_section", section.title))
+                .show(ui, |ui| {
+                    egui::Grid::new(("property_grid", section.title)).num_columns(2).show(ui, |ui| {
+                        for spec in &section.properties {
+                            ui.label(spec.label);
+                            changed |= self.show_property(ui, base, spec, project_path);
+                            ui.end_row();
+                        }
+                    });
+                });
+        }
+
+        changed
+    }
+
+    // Виджет одного свойства и кнопка сброса к значению по умолчанию
+    fn show_property(&mut self, ui: &mut Ui, base: &mut ElementBase, spec: &PropertySpec, project_path: Option<&Path>) -> bool {
+        let current = spec.target.get(base).cloned();
+        let id_source = ("property", spec.target.key());
+
+        let edited = ui.horizontal(|ui| {
+            let edited = match spec.kind {
+                PropertyKind::Text => show_text(ui, current.as_deref(), spec.hint),
+                PropertyKind::Url => show_url(ui, current.as_deref(), spec.hint),
+                PropertyKind::Color => self.color_pickers.entry(spec.target.key()).or_default()
+                    .show(ui, current.as_deref())
+                    .map(Some),
+                PropertyKind::Length { max } => show_length(ui, id_source, current.as_deref(), max, spec.hint),
+                PropertyKind::Enum(options) => show_enum(ui, id_source, current.as_deref(), options),
+                PropertyKind::Suggest(options) => show_suggest(ui, id_source, current.as_deref(), options, spec.hint),
+                PropertyKind::Boolean(on) => {
+                    let mut checked = current.as_deref() == Some(on);
+                    ui.checkbox(&mut checked, "")
+                        .changed()
+                        .then(|| checked.then(|| on.to_string()))
+                },
+                PropertyKind::Asset => self.show_asset(ui, spec.target, current.as_deref(), project_path),
+            };
+
+            let resettable = !matches!(spec.kind, PropertyKind::Boolean(_));
+            if resettable && current.is_some() && ui.small_button("✕").on_hover_text("Сбросить").clicked() {
+                return Some(None);
+            }
+            edited
+        }).inner;
+
+        match edited {
+            Some(value) if value != current => {
+                spec.target.set(base, value);
+                true
+            },
+            _ => false,
+        }
+    }
+
+    // Файл изображения: имя выбранного файла и импорт нового в папку проекта
+    fn show_asset(&mut self, ui: &mut Ui, target: PropertyTarget, current: Option<&str>, project_path: Option<&Path>) -> Option<Option<String>> {
+        let path = match target {
+            PropertyTarget::Style(_) => current.and_then(css::url_path),
+            PropertyTarget::Attribute(_) => current.map(str::to_string),
+        };
+        let mut edited = None;
+
+        ui.vertical(|ui| {
+            let file_name = path.as_deref()
+                .and_then(|path| Path::new(path).file_name())
+                .map(|name| name.to_string_lossy().to_string());
+            ui.label(file_name.unwrap_or_else(|| "не выбран".to_string()));
+
+            ui.horizontal(|ui| {
+                let import_path = self.import_paths.entry(target.key()).or_default();
+                ui.add(egui::TextEdit::singleline(import_path).hint_text("Путь к файлу").desired_width(120.0));
+                if ui.button("Импорт").clicked() {
+                    match project::import_asset(project_path, Path::new(import_path.trim())) {
+                        Ok(path) => {
+                            edited = Some(Some(match target {
+                                PropertyTarget::Style(_) => css::url(&path),
+                                PropertyTarget::Attribute(_) => path,
+                            }));
+                            self.import_error = None;
+                        },
+                        Err(e) => self.import_error = Some(e.to_string()),
+                    }
+                }
+            });
+
+            if let Some(error) = &self.import_error {
+                ui.colored_label(Color32::RED, error);
+            }
+        });
+
+        edited
+    }
+}
+
+// Виджеты возвращают None, если значение не менялось, и Some(None), если свойство удалено
+fn show_text(ui: &mut Ui, current: Option<&str>, hint: &str) -> Option<Option<String>> {
+    let mut text = current.unwrap_or_default().to_string();
+    let response = ui.add(egui::TextEdit::singleline(&mut text).hint_text(hint));
+    response.changed().then(|| (!text.trim().is_empty()).then_some(text))
+}
+
+// Адрес в сети, почта, телефон или путь на сайте; подозрительный адрес отмечается
+fn show_url(ui: &mut Ui, current: Option<&str>, hint: &str) -> Option<Option<String>> {
+    let edited = show_text(ui, current, hint);
+    let value = edited.clone().unwrap_or_else(|| current.map(str::to_string)).unwrap_or_default();
+    let valid = ["http://", "https://", "mailto:", "tel:", "/", "#", "./", "../"].iter()
+        .any(|prefix| value.trim().starts_with(prefix));
+    if !value.trim().is_empty() && !valid {
+        ui.colored_label(Color32::from_rgb(230, 140, 0), "⚠")
+            .on_hover_text("Адрес должен начинаться с https://, mailto:, tel: или /");
+    }
+    edited
+}
+
+// Число и единица; сокращенная запись из нескольких значений редактируется текстом
+fn show_length(ui: &mut Ui, id_source: (&str, &str), current: Option<&str>, max: f32, hint: &str) -> Option<Option<String>> {
+    let parsed = match current {
+        Some(value) => match Length::parse(value) {
+            Some(length) => length,
+            None => return show_text(ui, current, hint),
+        },
+        None => Length::Px(0.0),
+    };
+    let (mut number, mut unit) = match parsed {
+        Length::Px(n) => (n, "px"),
+        Length::Em(n) => (n, "em"),
+        Length::Rem(n) => (n, "rem"),
+        Length::Percent(n) => (n, "%"),
+    };
+
+    // em и rem - доли размера шрифта, поэтому меняются мельче пикселей
+    let relative = unit == "em" || unit == "rem";
+    let (speed, limit) = if relative { (0.05, max / ROOT_FONT_SIZE) } else { (1.0, max) };
+    let mut changed = ui.add(egui::DragValue::new(&mut number).speed(speed).clamp_range(0.0..=limit)).changed();
+    egui::ComboBox::from_id_source(id_source)
+        .width(56.0)
+        .selected_text(unit)
+        .show_ui(ui, |ui| {
+            for option in LENGTH_UNITS {
+                changed |= ui.selectable_value(&mut unit, option, option).changed();
+            }
+        });
+
+    changed.then(|| Some(format!("{}{}", (number * 100.0).round() / 100.0, unit)))
+}
+
+fn show_enum(ui: &mut Ui, id_source: (&str, &str), current: Option<&str>, options: &[(&str, &str)]) -> Option<Option<String>> {
+    let selected = match current {
+        Some(value) => options.iter()
+            .find(|(option, _)| *option == value)
+            .map(|(_, label)| label.to_string())
+            .unwrap_or_else(|| value.to_string()),
+        None => "—".to_string(),
+    };
+
+    let mut edited = None;
+    egui::ComboBox::from_id_source(id_source)
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            if ui.selectable_label(current.is_none(), "—").clicked() {
+                edited = Some(None);
+            }
+            for (value, label) in options {
+                if ui.selectable_label(current == Some(*value), *label).clicked() {
+                    edited = Some(Some(value.to_string()));
+                }
+            }
+        });
+    edited
+}
+
+// Текстовое поле и список готовых вариантов
+fn show_suggest(ui: &mut Ui, id_source: (&str, &str), current: Option<&str>, options: &[&str], hint: &str) -> Option<Option<String>> {
+    let mut edited = show_text(ui, current, hint);
+    egui::ComboBox::from_id_source(id_source)
+        .width(24.0)
+        .selected_text("")
+        .show_ui(ui, |ui| {
+            for option in options {
+                if ui.selectable_label(current == Some(*option), *option).clicked() {
+                    edited = Some(Some(option.to_string()));
+                }
+            }
+        });
+    edited
+}
@@ -0,0 +1,306 @@
+// Реестр типов элементов: по `ElementType` находит функцию, которая
+// восстанавливает `Box<dyn UIElement>` из сохраненных данных.
+// Новые типы (в том числе `ElementType::Custom`) регистрируются через
+// `register_element`, без изменений в `Page` и в формате проекта.
+// Палитра редактора строится из видов элементов (`ElementKind`), зарегистрированных
+// через `register_element_kind`: новый вид появляется в палитре без правок редактора.
+// Встроенные элементы регистрируются тем же способом при запуске приложения
+// (`register_builtin_elements`).
+
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+use crate::elements::{ElementType, UIElement};
+use crate::elements::buttons::button::Button;
+use crate::elements::containers::container::Container;
+use crate::elements::forms::form::Form;
+use crate::elements::forms::form_field::{FieldKind, FormField};
+use crate::elements::images::image::Image;
+use crate::elements::links::link::Link;
+use crate::elements::opaque::OpaqueElement;
+use crate::elements::texts::text::{Text, TextSpan, TextTag};
+
+// Функция восстановления элемента из JSON
+pub type ElementDeserializer = fn(serde_json::Value) -> Result<Box<dyn UIElement>, serde_json::Error>;
+
+fn registry() -> &'static RwLock<HashMap<ElementType, ElementDeserializer>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<ElementType, ElementDeserializer>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+// Регистрирует встроенные типы элементов и их виды в палитре.
+// Повторный вызов ничего не меняет: записи с теми же ключами заменяются
+pub fn register_builtin_elements() {
+    register_element(ElementType::Button, deserialize_as::<Button>);
+    register_element(ElementType::Text, deserialize_as::<Text>);
+    register_element(ElementType::Image, deserialize_as::<Image>);
+    register_element(ElementType::Container, deserialize_as::<Container>);
+    register_element(ElementType::Form, deserialize_as::<Form>);
+    register_element(ElementType::Input, deserialize_as::<FormField>);
+    register_element(ElementType::Link, deserialize_as::<Link>);
+
+    for kind in builtin_element_kinds() {
+        register_element_kind(kind);
+    }
+}
+
+// Восстанавливает элемент конкретного типа через serde
+pub fn deserialize_as<T>(data: serde_json::Value) -> Result<Box<dyn UIElement>, serde_json::Error>
+where
+    T: UIElement + DeserializeOwned + 'static,
+{
+    serde_json::from_value::<T>(data).map(|element| Box::new(element) as Box<dyn UIElement>)
+}
+
+// Регистрирует (или заменяет) функцию восстановления для типа элемента
+pub fn register_element(element_type: ElementType, deserializer: ElementDeserializer) {
+    if let Ok(mut deserializers) = registry().write() {
+        deserializers.insert(element_type, deserializer);
+    }
+}
+
+// Восстанавливает элемент по типу. Незарегистрированные типы не теряются,
+// а сохраняются как непрозрачные данные и записываются обратно без изменений.
+pub fn deserialize_element(element_type: &ElementType, data: serde_json::Value) -> Result<Box<dyn UIElement>, String> {
+    let deserializer = registry().read()
+        .map_err(|e| e.to_string())?
+        .get(element_type)
+        .copied();
+
+    match deserializer {
+        Some(deserialize) => deserialize(data).map_err(|e| format!("{:?}: {}", element_type, e)),
+        None => Ok(Box::new(OpaqueElement::new(element_type.clone(), data))),
+    }
+}
+
+// Функция, создающая новый элемент для палитры
+pub type ElementFactory = fn() -> Box<dyn UIElement>;
+
+// Вид элемента в палитре редактора. Один тип элемента может давать несколько видов
+// (например, поле ввода и флажок - это поля формы)
+#[derive(Clone, Debug)]
+pub struct ElementKind {
+    // Уникальный ключ вида
+    pub id: String,
+    pub name: String,
+    // Значок перед названием
+    pub icon: String,
+    // Раздел палитры
+    pub category: String,
+    pub factory: ElementFactory,
+    // Размер нового элемента
+    pub default_size: (f32, f32),
+}
+
+impl ElementKind {
+    pub fn new(id: &str, name: &str, icon: &str, category: &str, factory: ElementFactory, default_size: (f32, f32)) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            icon: icon.to_string(),
+            category: category.to_string(),
+            factory,
+            default_size,
+        }
+    }
+
+    // Новый элемент этого вида
+    pub fn create(&self) -> Box<dyn UIElement> {
+        let mut element = (self.factory)();
+        element.set_size(self.default_size);
+        element
+    }
+
+    // Подходит ли вид под строку поиска (по названию или разделу, без учета регистра)
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || self.name.to_lowercase().contains(&query)
+            || self.category.to_lowercase().contains(&query)
+    }
+}
+
+fn kinds() -> &'static RwLock<Vec<ElementKind>> {
+    static KINDS: OnceLock<RwLock<Vec<ElementKind>>> = OnceLock::new();
+    KINDS.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+// Встроенные виды элементов в порядке палитры
+fn builtin_element_kinds() -> Vec<ElementKind> {
+    vec![
+        ElementKind::new("heading", "Заголовок", "H", "Основные", create_heading, (400.0, 50.0)),
+        ElementKind::new("text", "Текст", "¶", "Основные", || Box::new(Text::new()), (300.0, 60.0)),
+        ElementKind::new("button", "Кнопка", "⏺", "Основные", || Box::new(Button::new()), (100.0, 50.0)),
+        ElementKind::new("link", "Ссылка", "🔗", "Основные", || Box::new(Link::new()), (160.0, 24.0)),
+        ElementKind::new("image", "Изображение", "🖼", "Медиа", || Box::new(Image::new()), (200.0, 150.0)),
+        ElementKind::new("container", "Контейнер", "⬜", "Раскладка", || Box::new(Container::new()), (300.0, 200.0)),
+        ElementKind::new("form", "Форма", "📋", "Формы", || Box::new(Form::new()), (320.0, 260.0)),
+        ElementKind::new("input", "Поле ввода", "✏", "Формы", || Box::new(FormField::new(FieldKind::Text)), (260.0, 56.0)),
+        ElementKind::new("textarea", "Многострочное поле", "☰", "Формы", || Box::new(FormField::new(FieldKind::Textarea)), (260.0, 110.0)),
+        ElementKind::new("select", "Выпадающий список", "⏷", "Формы", || Box::new(FormField::new(FieldKind::Select)), (260.0, 56.0)),
+        ElementKind::new("checkbox", "Флажок", "☑", "Формы", || Box::new(FormField::new(FieldKind::Checkbox)), (260.0, 24.0)),
+        ElementKind::new("submit", "Кнопка отправки", "➡", "Формы", || Box::new(FormField::new(FieldKind::Submit)), (140.0, 40.0)),
+    ]
+}
+
+// Заголовок второго уровня: жирный текст с размером шрифта тега
+fn create_heading() -> Box<dyn UIElement> {
+    let mut text = Text::new();
+    text.set_tag(TextTag::H2);
+    text.base.styles.insert("font-weight".to_string(), "700".to_string());
+    text.spans = vec![TextSpan::new("Заголовок")];
+    Box::new(text)
+}
+
+// Регистрирует вид элемента в палитре; вид с тем же ключом заменяется
+pub fn register_element_kind(kind: ElementKind) {
+    if let Ok(mut kinds) = kinds().write() {
+        match kinds.iter_mut().find(|k| k.id == kind.id) {
+            Some(existing) => *existing = kind,
+            None => kinds.push(kind),
+        }
+    }
+}
+
+// Зарегистрированные виды элементов в порядке регистрации
+pub fn element_kinds() -> Vec<ElementKind> {
+    kinds().read().map(|kinds| kinds.clone()).unwrap_or_default()
+}
+
+// Вид элемента по ключу
+pub fn find_element_kind(id: &str) -> Option<ElementKind> {
+    kinds().read().ok()?.iter().find(|kind| kind.id == id).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Serialize, Deserialize};
+    use std::any::Any;
+    use crate::elements::ElementBase;
+    use crate::elements::canvas::CanvasContext;
+    use crate::elements::export::ExportContext;
+    use crate::models::page::Page;
+
+    // Элемент, которого нет среди встроенных: регистрируется так же, как расширение
+    #[derive(Clone, Serialize, Deserialize)]
+    struct Badge {
+        base: ElementBase,
+        label: String,
+    }
+
+    impl UIElement for Badge {
+        fn base(&self) -> &ElementBase {
+            &self.base
+        }
+
+        fn base_mut(&mut self) -> &mut ElementBase {
+            &mut self.base
+        }
+
+        fn get_id(&self) -> &str {
+            &self.base.id
+        }
+
+        fn get_element_type(&self) -> &ElementType {
+            &self.base.element_type
+        }
+
+        fn get_position(&self) -> (f32, f32) {
+            self.base.position
+        }
+
+        fn set_position(&mut self, position: (f32, f32)) {
+            self.base.position = position;
+        }
+
+        fn get_size(&self) -> (f32, f32) {
+            self.base.size
+        }
+
+        fn set_size(&mut self, size: (f32, f32)) {
+            self.base.size = size;
+        }
+
+        fn contains_point(&self, point: (f32, f32)) -> bool {
+            self.base.contains_point(point)
+        }
+
+        fn render(&self, _painter: &egui::Painter, _canvas: &CanvasContext) {}
+
+        fn clone_box(&self) -> Box<dyn UIElement> {
+            Box::new(self.clone())
+        }
+
+        fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+            serde_json::to_value(self)
+        }
+
+        fn to_html(&self, _ctx: &ExportContext) -> String {
+            self.label.clone()
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    fn create_badge() -> Box<dyn UIElement> {
+        Box::new(Badge {
+            base: ElementBase::new(ElementType::Custom("badge".to_string())),
+            label: "Новинка".to_string(),
+        })
+    }
+
+    fn page_with(elements: Vec<Box<dyn UIElement>>) -> Page {
+        let mut page = Page::new("home".to_string(), "Главная".to_string(), "index.html".to_string());
+        page.elements = elements;
+        page
+    }
+
+    #[test]
+    fn registered_custom_kind_round_trips() {
+        register_builtin_elements();
+        register_element(ElementType::Custom("badge".to_string()), deserialize_as::<Badge>);
+        register_element_kind(ElementKind::new("badge", "Значок", "★", "Основные", create_badge, (80.0, 24.0)));
+
+        let kind = find_element_kind("badge").unwrap();
+        assert!(element_kinds().iter().any(|k| k.id == "heading"));
+        let element = kind.create();
+        assert_eq!(element.get_size(), (80.0, 24.0));
+
+        let json = serde_json::to_value(page_with(vec![element])).unwrap();
+        let page: Page = serde_json::from_value(json).unwrap();
+        let badge = page.elements[0].as_any().downcast_ref::<Badge>().unwrap();
+        assert_eq!(badge.label, "Новинка");
+        assert_eq!(badge.get_size(), (80.0, 24.0));
+    }
+
+    #[test]
+    fn unknown_type_survives_load_and_save() {
+        register_builtin_elements();
+        let base = ElementBase::new(ElementType::Custom("carousel".to_string()));
+        let elements = serde_json::json!([{
+            "type": { "Custom": "carousel" },
+            "data": {
+                "base": serde_json::to_value(&base).unwrap(),
+                "slides": ["a.png", "b.png"],
+                "interval": 5,
+            },
+        }]);
+        let mut json = serde_json::to_value(page_with(Vec::new())).unwrap();
+        json["elements"] = elements.clone();
+
+        let page: Page = serde_json::from_value(json).unwrap();
+        let opaque = page.elements[0].as_any().downcast_ref::<OpaqueElement>().unwrap();
+        assert_eq!(opaque.get_id(), base.id);
+
+        let saved = serde_json::to_value(&page).unwrap();
+        assert_eq!(saved["elements"], elements);
+    }
+}
@@ -0,0 +1,45 @@
+// Сериализация списка элементов `Vec<Box<dyn UIElement>>` для `#[serde(with = ...)]`.
+// Каждый элемент сохраняется как `{ "type": ElementType, "data": {...} }`,
+// а при загрузке восстанавливается по значению `type` через реестр типов.
+
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use serde::de::Error as _;
+use serde::ser::Error as _;
+
+use crate::elements::{ElementType, UIElement};
+use crate::elements::registry;
+
+#[derive(Serialize, Deserialize)]
+struct TaggedElement {
+    #[serde(rename = "type")]
+    element_type: ElementType,
+    data: serde_json::Value,
+}
+
+pub fn serialize<S>(elements: &[Box<dyn UIElement>], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    // Ошибка сериализации элемента прерывает сохранение, а не записывает null
+    let tagged = elements
+        .iter()
+        .map(|element| Ok(TaggedElement {
+            element_type: element.get_element_type().clone(),
+            data: element.to_json().map_err(S::Error::custom)?,
+        }))
+        .collect::<Result<Vec<_>, S::Error>>()?;
+
+    tagged.serialize(serializer)
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Box<dyn UIElement>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let tagged = Vec::<TaggedElement>::deserialize(deserializer)?;
+
+    tagged
+        .into_iter()
+        .map(|item| registry::deserialize_element(&item.element_type, item.data).map_err(D::Error::custom))
+        .collect()
+}
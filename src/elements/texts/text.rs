@@ -0,0 +1,402 @@
+use serde::{Serialize, Deserialize};
+use egui::{Pos2, Rect, Vec2, Color32, Stroke, Align};
+use egui::text::{LayoutJob, TextFormat};
+use crate::elements::{ElementBase, ElementType, UIElement};
+use crate::elements::canvas::CanvasContext;
+use crate::elements::css::{self, Border};
+use crate::elements::export::{CssRule, ExportContext};
+use crate::utils::html_generator::escape_html;
+use std::any::Any;
+
+// Семантический тег текстового блока
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum TextTag {
+    H1,
+    H2,
+    H3,
+    H4,
+    H5,
+    H6,
+    Paragraph,
+}
+
+impl TextTag {
+    pub const ALL: [TextTag; 7] = [
+        TextTag::H1, TextTag::H2, TextTag::H3, TextTag::H4, TextTag::H5, TextTag::H6, TextTag::Paragraph,
+    ];
+    
+    // Имя HTML-тега
+    pub fn html_tag(&self) -> &'static str {
+        match self {
+            TextTag::H1 => "h1",
+            TextTag::H2 => "h2",
+            TextTag::H3 => "h3",
+            TextTag::H4 => "h4",
+            TextTag::H5 => "h5",
+            TextTag::H6 => "h6",
+            TextTag::Paragraph => "p",
+        }
+    }
+    
+    // Название для интерфейса
+    pub fn label(&self) -> &'static str {
+        match self {
+            TextTag::H1 => "Заголовок 1",
+            TextTag::H2 => "Заголовок 2",
+            TextTag::H3 => "Заголовок 3",
+            TextTag::H4 => "Заголовок 4",
+            TextTag::H5 => "Заголовок 5",
+            TextTag::H6 => "Заголовок 6",
+            TextTag::Paragraph => "Абзац",
+        }
+    }
+    
+    // Размер шрифта по умолчанию (как в стилях браузера)
+    pub fn default_font_size(&self) -> f32 {
+        match self {
+            TextTag::H1 => 32.0,
+            TextTag::H2 => 24.0,
+            TextTag::H3 => 19.0,
+            TextTag::H4 => 16.0,
+            TextTag::H5 => 13.0,
+            TextTag::H6 => 11.0,
+            TextTag::Paragraph => 16.0,
+        }
+    }
+}
+
+// Фрагмент текста с собственным форматированием
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TextSpan {
+    pub text: String,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    // Адрес ссылки, если фрагмент является ссылкой
+    pub link: Option<String>,
+}
+
+impl TextSpan {
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+    
+    // HTML фрагмента с вложенными тегами форматирования
+    fn to_html(&self) -> String {
+        let mut html = escape_html(&self.text).replace('\n', "<br>");
+        if self.underline {
+            html = format!("<u>{}</u>", html);
+        }
+        if self.italic {
+            html = format!("<em>{}</em>", html);
+        }
+        if self.bold {
+            html = format!("<strong>{}</strong>", html);
+        }
+        if let Some(link) = &self.link {
+            html = format!("<a href=\"{}\">{}</a>", escape_html(link), html);
+        }
+        html
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Text {
+    pub base: ElementBase,
+    pub tag: TextTag,
+    pub spans: Vec<TextSpan>,
+}
+
+impl Default for Text {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Text {
+    pub fn new() -> Self {
+        let mut base = ElementBase::new(ElementType::Text);
+        base.size = (300.0, 60.0);
+        
+        // Устанавливаем стили по умолчанию
+        base.styles.insert("font-family".to_string(), "Arial, sans-serif".to_string());
+        base.styles.insert("font-size".to_string(), "16px".to_string());
+        base.styles.insert("font-weight".to_string(), "400".to_string());
+        base.styles.insert("line-height".to_string(), "1.4".to_string());
+        base.styles.insert("text-align".to_string(), "left".to_string());
+        base.styles.insert("color".to_string(), "#333333".to_string());
+        
+        Self {
+            base,
+            tag: TextTag::Paragraph,
+            spans: vec![TextSpan::new("Текст")],
+        }
+    }
+    
+    // Смена тега. Размер шрифта становится принятым для нового тега по умолчанию,
+    // только если пользователь не задал свой (размер равен размеру прежнего тега)
+    pub fn set_tag(&mut self, tag: TextTag) {
+        let default_size = |tag: TextTag| format!("{}px", tag.default_font_size());
+        let untouched = self.base.styles.get("font-size").is_none_or(|size| size.trim() == default_size(self.tag));
+        if untouched {
+            self.base.styles.insert("font-size".to_string(), default_size(tag));
+        }
+        self.tag = tag;
+    }
+    
+    // Размер шрифта в пикселях
+    pub fn font_size(&self) -> f32 {
+        css::font_size(&self.base.styles, self.tag.default_font_size())
+    }
+    
+    // Жирность шрифта (100-900)
+    pub fn font_weight(&self) -> u32 {
+        match self.base.styles.get("font-weight").map(|s| s.trim()) {
+            Some("bold") => 700,
+            Some("normal") | None => 400,
+            Some(value) => value.parse::<u32>().unwrap_or(400),
+        }
+    }
+    
+    // Межстрочный интервал в пикселях
+    fn line_height(&self, font_size: f32) -> f32 {
+        css::line_height(&self.base.styles, font_size)
+    }
+    
+    // Область текста внутри рамки и внутренних отступов; scale - масштаб холста
+    fn content_rect(&self, rect: Rect, scale: f32) -> Rect {
+        let styles = &self.base.styles;
+        let font_size = self.font_size();
+        let border = Border::from_styles(styles, font_size, Border::none()).widths();
+        let padding = css::edges(styles, "padding", font_size, self.base.size.0);
+        let inset = |side: usize| (border[side] + padding[side]) * scale;
+        let min = rect.min + Vec2::new(inset(3), inset(0));
+        let max = rect.max - Vec2::new(inset(1), inset(2));
+        Rect::from_min_max(min, max.max(min))
+    }
+    
+    // Цвет текста из стилей
+    fn text_color(&self) -> Color32 {
+        css::color(&self.base.styles, "color").unwrap_or(Color32::from_gray(51))
+    }
+    
+    // Семейство шрифта egui, наиболее близкое к CSS font-family
+    fn font_family(&self) -> egui::FontFamily {
+        let family = self.base.styles.get("font-family").map(|s| s.to_lowercase()).unwrap_or_default();
+        if family.contains("mono") || family.contains("courier") {
+            egui::FontFamily::Monospace
+        } else {
+            egui::FontFamily::Proportional
+        }
+    }
+    
+    // Выравнивание текста по горизонтали
+    fn text_align(&self) -> Align {
+        match self.base.styles.get("text-align").map(|s| s.trim()) {
+            Some("center") => Align::Center,
+            Some("right") | Some("end") => Align::Max,
+            _ => Align::Min,
+        }
+    }
+    
+    // Строит разметку текста для egui.
+    // В слое `bold_layer` видимы только жирные фрагменты: его рисуют поверх со сдвигом,
+    // потому что встроенные шрифты egui не имеют жирного начертания.
+    fn layout_job(&self, bold_layer: bool, scale: f32) -> LayoutJob {
+        let font_size = self.font_size() * scale;
+        let font_id = egui::FontId::new(font_size, self.font_family());
+        let color = self.text_color();
+        let link_color = Color32::from_rgb(0, 0, 238);
+        let element_bold = self.font_weight() >= 600;
+        
+        let mut job = LayoutJob::default();
+        let size = Vec2::new(self.base.size.0, self.base.size.1) * scale;
+        job.wrap.max_width = self.content_rect(Rect::from_min_size(Pos2::ZERO, size), scale).width().max(1.0);
+        job.halign = self.text_align();
+        job.justify = self.base.styles.get("text-align").map(|s| s.trim() == "justify").unwrap_or(false);
+        
+        for span in &self.spans {
+            let span_color = if span.link.is_some() { link_color } else { color };
+            let visible = !bold_layer || span.bold || element_bold;
+            let underline = span.underline || span.link.is_some();
+            
+            let format = TextFormat {
+                font_id: font_id.clone(),
+                line_height: Some(self.line_height(self.font_size()) * scale),
+                color: if visible { span_color } else { Color32::TRANSPARENT },
+                italics: span.italic,
+                underline: if underline && !bold_layer { Stroke::new(scale, span_color) } else { Stroke::NONE },
+                ..Default::default()
+            };
+            job.append(&span.text, 0.0, format);
+        }
+        
+        job
+    }
+    
+    // Есть ли в тексте жирные фрагменты
+    fn has_bold(&self) -> bool {
+        self.font_weight() >= 600 || self.spans.iter().any(|s| s.bold)
+    }
+}
+
+impl UIElement for Text {
+    fn base(&self) -> &ElementBase {
+        &self.base
+    }
+    
+    fn base_mut(&mut self) -> &mut ElementBase {
+        &mut self.base
+    }
+    
+    fn get_id(&self) -> &str {
+        &self.base.id
+    }
+    
+    fn get_element_type(&self) -> &ElementType {
+        &self.base.element_type
+    }
+    
+    fn get_position(&self) -> (f32, f32) {
+        self.base.position
+    }
+    
+    fn set_position(&mut self, position: (f32, f32)) {
+        self.base.position = position;
+    }
+    
+    fn get_size(&self) -> (f32, f32) {
+        self.base.size
+    }
+    
+    fn set_size(&mut self, size: (f32, f32)) {
+        self.base.size = size;
+    }
+    
+    fn clone_box(&self) -> Box<dyn UIElement> {
+        Box::new(self.clone())
+    }
+    
+    fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(self)
+    }
+    
+    // Возвращает семантическую HTML-разметку текста
+    fn to_html(&self, ctx: &ExportContext) -> String {
+        let content: String = self.spans.iter().map(|span| span.to_html()).collect();
+        let tag = self.tag.html_tag();
+        format!("<{}{}>{}</{}>", tag, self.base.attributes_html(ctx), content, tag)
+    }
+    
+    // На холсте отступы и рамка лежат внутри размера элемента, а у заголовков и абзацев
+    // в браузере есть внешние отступы по умолчанию; убираем их, чтобы текст стоял на месте
+    fn to_css(&self, ctx: &ExportContext) -> Vec<CssRule> {
+        let mut rule = self.base.css_rule(ctx);
+        if !self.base.styles.contains_key("margin") {
+            rule.declare("margin", "0".to_string());
+        }
+        rule.declare("box-sizing", "border-box".to_string());
+        vec![rule]
+    }
+    
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    
+    fn contains_point(&self, point: (f32, f32)) -> bool {
+        self.base.contains_point(point)
+    }
+    
+    fn render(&self, painter: &egui::Painter, canvas: &CanvasContext) {
+        let element_rect = canvas.rect(&self.base);
+        let selected = canvas.is_selected(&self.base.id);
+        
+        canvas.paint_background(painter, &self.base, Color32::TRANSPARENT);
+        
+        // Текст располагается внутри рамки и внутренних отступов
+        let content_rect = self.content_rect(element_rect, canvas.scale);
+        
+        // Точка привязки зависит от выравнивания, как у Label в egui
+        let anchor_x = match self.text_align() {
+            Align::Center => content_rect.center().x,
+            Align::Max => content_rect.right(),
+            Align::Min => content_rect.left(),
+        };
+        let anchor = Pos2::new(anchor_x, content_rect.top());
+        
+        let galley = painter.fonts(|f| f.layout_job(self.layout_job(false, canvas.scale)));
+        painter.galley(anchor, galley);
+        
+        if self.has_bold() {
+            let bold_galley = painter.fonts(|f| f.layout_job(self.layout_job(true, canvas.scale)));
+            painter.galley(anchor + Vec2::new(0.6 * canvas.scale, 0.0), bold_galley);
+        }
+        
+        canvas.paint_border(painter, &self.base, Border::none());
+        
+        if selected {
+            painter.rect_stroke(element_rect, 0.0, Stroke::new(1.0, Color32::BLUE));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans_nest_formatting_inside_links_and_escape_text() {
+        let span = TextSpan {
+            text: "a < b & \"c\"\nd".to_string(),
+            bold: true,
+            italic: true,
+            underline: true,
+            link: Some("/search?q=1&x=\"2\"".to_string()),
+        };
+        assert_eq!(
+            span.to_html(),
+            "<a href=\"/search?q=1&amp;x=&quot;2&quot;\"><strong><em><u>a &lt; b &amp; &quot;c&quot;<br>d</u></em></strong></a>"
+        );
+        assert_eq!(TextSpan::new("<b>").to_html(), "&lt;b&gt;");
+    }
+
+    #[test]
+    fn tag_change_keeps_font_size_set_by_user() {
+        let mut text = Text::new();
+        text.set_tag(TextTag::H1);
+        assert_eq!(text.base.styles["font-size"], "32px");
+
+        text.base.styles.insert("font-size".to_string(), "40px".to_string());
+        text.set_tag(TextTag::H3);
+        assert_eq!(text.base.styles["font-size"], "40px");
+        assert_eq!(text.tag, TextTag::H3);
+    }
+
+    #[test]
+    fn exported_text_has_no_browser_margins() {
+        let mut text = Text::new();
+        text.set_tag(TextTag::H2);
+        text.base.styles.insert("padding".to_string(), "8px".to_string());
+        let ctx = ExportContext::default();
+
+        let html = text.to_html(&ctx);
+        assert!(html.starts_with("<h2 ") && html.ends_with("</h2>"));
+        let rules = text.to_css(&ctx);
+        assert_eq!(rules.len(), 1);
+        let declarations = &rules[0].declarations;
+        assert!(declarations.contains(&("margin".to_string(), "0".to_string())));
+        assert!(declarations.contains(&("box-sizing".to_string(), "border-box".to_string())));
+
+        // Внешний отступ, заданный пользователем, не перезаписывается
+        text.base.styles.insert("margin".to_string(), "4px".to_string());
+        let declarations = &text.to_css(&ctx)[0].declarations;
+        assert_eq!(declarations.iter().filter(|(property, _)| property == "margin").count(), 1);
+    }
+}
@@ -0,0 +1,109 @@
+use egui::Ui;
+use crate::elements::texts::text::{Text, TextSpan, TextTag};
+use crate::elements::UIElement;
+
+// Структура для работы со свойствами текста.
+// Шрифт, цвет и выравнивание редактируются в общем инспекторе свойств
+#[derive(Default)]
+pub struct TextProperty;
+
+impl TextProperty {
+    pub fn new() -> Self {
+        Self
+    }
+    
+    // Метод для отображения и редактирования свойств текста
+    pub fn show(&mut self, ui: &mut Ui, element: &mut Box<dyn UIElement>) -> bool {
+        let Some(text) = element.as_any_mut().downcast_mut::<Text>() else {
+            return false;
+        };
+        
+        let mut changed = false;
+        
+        // Семантический тег
+        ui.label("Тип текста:");
+        let mut tag = text.tag;
+        egui::ComboBox::from_id_source("text_tag")
+            .selected_text(tag.label())
+            .show_ui(ui, |ui| {
+                for option in TextTag::ALL {
+                    ui.selectable_value(&mut tag, option, option.label());
+                }
+            });
+        if tag != text.tag {
+            // При смене тега подставляется размер шрифта по умолчанию, если он не менялся
+            text.set_tag(tag);
+            changed = true;
+        }
+        
+        ui.separator();
+        changed |= Self::show_spans(ui, text);
+        
+        changed
+    }
+    
+    // Редактор фрагментов текста с форматированием
+    fn show_spans(ui: &mut Ui, text: &mut Text) -> bool {
+        let mut changed = false;
+        let mut remove_index = None;
+        let can_remove = text.spans.len() > 1;
+        
+        ui.heading("Содержимое");
+        
+        for (index, span) in text.spans.iter_mut().enumerate() {
+            ui.push_id(index, |ui| {
+                changed |= ui.add(
+                    egui::TextEdit::multiline(&mut span.text)
+                        .desired_rows(2)
+                        .desired_width(ui.available_width())
+                ).changed();
+                
+                ui.horizontal(|ui| {
+                    if ui.selectable_label(span.bold, egui::RichText::new("Ж").strong()).on_hover_text("Жирный").clicked() {
+                        span.bold = !span.bold;
+                        changed = true;
+                    }
+                    if ui.selectable_label(span.italic, egui::RichText::new("К").italics()).on_hover_text("Курсив").clicked() {
+                        span.italic = !span.italic;
+                        changed = true;
+                    }
+                    if ui.selectable_label(span.underline, egui::RichText::new("Ч").underline()).on_hover_text("Подчеркнутый").clicked() {
+                        span.underline = !span.underline;
+                        changed = true;
+                    }
+                    
+                    let mut is_link = span.link.is_some();
+                    if ui.checkbox(&mut is_link, "Ссылка").changed() {
+                        span.link = if is_link { Some("https://".to_string()) } else { None };
+                        changed = true;
+                    }
+                    
+                    if can_remove && ui.small_button("Удалить").clicked() {
+                        remove_index = Some(index);
+                    }
+                });
+                
+                if let Some(link) = span.link.as_mut() {
+                    ui.horizontal(|ui| {
+                        ui.label("Адрес:");
+                        changed |= ui.text_edit_singleline(link).changed();
+                    });
+                }
+                
+                ui.add_space(4.0);
+            });
+        }
+        
+        if let Some(index) = remove_index {
+            text.spans.remove(index);
+            changed = true;
+        }
+        
+        if ui.button("Добавить фрагмент").clicked() {
+            text.spans.push(TextSpan::new(" "));
+            changed = true;
+        }
+        
+        changed
+    }
+}
@@ -1,29 +1,31 @@
 mod models;
 pub mod elements;
 mod editor;
+mod app;
+mod utils;
 
 use eframe::{App, CreationContext};
-use models::page::Page;
+use models::site::Site;
 use editor::Editor;
 
 // Основной класс приложения
 struct SiteGeneratorApp {
     editor: Editor,
-    current_page: Page,
+    site: Site,
 }
 
 impl SiteGeneratorApp {
-    fn new(_cc: &CreationContext) -> Self {
-        // Создаем тестовую страницу
-        let page = Page::new(
-            "home".to_string(),
-            "Главная страница".to_string(),
-            "index.html".to_string()
-        );
+    fn new(cc: &CreationContext) -> Self {
+        // Загрузчики изображений для отображения картинок на холсте
+        egui_extras::install_image_loaders(&cc.egui_ctx);
         
+        // Типы элементов для загрузки проектов и виды элементов для палитры
+        elements::registry::register_builtin_elements();
+        
+        // Создаем сайт с главной страницей
         Self {
             editor: Editor::new(),
-            current_page: page,
+            site: Site::new(),
         }
     }
 }
@@ -32,7 +34,7 @@ impl App for SiteGeneratorApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show(ctx, |ui| {
             // Показываем редактор
-            self.editor.show(ctx, ui, &mut self.current_page);
+            self.editor.show(ctx, ui, &mut self.site);
         });
     }
 }
@@ -1,4 +1,5 @@
 
+use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
@@ -6,13 +7,15 @@ use uuid::Uuid;
 use crate::elements::UIElement;
 
 
-// Убираем derive для структуры Page
+// Clone реализован через UIElement::clone_box, сериализация элементов - через serde_elements
 #[allow(dead_code)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Page {
     pub id: String,
     pub name: String,
     pub title: String,
     pub file_name: String,
+    #[serde(with = "crate::elements::serde_elements")]
     pub elements: Vec<Box<dyn UIElement>>,
     pub meta_tags: HashMap<String, String>,
     pub styles: HashMap<String, String>,
@@ -33,29 +36,289 @@ impl Page {
         }
     }
     
+    // Создает копию страницы с новым идентификатором; элементы копии тоже получают новые
+    // идентификаторы, чтобы не совпадали классы CSS, якоря ссылок и записи истории
+    pub fn duplicate(&self, name: String, file_name: String) -> Self {
+        let mut page = self.clone();
+        page.id = Uuid::new_v4().to_string();
+        page.name = name;
+        page.file_name = file_name;
+        for element in page.elements.iter_mut() {
+            element.regenerate_ids();
+        }
+        page
+    }
+    
     // Добавление элемента на страницу
     pub fn add_element(&mut self, element: Box<dyn UIElement>) {
         self.elements.push(element);
     }
     
-    // Удаление элемента со страницы
-    pub fn remove_element(&mut self, element_id: &str) {
-        self.elements.retain(|e| e.get_id() != element_id);
+    // Добавление элемента в контейнер (или на страницу, если контейнер не указан)
+    pub fn insert_element(&mut self, parent_id: Option<&str>, element: Box<dyn UIElement>) {
+        match parent_id.and_then(|id| self.find_element_mut(id)).and_then(|p| p.children_mut()) {
+            Some(children) => children.push(element),
+            None => self.add_element(element),
+        }
+    }
+    
+    // Вставка элемента в контейнер (или на страницу) на заданную позицию списка
+    pub fn insert_element_at(&mut self, parent_id: Option<&str>, index: usize, element: Box<dyn UIElement>) {
+        if let Some(children) = parent_id.and_then(|id| self.find_element_mut(id)).and_then(|p| p.children_mut()) {
+            let index = index.min(children.len());
+            children.insert(index, element);
+            return;
+        }
+        let index = index.min(self.elements.len());
+        self.elements.insert(index, element);
+    }
+    
+    // Заменяет элемент с тем же ID новым состоянием
+    pub fn replace_element(&mut self, element: Box<dyn UIElement>) -> bool {
+        match self.find_element_mut(element.get_id()) {
+            Some(slot) => {
+                *slot = element;
+                true
+            },
+            None => false,
+        }
+    }
+    
+    // Родитель элемента и его индекс в списке родителя
+    pub fn element_location(&self, element_id: &str) -> Option<(Option<String>, usize)> {
+        let parent_id = self.parent_id(element_id);
+        let siblings = match &parent_id {
+            Some(id) => find_in(&self.elements, id)?.children()?,
+            None => &self.elements,
+        };
+        let index = siblings.iter().position(|e| e.get_id() == element_id)?;
+        Some((parent_id, index))
+    }
+    
+    // Удаление элемента со страницы (на любом уровне вложенности)
+    pub fn remove_element(&mut self, element_id: &str) -> Option<Box<dyn UIElement>> {
+        remove_in(&mut self.elements, element_id)
     }
     
     // Поиск элемента по ID
-    pub fn find_element(&self, element_id: &str) -> Option<&Box<dyn UIElement>> {
-        self.elements.iter().find(|e| e.get_id() == element_id)
+    pub fn find_element(&self, element_id: &str) -> Option<&dyn UIElement> {
+        find_in(&self.elements, element_id)
     }
     
     // Поиск элемента для редактирования по ID
     pub fn find_element_mut(&mut self, element_id: &str) -> Option<&mut Box<dyn UIElement>> {
-        self.elements.iter_mut().find(|e| e.get_id() == element_id)
+        find_in_mut(&mut self.elements, element_id)
+    }
+    
+    // Поиск самого глубоко вложенного элемента в точке (x, y) в координатах страницы
+    pub fn find_element_at_point(&self, point: (f32, f32)) -> Option<&dyn UIElement> {
+        find_at_point_in(&self.elements, point)
+    }
+    
+    // Поиск контейнера в точке, в который можно поместить элемент `exclude_id`
+    // (сам элемент и его потомки не рассматриваются)
+    pub fn find_container_at_point(&self, point: (f32, f32), exclude_id: Option<&str>) -> Option<&dyn UIElement> {
+        find_container_in(&self.elements, point, exclude_id)
+    }
+    
+    // ID родительского контейнера элемента (None - элемент лежит на странице)
+    pub fn parent_id(&self, element_id: &str) -> Option<String> {
+        parent_in(&self.elements, element_id, None).flatten()
+    }
+    
+    // Позиция элемента в координатах страницы
+    pub fn absolute_position(&self, element_id: &str) -> Option<(f32, f32)> {
+        absolute_position_in(&self.elements, element_id, (0.0, 0.0))
+    }
+    
+    // Начало координат родителя элемента в координатах страницы
+    pub fn parent_origin(&self, element_id: &str) -> (f32, f32) {
+        self.parent_id(element_id)
+            .and_then(|parent| self.absolute_position(&parent))
+            .unwrap_or((0.0, 0.0))
+    }
+    
+    // Переносит элемент в другой контейнер (или на страницу), сохраняя его положение на экране
+    pub fn move_to_parent(&mut self, element_id: &str, new_parent_id: Option<&str>) -> bool {
+        if self.parent_id(element_id).as_deref() == new_parent_id {
+            return false;
+        }
+        self.move_element(element_id, new_parent_id, usize::MAX)
+    }
+    
+    // Перемещение элемента в контейнер (или на страницу) на позицию index среди текущих
+    // элементов нового родителя; положение элемента на странице сохраняется
+    pub fn move_element(&mut self, element_id: &str, new_parent_id: Option<&str>, index: usize) -> bool {
+        // Нельзя переместить контейнер в самого себя или в своего потомка
+        if let Some(parent_id) = new_parent_id {
+            let inside = self.find_element(element_id)
+                .and_then(|e| e.children())
+                .is_some_and(|children| find_in(children, parent_id).is_some());
+            if parent_id == element_id || inside {
+                return false;
+            }
+        }
+        
+        let Some((old_parent_id, old_index)) = self.element_location(element_id) else {
+            return false;
+        };
+        let Some(absolute) = self.absolute_position(element_id) else {
+            return false;
+        };
+        let new_origin = new_parent_id
+            .and_then(|id| self.absolute_position(id))
+            .unwrap_or((0.0, 0.0));
+        
+        // После удаления элемента из того же списка позиции за ним сдвигаются
+        let index = if old_parent_id.as_deref() == new_parent_id && old_index < index {
+            index - 1
+        } else {
+            index
+        };
+        
+        let Some(mut element) = self.remove_element(element_id) else {
+            return false;
+        };
+        element.set_position((absolute.0 - new_origin.0, absolute.1 - new_origin.1));
+        self.insert_element_at(new_parent_id, index, element);
+        true
+    }
+}
+
+// Имя файла страницы - путь внутри папки экспорта: вложенные папки через /,
+// без .., корня, диска и обратной косой черты, чтобы экспорт не вышел за пределы папки
+pub fn is_valid_file_name(file_name: &str) -> bool {
+    !file_name.is_empty() && sanitize_file_name(file_name) == file_name
+}
+
+// Приводит имя файла страницы к допустимому: убирает корень, диск, пустые части, . и ..
+// (пустой результат заменяется именем по умолчанию)
+pub fn sanitize_file_name(file_name: &str) -> String {
+    let parts: Vec<&str> = file_name
+        .split(['/', '\\'])
+        // Двоеточие бывает только в имени диска (C:), в именах файлов Windows оно запрещено
+        .filter(|part| !part.is_empty() && *part != "." && *part != ".." && !part.contains(':'))
+        .collect();
+    if parts.is_empty() {
+        "page.html".to_string()
+    } else {
+        parts.join("/")
     }
+}
+
+fn find_in<'a>(elements: &'a [Box<dyn UIElement>], element_id: &str) -> Option<&'a dyn UIElement> {
+    for element in elements {
+        if element.get_id() == element_id {
+            return Some(element.as_ref());
+        }
+        if let Some(found) = element.children().and_then(|children| find_in(children, element_id)) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+fn find_in_mut<'a>(elements: &'a mut [Box<dyn UIElement>], element_id: &str) -> Option<&'a mut Box<dyn UIElement>> {
+    for element in elements.iter_mut() {
+        if element.get_id() == element_id {
+            return Some(element);
+        }
+        if let Some(found) = element.children_mut().and_then(|children| find_in_mut(children, element_id)) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+fn remove_in(elements: &mut Vec<Box<dyn UIElement>>, element_id: &str) -> Option<Box<dyn UIElement>> {
+    if let Some(index) = elements.iter().position(|e| e.get_id() == element_id) {
+        return Some(elements.remove(index));
+    }
+    elements.iter_mut()
+        .filter_map(|e| e.children_mut())
+        .find_map(|children| remove_in(children, element_id))
+}
+
+// Элементы, нарисованные позже, лежат выше, поэтому обходим список с конца
+fn find_at_point_in(elements: &[Box<dyn UIElement>], point: (f32, f32)) -> Option<&dyn UIElement> {
+    // Скрытые и заблокированные элементы мышью не выбираются
+    let element = elements.iter().rev().find(|e| e.base().is_interactive() && e.contains_point(point))?;
+    let position = element.get_position();
+    let local = (point.0 - position.0, point.1 - position.1);
     
-    // Поиск элемента в точке (x, y)
-    pub fn find_element_at_point(&self, point: (f32, f32)) -> Option<&Box<dyn UIElement>> {
-        self.elements.iter().rev().find(|e| e.contains_point(point))
+    element.children()
+        .and_then(|children| find_at_point_in(children, local))
+        .or(Some(element.as_ref()))
+}
+
+fn find_container_in<'a>(
+    elements: &'a [Box<dyn UIElement>],
+    point: (f32, f32),
+    exclude_id: Option<&str>,
+) -> Option<&'a dyn UIElement> {
+    for element in elements.iter().rev() {
+        if Some(element.get_id()) == exclude_id || !element.base().is_interactive() || !element.contains_point(point) {
+            continue;
+        }
+        let Some(children) = element.children() else {
+            continue;
+        };
+        
+        let position = element.get_position();
+        let local = (point.0 - position.0, point.1 - position.1);
+        return find_container_in(children, local, exclude_id).or(Some(element.as_ref()));
     }
+    None
 }
 
+// Возвращает Some(родитель), если элемент найден
+fn parent_in(elements: &[Box<dyn UIElement>], element_id: &str, parent: Option<&str>) -> Option<Option<String>> {
+    for element in elements {
+        if element.get_id() == element_id {
+            return Some(parent.map(|p| p.to_string()));
+        }
+        if let Some(found) = element.children().and_then(|children| parent_in(children, element_id, Some(element.get_id()))) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+fn absolute_position_in(elements: &[Box<dyn UIElement>], element_id: &str, origin: (f32, f32)) -> Option<(f32, f32)> {
+    for element in elements {
+        let position = element.get_position();
+        let absolute = (origin.0 + position.0, origin.1 + position.1);
+        if element.get_id() == element_id {
+            return Some(absolute);
+        }
+        if let Some(found) = element.children().and_then(|children| absolute_position_in(children, element_id, absolute)) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_names_stay_inside_export_dir() {
+        assert!(is_valid_file_name("index.html"));
+        assert!(is_valid_file_name("blog/about.html"));
+        assert!(!is_valid_file_name(""));
+        assert!(!is_valid_file_name("../x.html"));
+        assert!(!is_valid_file_name("/etc/x.html"));
+        assert!(!is_valid_file_name("blog\\about.html"));
+        assert!(!is_valid_file_name("C:/x.html"));
+        assert!(!is_valid_file_name("blog//about.html"));
+    }
+
+    #[test]
+    fn sanitize_removes_escaping_parts() {
+        assert_eq!(sanitize_file_name("../x.html"), "x.html");
+        assert_eq!(sanitize_file_name("/etc/../x.html"), "etc/x.html");
+        assert_eq!(sanitize_file_name("C:\\site\\.\\x.html"), "site/x.html");
+        assert_eq!(sanitize_file_name("../.."), "page.html");
+    }
+}
@@ -0,0 +1,286 @@
+use serde::{Serialize, Deserialize};
+use std::borrow::Cow;
+use uuid::Uuid;
+
+use crate::elements::breakpoints::{switch_breakpoints, take_overrides, BreakpointOverride};
+use crate::models::page::{self, Page};
+
+// Ширина экрана, для которой элементы могут выглядеть по-своему
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Breakpoint {
+    pub id: String,
+    pub name: String,
+    // Ширина холста в редакторе и верхняя граница диапазона на сайте
+    pub width: f32,
+}
+
+impl Breakpoint {
+    pub fn new(name: String, width: f32) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            name,
+            width,
+        }
+    }
+}
+
+// Общие настройки сайта
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct SiteSettings {
+    pub name: String,
+    // Базовый URL, по которому будет опубликован сайт
+    pub base_url: String,
+    // Язык по умолчанию (атрибут lang у <html>)
+    pub default_language: String,
+    // Путь к иконке сайта
+    pub favicon: Option<String>,
+    // Ширины экрана; первая - основная, остальные переопределяют ее на более узких экранах
+    pub breakpoints: Vec<Breakpoint>,
+    // Цвета сайта в записи CSS; палитры выбора цвета показывают их образцами
+    pub palette: Vec<String>,
+}
+
+impl Default for SiteSettings {
+    fn default() -> Self {
+        Self {
+            name: "Мой сайт".to_string(),
+            base_url: String::new(),
+            default_language: "ru".to_string(),
+            favicon: None,
+            breakpoints: vec![
+                Breakpoint { id: "desktop".to_string(), name: "Компьютер".to_string(), width: 1200.0 },
+                Breakpoint { id: "tablet".to_string(), name: "Планшет".to_string(), width: 991.0 },
+                Breakpoint { id: "mobile".to_string(), name: "Телефон".to_string(), width: 575.0 },
+            ],
+            palette: vec![
+                "#1E88E5".to_string(),
+                "#4CAF50".to_string(),
+                "#333333".to_string(),
+                "#F5F5F5".to_string(),
+                "#FFFFFF".to_string(),
+            ],
+        }
+    }
+}
+
+impl SiteSettings {
+    // Основная ширина сайта
+    pub fn base_breakpoint(&self) -> Option<&Breakpoint> {
+        self.breakpoints.first()
+    }
+
+    // Ширина по идентификатору; None и основная ширина дают основную
+    pub fn breakpoint(&self, breakpoint_id: Option<&str>) -> Option<&Breakpoint> {
+        breakpoint_id
+            .and_then(|id| self.breakpoints.iter().find(|b| b.id == id))
+            .or_else(|| self.base_breakpoint())
+    }
+
+    // Переопределяющие ширины от широкой к узкой
+    pub fn narrower_breakpoints(&self) -> Vec<&Breakpoint> {
+        let mut breakpoints: Vec<&Breakpoint> = self.breakpoints.iter().skip(1).collect();
+        breakpoints.sort_by(|a, b| b.width.total_cmp(&a.width));
+        breakpoints
+    }
+
+    // Медиазапросы ширин (None - основная ширина). Каждая ширина действует от своего
+    // значения до следующей более узкой, основная - выше самой широкой из остальных.
+    // Если других ширин нет, у основной медиазапроса нет
+    pub fn media_queries(&self) -> Vec<(Option<&str>, Option<String>)> {
+        let narrower = self.narrower_breakpoints();
+        let mut queries = vec![(None, narrower.first().map(|b| format!("(min-width: {}px)", b.width + 1.0)))];
+        for (index, breakpoint) in narrower.iter().enumerate() {
+            let query = match narrower.get(index + 1) {
+                Some(next) => format!("(min-width: {}px) and (max-width: {}px)", next.width + 1.0, breakpoint.width),
+                None => format!("(max-width: {}px)", breakpoint.width),
+            };
+            queries.push((Some(breakpoint.id.as_str()), Some(query)));
+        }
+        queries
+    }
+}
+
+// Сайт: упорядоченный набор страниц и общие настройки
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Site {
+    pub settings: SiteSettings,
+    pub pages: Vec<Page>,
+    // Индекс страницы, открытой в редакторе
+    #[serde(skip)]
+    pub current_page: usize,
+    // Ширина экрана, выбранная в редакторе (None - основная); у элементов подставлены ее значения
+    #[serde(skip)]
+    pub active_breakpoint: Option<String>,
+}
+
+impl Default for Site {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Site {
+    // Создает сайт с одной главной страницей
+    pub fn new() -> Self {
+        let home = Page::new(
+            "home".to_string(),
+            "Главная страница".to_string(),
+            "index.html".to_string()
+        );
+
+        Self {
+            settings: SiteSettings::default(),
+            pages: vec![home],
+            current_page: 0,
+            active_breakpoint: None,
+        }
+    }
+
+    // Текущая страница редактора
+    pub fn current_page(&self) -> &Page {
+        &self.pages[self.current_page]
+    }
+
+    pub fn current_page_mut(&mut self) -> &mut Page {
+        &mut self.pages[self.current_page]
+    }
+
+    // Переключение на страницу по индексу
+    pub fn select_page(&mut self, index: usize) {
+        if index < self.pages.len() {
+            self.current_page = index;
+        }
+    }
+
+    // Переключает элементы всех страниц на ширину экрана breakpoint_id (None - основная)
+    pub fn set_breakpoint(&mut self, breakpoint_id: Option<String>) {
+        // Основная и неизвестная ширины приводятся к None
+        let breakpoint_id = breakpoint_id.filter(|id| {
+            self.settings.breakpoints.iter().skip(1).any(|b| b.id == *id)
+        });
+        if breakpoint_id == self.active_breakpoint {
+            return;
+        }
+
+        let from = self.active_breakpoint.take();
+        for page in &mut self.pages {
+            switch_breakpoints(&mut page.elements, from.as_deref(), breakpoint_id.as_deref());
+        }
+        self.active_breakpoint = breakpoint_id;
+    }
+
+    // Убирает у элементов всех страниц отличия ширины breakpoint_id (когда ширина удалена)
+    // и возвращает их с идентификаторами страниц и элементов, чтобы удаление можно было отменить
+    pub fn take_breakpoint_overrides(&mut self, breakpoint_id: &str) -> Vec<(String, String, BreakpointOverride)> {
+        let mut overrides = Vec::new();
+        for page in &mut self.pages {
+            let mut taken = Vec::new();
+            take_overrides(&mut page.elements, breakpoint_id, &mut taken);
+            overrides.extend(taken.into_iter().map(|(element_id, values)| (page.id.clone(), element_id, values)));
+        }
+        overrides
+    }
+
+    // Возвращает элементам отличия ширины breakpoint_id, убранные take_breakpoint_overrides
+    pub fn restore_breakpoint_overrides(&mut self, breakpoint_id: &str, overrides: &[(String, String, BreakpointOverride)]) {
+        for (page_id, element_id, values) in overrides {
+            let element = self.page_index(page_id)
+                .and_then(|index| self.pages[index].find_element_mut(element_id));
+            if let Some(element) = element {
+                element.base_mut().overrides.insert(breakpoint_id.to_string(), values.clone());
+            }
+        }
+    }
+
+    // Сайт с основными значениями элементов - для сохранения и экспорта
+    pub fn with_base_breakpoint(&self) -> Cow<'_, Site> {
+        if self.active_breakpoint.is_none() {
+            return Cow::Borrowed(self);
+        }
+        let mut site = self.clone();
+        site.set_breakpoint(None);
+        Cow::Owned(site)
+    }
+
+    // Добавляет новую пустую страницу и делает ее текущей
+    pub fn add_page(&mut self) -> usize {
+        let name = self.unique_page_name("page");
+        let file_name = self.unique_file_name(&name);
+        let page = Page::new(name.clone(), name, file_name);
+
+        self.pages.push(page);
+        self.current_page = self.pages.len() - 1;
+        self.current_page
+    }
+
+    // Дублирует страницу и вставляет копию сразу после оригинала
+    pub fn duplicate_page(&mut self, index: usize) -> Option<usize> {
+        let source = self.pages.get(index)?;
+        let name = self.unique_page_name(&format!("{}-copy", source.name));
+        let file_name = self.unique_file_name(&name);
+        let mut copy = source.duplicate(name, file_name);
+        copy.title = format!("{} (копия)", source.title);
+
+        self.pages.insert(index + 1, copy);
+        self.current_page = index + 1;
+        Some(self.current_page)
+    }
+
+    // Удаляет страницу; последняя страница сайта не удаляется
+    pub fn remove_page(&mut self, index: usize) -> bool {
+        if self.pages.len() <= 1 || index >= self.pages.len() {
+            return false;
+        }
+
+        self.pages.remove(index);
+        if self.current_page > index || self.current_page >= self.pages.len() {
+            self.current_page = self.current_page.saturating_sub(1);
+        }
+        true
+    }
+
+    // Индекс страницы по идентификатору
+    pub fn page_index(&self, page_id: &str) -> Option<usize> {
+        self.pages.iter().position(|p| p.id == page_id)
+    }
+
+    // Перемещает страницу на новую позицию, сохраняя выбор текущей страницы
+    pub fn move_page(&mut self, from: usize, to: usize) {
+        if from >= self.pages.len() || to >= self.pages.len() || from == to {
+            return;
+        }
+
+        let current_id = self.current_page().id.clone();
+        let page = self.pages.remove(from);
+        self.pages.insert(to, page);
+
+        if let Some(index) = self.pages.iter().position(|p| p.id == current_id) {
+            self.current_page = index;
+        }
+    }
+
+    // Подбирает имя страницы, которого еще нет на сайте
+    fn unique_page_name(&self, base: &str) -> String {
+        let mut name = base.to_string();
+        let mut counter = 2;
+        while self.pages.iter().any(|p| p.name == name) {
+            name = format!("{}-{}", base, counter);
+            counter += 1;
+        }
+        name
+    }
+
+    // Подбирает имя HTML-файла, которого еще нет на сайте; имя страницы может быть любым,
+    // поэтому основа имени приводится к допустимому пути
+    fn unique_file_name(&self, base: &str) -> String {
+        let base = page::sanitize_file_name(base);
+        let mut file_name = format!("{}.html", base);
+        let mut counter = 2;
+        while self.pages.iter().any(|p| p.file_name == file_name) {
+            file_name = format!("{}-{}.html", base, counter);
+            counter += 1;
+        }
+        file_name
+    }
+}
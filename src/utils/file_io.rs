@@ -0,0 +1,85 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Записывает значение в файл в формате JSON с отступами
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    // Пишем во временный файл рядом с целевым и переименовываем, чтобы не испортить проект при сбое.
+    // Имя временного файла включает полное имя целевого и номер процесса, чтобы не задеть соседние файлы
+    let file_name = path.file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Путь не указывает на файл"))?;
+    let tmp_path = path.with_file_name(format!("{}.{}.tmp", file_name.to_string_lossy(), std::process::id()));
+    let result = fs::write(&tmp_path, json).and_then(|()| fs::rename(&tmp_path, path));
+    // При ошибке временный файл не должен остаться в папке проекта
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+// Читает JSON-файл и разбирает его в значение нужного типа
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+    let json = fs::read_to_string(path)?;
+    serde_json::from_str(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// Копирует файл в папку, подбирая свободное имя, и возвращает путь к копии
+pub fn copy_to_dir_unique(source: &Path, dir: &Path) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+
+    let file_name = source.file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Путь не указывает на файл"))?;
+    let target = dir.join(unique_file_name(file_name, |name| dir.join(name).exists()));
+
+    fs::copy(source, &target)?;
+    Ok(target)
+}
+
+// Имя файла, которое еще не занято: к имени добавляется номер (logo-2.png, logo-3.png, ...)
+pub fn unique_file_name(file_name: &OsStr, taken: impl Fn(&str) -> bool) -> String {
+    let name = file_name.to_string_lossy().to_string();
+    let stem = Path::new(file_name).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let extension = Path::new(file_name).extension().map(|e| e.to_string_lossy().to_string());
+
+    let mut candidate = name;
+    let mut counter = 2;
+    while taken(&candidate) {
+        candidate = match &extension {
+            Some(ext) => format!("{}-{}.{}", stem, counter, ext),
+            None => format!("{}-{}", stem, counter),
+        };
+        counter += 1;
+    }
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_write_leaves_no_temp_file() {
+        let dir = std::env::temp_dir().join(format!("file-io-tmp-{}", std::process::id()));
+        // На месте файла лежит папка: переименование временного файла не удастся
+        let path = dir.join("site.sgproj");
+        fs::create_dir_all(path.join("inner")).unwrap();
+
+        assert!(write_json(&path, &vec![1, 2, 3]).is_err());
+        let names: Vec<_> = fs::read_dir(&dir).unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(names, vec![std::ffi::OsString::from("site.sgproj")]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
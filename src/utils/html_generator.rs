@@ -1,162 +1,356 @@
-use crate::models::element::{Element, ElementType};
-use crate::models::page::Page;
+use crate::app::project;
+use crate::elements::{children_css, UIElement};
+use crate::elements::breakpoints::breakpoints_css;
+use crate::elements::export::ExportContext;
+use crate::models::page::{self, Page};
+use crate::models::site::Site;
+use crate::utils::file_io;
 use std::collections::HashMap;
-use std::fs::{self, File};
-use std::io::Write;
-use std::path::Path;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Шаблон страницы по умолчанию встраивается в программу
+const PAGE_TEMPLATE: &str = include_str!("../../templates/page_template.html");
+
+// Экранирует спецсимволы HTML в тексте и значениях атрибутов
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Подставляет значения вместо {имя} за один проход по шаблону: подставленный текст
+// (заголовок, стили, содержимое страницы) не просматривается повторно
+fn fill_template(template: &str, values: &[(&str, &str)]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let placeholder = values.iter().find(|(name, _)| {
+            rest[1..].strip_prefix(name).is_some_and(|tail| tail.starts_with('}'))
+        });
+        match placeholder {
+            Some((name, value)) => {
+                result.push_str(value);
+                rest = &rest[name.len() + 2..];
+            },
+            // Обычная фигурная скобка, например в стилях шаблона
+            None => {
+                result.push('{');
+                rest = &rest[1..];
+            },
+        }
+    }
+    result.push_str(rest);
+    result
+}
 
 pub struct HtmlGenerator;
 
 impl HtmlGenerator {
-    // Генерирует HTML для страницы
-    pub fn generate_html(page: &Page, output_dir: &str) -> Result<String, std::io::Error> {
-        // Создаем директорию для экспорта, если она не существует
-        fs::create_dir_all(output_dir)?;
+    // Генерирует HTML и таблицу стилей страницы и записывает их в папку экспорта
+    pub fn generate_html(site: &Site, page: &Page, ctx: &ExportContext, output_dir: &Path) -> io::Result<PathBuf> {
+        // Ссылки на другие страницы и пути к ресурсам строятся относительно текущей
+        let ctx = &ExportContext {
+            current_page_id: Some(page.id.clone()),
+            document: page.file_name.clone(),
+            ..ctx.clone()
+        };
         
-        // Путь к файлу страницы
-        let file_path = format!("{}/{}", output_dir, page.file_name);
+        // Путь к файлу страницы; имя с .. или корнем вывело бы запись за пределы папки экспорта
+        if !page::is_valid_file_name(&page.file_name) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Недопустимое имя файла страницы: {}", page.file_name),
+            ));
+        }
+        let file_path = output_dir.join(&page.file_name);
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
         
-        // Читаем шаблон страницы
-        let template = fs::read_to_string("templates/page_template.html")
-            .unwrap_or_else(|_| "<!DOCTYPE html><html><head><title>{title}</title>{head}</head><body>{body}</body></html>".to_string());
+        // Стили элементов записываются в отдельный файл страницы; url() в нем отсчитываются от него самого
+        let stylesheet = Self::stylesheet_path(page);
+        let stylesheet_path = output_dir.join(&stylesheet);
+        if let Some(parent) = stylesheet_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let css_ctx = ExportContext {
+            document: stylesheet.clone(),
+            ..ctx.clone()
+        };
+        fs::write(stylesheet_path, Self::generate_elements_css(&page.elements, site, &css_ctx))?;
         
         // Генерируем метатеги
         let mut meta_tags = String::new();
-        for (name, content) in &page.meta_tags {
-            meta_tags.push_str(&format!("<meta name=\"{}\" content=\"{}\">\n", name, content));
+        let mut meta_names: Vec<&String> = page.meta_tags.keys().collect();
+        meta_names.sort();
+        for name in meta_names {
+            meta_tags.push_str(&format!("    <meta name=\"{}\" content=\"{}\">\n",
+                escape_html(name), escape_html(&page.meta_tags[name])));
         }
         
-        // Генерируем стили
+        // Иконка сайта копируется в корень экспорта
+        if let Some(favicon) = &ctx.favicon {
+            meta_tags.push_str(&format!("    <link rel=\"icon\" href=\"{}\">\n", escape_html(&ctx.file_url(favicon))));
+        }
+        
+        // Подключаем таблицу стилей элементов
+        meta_tags.push_str(&format!("    <link rel=\"stylesheet\" href=\"{}\">\n", escape_html(&ctx.file_url(&stylesheet))));
+        
+        // Генерируем стили страницы
         let mut styles = String::new();
-        styles.push_str("<style>\n");
-        for (selector, style) in &page.styles {
-            styles.push_str(&format!("{} {{ {} }}\n", selector, style));
+        if !page.styles.is_empty() {
+            let mut selectors: Vec<&String> = page.styles.keys().collect();
+            selectors.sort();
+            styles.push_str("    <style>\n");
+            for selector in selectors {
+                styles.push_str(&format!("        {} {{ {} }}\n", selector, page.styles[selector]));
+            }
+            styles.push_str("    </style>\n");
         }
-        styles.push_str("</style>\n");
         
         // Генерируем скрипты
         let mut scripts = String::new();
         for script in &page.scripts {
-            scripts.push_str(&format!("<script>{}</script>\n", script));
+            scripts.push_str(&format!("    <script>{}</script>\n", script));
         }
         
         // Собираем head
-        let head = format!("{}\n{}\n{}", meta_tags, styles, scripts);
+        let head = format!("{}{}{}", meta_tags, styles, scripts);
         
         // Генерируем HTML для элементов
-        let body = Self::generate_elements_html(&page.elements);
+        let body = Self::generate_elements_html(&page.elements, ctx);
         
         // Подставляем в шаблон
-        let html = template
-            .replace("{title}", &page.title)
-            .replace("{head}", &head)
-            .replace("{body}", &body);
+        let html = fill_template(PAGE_TEMPLATE, &[
+            ("lang", &escape_html(&site.settings.default_language)),
+            ("title", &escape_html(&page.title)),
+            ("head", head.trim_end()),
+            ("body", body.trim_end()),
+        ]);
         
         // Записываем в файл
-        let mut file = File::create(&file_path)?;
-        file.write_all(html.as_bytes())?;
+        fs::write(&file_path, html)?;
         
         Ok(file_path)
     }
     
-    // Генерирует HTML для элементов
-    fn generate_elements_html(elements: &[Element]) -> String {
+    // Генерирует HTML для элементов: каждый элемент сам формирует свою разметку
+    fn generate_elements_html(elements: &[Box<dyn UIElement>], ctx: &ExportContext) -> String {
         let mut html = String::new();
         
         for element in elements {
-            html.push_str(&Self::generate_element_html(element));
+            html.push_str("    ");
+            html.push_str(&element.to_html(ctx));
+            html.push('\n');
         }
         
         html
     }
     
-    // Генерирует HTML для одного элемента
-    fn generate_element_html(element: &Element) -> String {
-        let mut style = String::new();
-        for (key, value) in &element.styles {
-            style.push_str(&format!("{}:{};", key, value));
-        }
+    // Собирает правила CSS всех элементов страницы и их отличия на других ширинах экрана
+    fn generate_elements_css(elements: &[Box<dyn UIElement>], site: &Site, ctx: &ExportContext) -> String {
+        let mut css = String::new();
         
-        let mut attributes = String::new();
-        for (key, value) in &element.attributes {
-            if key != "style" {
-                attributes.push_str(&format!(" {}=\"{}\"", key, value));
-            }
+        for rule in children_css(elements, false, ctx) {
+            css.push_str(&rule.to_css());
+            css.push('\n');
         }
         
-        // Позиционирование через CSS
-        style.push_str(&format!("position:absolute;left:{}px;top:{}px;width:{}px;height:{}px;",
-            element.position.0, element.position.1, element.size.0, element.size.1));
-        
-        let style_attr = format!(" style=\"{}\"", style);
-        
-        // Генерируем HTML в зависимости от типа элемента
-        match element.element_type {
-            ElementType::Text => {
-                format!("<div id=\"{}\"{}{}>{}</div>\n", 
-                    element.id, style_attr, attributes, element.content)
-            },
-            ElementType::Button => {
-                format!("<button id=\"{}\"{}{}>{}</button>\n", 
-                    element.id, style_attr, attributes, element.content)
-            },
-            ElementType::Image => {
-                let src = element.attributes.get("src").cloned().unwrap_or_default();
-                let alt = element.attributes.get("alt").cloned().unwrap_or_default();
-                format!("<img id=\"{}\" src=\"{}\" alt=\"{}\"{}>\n", 
-                    element.id, src, alt, style_attr)
-            },
-            ElementType::Container => {
-                let mut container_html = format!("<div id=\"{}\"{}{}>\n", 
-                    element.id, style_attr, attributes);
-                    
-                // Добавляем дочерние элементы
-                for child in &element.children {
-                    container_html.push_str(&Self::generate_element_html(child));
-                }
-                
-                container_html.push_str("</div>\n");
-                container_html
-            },
-            ElementType::Form => {
-                let mut form_html = format!("<form id=\"{}\"{}{}>\n", 
-                    element.id, style_attr, attributes);
-                    
-                // Добавляем дочерние элементы
-                for child in &element.children {
-                    form_html.push_str(&Self::generate_element_html(child));
-                }
-                
-                form_html.push_str("</form>\n");
-                form_html
-            },
-            ElementType::Link => {
-                let href = element.attributes.get("href").cloned().unwrap_or_default();
-                format!("<a id=\"{}\" href=\"{}\"{}{}>{}</a>\n", 
-                    element.id, href, style_attr, attributes, element.content)
-            },
-            ElementType::Custom(ref name) => {
-                format!("<div id=\"{}\" data-type=\"{}\"{}{}>{}</div>\n", 
-                    element.id, name, style_attr, attributes, element.content)
+        css.push_str(&breakpoints_css(elements, &site.settings, ctx));
+        css
+    }
+    
+    // Путь к таблице стилей страницы относительно папки экспорта. Повторяет путь файла страницы,
+    // чтобы у blog/about.html и about.html были разные таблицы
+    fn stylesheet_path(page: &Page) -> String {
+        let path = Path::new(&page.file_name).with_extension("css");
+        format!("css/{}", path.to_string_lossy().replace('\\', "/"))
+    }
+    
+    // Копирует файлы ресурсов элементов в папку изображений экспорта. Каждый файл получает
+    // свое имя, чтобы одноименные файлы из разных папок не затирали друг друга.
+    // Отсутствующие файлы пропускаются и попадают в warnings.
+    // Возвращает имена копий по пути файла в проекте
+    fn copy_assets(
+        site: &Site,
+        project_dir: Option<&Path>,
+        assets_dir: &Path,
+        warnings: &mut Vec<String>,
+    ) -> io::Result<HashMap<String, String>> {
+        let mut names: HashMap<String, String> = HashMap::new();
+        let sources = site.pages.iter()
+            .flat_map(|page| &page.elements)
+            .flat_map(|element| element.assets());
+        for source in sources {
+            if names.contains_key(&source) {
+                continue;
+            }
+            let path = project::resolve_asset(project_dir, &source);
+            let Some(file_name) = path.file_name() else {
+                continue;
+            };
+            if !path.is_file() {
+                warnings.push(missing_file(&path));
+                continue;
             }
+            let name = file_io::unique_file_name(file_name, |name| names.values().any(|taken| taken == name));
+            fs::copy(&path, assets_dir.join(&name))?;
+            names.insert(source, name);
+        }
+        Ok(names)
+    }
+    
+    // Копирует иконку сайта в корень экспорта и возвращает имя копии.
+    // Отсутствующий файл пропускается и попадает в warnings
+    fn copy_favicon(
+        site: &Site,
+        project_dir: Option<&Path>,
+        output_dir: &Path,
+        warnings: &mut Vec<String>,
+    ) -> io::Result<Option<String>> {
+        let Some(source) = &site.settings.favicon else {
+            return Ok(None);
+        };
+        let path = project::resolve_asset(project_dir, source);
+        let Some(file_name) = path.file_name().map(|name| name.to_string_lossy().to_string()) else {
+            return Ok(None);
+        };
+        if !path.is_file() {
+            warnings.push(missing_file(&path));
+            return Ok(None);
         }
+        fs::copy(&path, output_dir.join(&file_name))?;
+        Ok(Some(file_name))
     }
     
     // Экспортирует весь проект сайта
-    pub fn export_project(pages: &[Page], output_dir: &str) -> Result<String, std::io::Error> {
+    // (пути к ресурсам отсчитываются от папки проекта project_dir).
+    // Отсутствующие файлы ресурсов не прерывают экспорт: возвращаются предупреждения о них
+    pub fn export_project(site: &Site, project_dir: Option<&Path>, output_dir: &Path) -> io::Result<Vec<String>> {
+        // Экспортируются основные значения элементов, отличия ширин - в @media
+        let site = &*site.with_base_breakpoint();
+        
         // Создаем директорию для экспорта, если она не существует
         fs::create_dir_all(output_dir)?;
         
         // Создаем директории для ресурсов
-        fs::create_dir_all(format!("{}/css", output_dir))?;
-        fs::create_dir_all(format!("{}/js", output_dir))?;
-        fs::create_dir_all(format!("{}/img", output_dir))?;
+        let mut ctx = ExportContext {
+            page_files: site.pages.iter()
+                .map(|page| (page.id.clone(), page.file_name.clone()))
+                .collect(),
+            ..ExportContext::default()
+        };
+        fs::create_dir_all(output_dir.join("css"))?;
+        fs::create_dir_all(output_dir.join("js"))?;
+        fs::create_dir_all(output_dir.join(&ctx.assets_dir))?;
+        
+        // Копируем иконку сайта и ресурсы элементов в папку изображений
+        let mut warnings = Vec::new();
+        ctx.favicon = Self::copy_favicon(site, project_dir, output_dir, &mut warnings)?;
+        ctx.assets = Self::copy_assets(site, project_dir, &output_dir.join(&ctx.assets_dir), &mut warnings)?;
         
         // Генерируем HTML для каждой страницы
-        for page in pages {
-            Self::generate_html(page, output_dir)?;
+        for page in &site.pages {
+            Self::generate_html(site, page, &ctx, output_dir)?;
         }
         
-        Ok(output_dir.to_string())
+        Ok(warnings)
+    }
+}
+
+fn missing_file(path: &Path) -> String {
+    format!("Файл не найден: {}", path.display())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::images::image::Image;
+
+    #[test]
+    fn template_values_are_not_substituted_again() {
+        let html = fill_template("<title>{title}</title>{body}", &[
+            ("title", "{body}"),
+            ("body", "<p>{title}</p>"),
+        ]);
+        assert_eq!(html, "<title>{body}</title><p>{title}</p>");
+    }
+
+    #[test]
+    fn unknown_braces_are_kept() {
+        assert_eq!(fill_template("body { margin: 0; } {x", &[("x", "1")]), "body { margin: 0; } {x");
+    }
+
+    #[test]
+    fn pages_in_subfolders_get_own_stylesheets() {
+        let page = |file: &str| Page::new("p".to_string(), "p".to_string(), file.to_string());
+        assert_eq!(HtmlGenerator::stylesheet_path(&page("about.html")), "css/about.css");
+        assert_eq!(HtmlGenerator::stylesheet_path(&page("blog/about.html")), "css/blog/about.css");
+    }
+
+    #[test]
+    fn asset_urls_are_relative_to_the_written_file() {
+        let ctx = |document: &str| ExportContext { document: document.to_string(), ..ExportContext::default() };
+        assert_eq!(ctx("index.html").asset_url("a.png"), "img/a.png");
+        assert_eq!(ctx("blog/post.html").asset_url("a.png"), "../img/a.png");
+        assert_eq!(ctx("css/blog/post.css").asset_url("a.png"), "../../img/a.png");
+        assert_eq!(ctx("blog/post.html").file_url("favicon.ico"), "../favicon.ico");
+    }
+
+    #[test]
+    fn same_named_assets_get_own_copies() {
+        let dir = std::env::temp_dir().join(format!("export-assets-{}", std::process::id()));
+        for folder in ["a", "b", "img"] {
+            fs::create_dir_all(dir.join(folder)).unwrap();
+        }
+        fs::write(dir.join("a/logo.png"), "a").unwrap();
+        fs::write(dir.join("b/logo.png"), "b").unwrap();
+
+        let mut site = Site::new();
+        for source in ["a/logo.png", "b/logo.png", "a/logo.png"] {
+            let mut image = Image::new();
+            image.source = source.to_string();
+            site.current_page_mut().add_element(Box::new(image));
+        }
+
+        let mut warnings = Vec::new();
+        let names = HtmlGenerator::copy_assets(&site, Some(&dir), &dir.join("img"), &mut warnings).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(names.len(), 2);
+        assert_ne!(names["a/logo.png"], names["b/logo.png"]);
+        assert_eq!(fs::read_to_string(dir.join("img").join(&names["b/logo.png"])).unwrap(), "b");
+
+        let ctx = ExportContext { assets: names, document: "blog/post.html".to_string(), ..ExportContext::default() };
+        assert_eq!(ctx.asset_url("b/logo.png"), "../img/logo-2.png");
+        fs::remove_dir_all(dir).unwrap();
+    }
+    #[test]
+    fn missing_files_are_skipped_with_warnings() {
+        let dir = std::env::temp_dir().join(format!("export-missing-{}", std::process::id()));
+        let mut site = Site::new();
+        site.settings.favicon = Some("favicon.ico".to_string());
+        let mut image = Image::new();
+        image.source = "assets/photo.png".to_string();
+        site.current_page_mut().add_element(Box::new(image));
+
+        let warnings = HtmlGenerator::export_project(&site, Some(&dir), &dir.join("out")).unwrap();
+        assert_eq!(warnings.len(), 2);
+        let html = fs::read_to_string(dir.join("out/index.html")).unwrap();
+        assert!(!html.contains("rel=\"icon\""));
+        assert!(html.contains("<img"));
+        fs::remove_dir_all(dir).unwrap();
     }
-}
\ No newline at end of file
+}
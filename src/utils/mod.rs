@@ -0,0 +1,2 @@
+pub mod file_io;
+pub mod html_generator;